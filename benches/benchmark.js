@@ -0,0 +1,35 @@
+// Benchmarks of the JS bindings, for costs the Rust benches can't see, such
+// as marshaling results into JS objects. Build the addon and run
+// `npm run bench`.
+
+const { searchIndices, searchArrays } = require("../index.js");
+
+const ITEM_COUNT = 200_000;
+const RUNS = 10;
+
+const items = Array.from({ length: ITEM_COUNT }, (_, i) => `item ${i}`);
+
+/** Fastest of `RUNS` calls of `f`, in milliseconds. */
+function fastest(f) {
+  let best = Infinity;
+  for (let run = 0; run < RUNS; run++) {
+    const start = process.hrtime.bigint();
+    f();
+    const elapsed = Number(process.hrtime.bigint() - start) / 1e6;
+    best = Math.min(best, elapsed);
+  }
+  return best;
+}
+
+// Every item matches, so each call returns `ITEM_COUNT` results and both
+// spend the same time scoring; the difference is the marshaling.
+const options = { limit: null };
+const cases = {
+  "searchIndices (object array)": () => searchIndices("item 1234", items, options),
+  "searchArrays (typed arrays)": () => searchArrays("item 1234", items, options),
+};
+
+console.log(`${ITEM_COUNT} results, fastest of ${RUNS} runs:`);
+for (const [name, f] of Object.entries(cases)) {
+  console.log(`  ${name}: ${fastest(f).toFixed(1)} ms`);
+}
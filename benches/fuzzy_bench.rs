@@ -1,5 +1,12 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use fast_fuzzy_rs::{fuzzy, search, SearchOptions};
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+};
+use fast_fuzzy_rs::{
+    fuzzy, levenshtein_distance_bounded_with, levenshtein_distance_with,
+    normalize_ascii, normalize_string, search, search_indices,
+    LevenshteinBuffer, SearchOptions,
+};
+use napi::Either;
 
 fn create_test_data() -> Vec<String> {
     vec![
@@ -65,7 +72,7 @@ fn bench_fuzzy_basic(c: &mut Criterion) {
             fuzzy(
                 black_box(str1.to_string()),
                 black_box(str2.to_string()),
-                black_box(Some(true)),
+                black_box(Some(Either::A(true))),
             )
         })
     });
@@ -80,7 +87,7 @@ fn bench_fuzzy_no_normalize(c: &mut Criterion) {
             fuzzy(
                 black_box(str1.to_string()),
                 black_box(str2.to_string()),
-                black_box(Some(false)),
+                black_box(Some(Either::A(false))),
             )
         })
     });
@@ -95,7 +102,7 @@ fn bench_fuzzy_unicode(c: &mut Criterion) {
             fuzzy(
                 black_box(str1.to_string()),
                 black_box(str2.to_string()),
-                black_box(Some(true)),
+                black_box(Some(Either::A(true))),
             )
         })
     });
@@ -135,10 +142,11 @@ fn bench_search_with_limit(c: &mut Criterion) {
     let items = create_large_test_data();
     let query = "apple";
     let options = SearchOptions {
-        limit: Some(5),
+        limit: Some(Some(5.0)),
         threshold: Some(0.5),
         normalize: Some(true),
         ignore_case: Some(true),
+        ..Default::default()
     };
 
     c.bench_function("search_with_limit", |b| {
@@ -152,14 +160,34 @@ fn bench_search_with_limit(c: &mut Criterion) {
     });
 }
 
+fn bench_search_large_with_limit(c: &mut Criterion) {
+    let items = create_large_test_data();
+    let query = "berry";
+    let options = SearchOptions {
+        limit: Some(Some(10.0)),
+        ..Default::default()
+    };
+
+    c.bench_function("search_large_with_limit", |b| {
+        b.iter(|| {
+            search(
+                black_box(query.to_string()),
+                black_box(items.clone()),
+                black_box(Some(options.clone())),
+            )
+        })
+    });
+}
+
 fn bench_search_high_threshold(c: &mut Criterion) {
     let items = create_test_data();
     let query = "appl";
     let options = SearchOptions {
-        limit: None,
+        limit: Some(None),
         threshold: Some(0.8),
         normalize: Some(true),
         ignore_case: Some(true),
+        ..Default::default()
     };
 
     c.bench_function("search_high_threshold", |b| {
@@ -177,10 +205,11 @@ fn bench_search_no_normalize(c: &mut Criterion) {
     let items = create_test_data();
     let query = "Apple";
     let options = SearchOptions {
-        limit: None,
+        limit: Some(None),
         threshold: None,
         normalize: Some(false),
         ignore_case: Some(true),
+        ..Default::default()
     };
 
     c.bench_function("search_no_normalize", |b| {
@@ -209,6 +238,107 @@ fn bench_search_unicode(c: &mut Criterion) {
     });
 }
 
+fn bench_search_large_unicode(c: &mut Criterion) {
+    let base_items = create_unicode_test_data();
+    let items: Vec<String> = (0..100)
+        .flat_map(|i| {
+            base_items
+                .iter()
+                .map(move |item| format!("{item} {i}"))
+        })
+        .collect();
+    // Without normalization the accents stay, so every comparison takes the
+    // `char` path instead of the ASCII one.
+    let options = SearchOptions {
+        normalize: Some(false),
+        ..Default::default()
+    };
+    let query = "São Paulo";
+
+    c.bench_function("search_large_unicode", |b| {
+        b.iter(|| {
+            search(
+                black_box(query.to_string()),
+                black_box(items.clone()),
+                black_box(Some(options.clone())),
+            )
+        })
+    });
+}
+
+fn bench_search_large_all_results(c: &mut Criterion) {
+    let items = create_large_test_data();
+    let query = "berry";
+    let options = SearchOptions {
+        limit: Some(None),
+        ..Default::default()
+    };
+
+    c.bench_function("search_large_all_results", |b| {
+        b.iter(|| {
+            search(
+                black_box(query.to_string()),
+                black_box(items.clone()),
+                black_box(Some(options.clone())),
+            )
+        })
+    });
+}
+
+fn bench_search_indices_large_all_results(c: &mut Criterion) {
+    let items = create_large_test_data();
+    let query = "berry";
+    let options = SearchOptions {
+        limit: Some(None),
+        ..Default::default()
+    };
+
+    c.bench_function("search_indices_large_all_results", |b| {
+        b.iter(|| {
+            search_indices(
+                black_box(query.to_string()),
+                black_box(items.clone()),
+                black_box(Some(options.clone())),
+            )
+        })
+    });
+}
+
+fn bench_search_parallel_threshold(c: &mut Criterion) {
+    let base_items = create_test_data();
+    let mut group = c.benchmark_group("search_parallel");
+
+    for size in [100, 1_000, 10_000] {
+        let items: Vec<String> = (0..size)
+            .map(|i| format!("{}_{}", base_items[i % base_items.len()], i))
+            .collect();
+
+        for (name, parallel_threshold) in
+            [("sequential", u32::MAX), ("parallel", 0)]
+        {
+            let options = SearchOptions {
+                parallel_threshold: Some(parallel_threshold),
+                ..Default::default()
+            };
+            group.bench_with_input(
+                BenchmarkId::new(name, size),
+                &items,
+                |b, items| {
+                    b.iter(|| {
+                        search(
+                            black_box("berry".to_string()),
+                            black_box(items.clone()),
+                            black_box(Some(options.clone())),
+                        )
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
 fn bench_fuzzy_worst_case(c: &mut Criterion) {
     let str1 = "abcdefghijklmnopqrstuvwxyz";
     let str2 = "ZYXWVUTSRQPONMLKJIHGFEDCBA";
@@ -218,7 +348,7 @@ fn bench_fuzzy_worst_case(c: &mut Criterion) {
             fuzzy(
                 black_box(str1.to_string()),
                 black_box(str2.to_string()),
-                black_box(Some(true)),
+                black_box(Some(Either::A(true))),
             )
         })
     });
@@ -233,7 +363,7 @@ fn bench_fuzzy_best_case(c: &mut Criterion) {
             fuzzy(
                 black_box(str1.to_string()),
                 black_box(str2.to_string()),
-                black_box(Some(true)),
+                black_box(Some(Either::A(true))),
             )
         })
     });
@@ -248,12 +378,182 @@ fn bench_fuzzy_long_strings(c: &mut Criterion) {
             fuzzy(
                 black_box(str1.to_string()),
                 black_box(str2.to_string()),
-                black_box(Some(true)),
+                black_box(Some(Either::A(true))),
             )
         })
     });
 }
 
+fn bench_near_identical_distance(c: &mut Criterion) {
+    // Long pairs that only differ by one word in the middle, like an edited
+    // sentence, in ASCII and with accented letters.
+    let pairs = [
+        (
+            "ascii",
+            "The quick brown fox jumps over the lazy dog while the cat watches \
+             from the windowsill and the bird sings in the old oak tree",
+            "The quick brown fox jumps over the lazy cat while the cat watches \
+             from the windowsill and the bird sings in the old oak tree",
+        ),
+        (
+            "unicode",
+            "Le renard brun très rapide saute par-dessus le chien paresseux \
+             pendant que le chat regarde depuis le rebord de la fenêtre",
+            "Le renard brun très rapide saute par-dessus le chat paresseux \
+             pendant que le chat regarde depuis le rebord de la fenêtre",
+        ),
+    ];
+
+    let mut group = c.benchmark_group("near_identical_distance");
+    let mut buffer = LevenshteinBuffer::default();
+    for (name, a, b) in pairs {
+        group.bench_function(name, |bench| {
+            bench.iter(|| {
+                levenshtein_distance_with(
+                    black_box(a),
+                    black_box(b),
+                    &mut buffer,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_normalize_large(c: &mut Criterion) {
+    let items = create_large_test_data();
+
+    c.bench_function("normalize_large", |b| {
+        b.iter(|| {
+            for item in &items {
+                black_box(normalize_string(black_box(item), true));
+            }
+        })
+    });
+}
+
+fn bench_normalize(c: &mut Criterion) {
+    let ascii = create_large_test_data();
+    let accented: Vec<String> = ascii
+        .iter()
+        .map(|item| item.replace('a', "á").replace('e', "è"))
+        .collect();
+    let messy: Vec<String> = ascii
+        .iter()
+        .map(|item| format!("  {}\t\t{item}  \n", item.to_uppercase()))
+        .collect();
+    let cjk: Vec<String> = (0..1000)
+        .map(|i| format!("東京都 北京市 {i}"))
+        .collect();
+
+    let mut group = c.benchmark_group("normalize");
+    for (name, items) in [
+        ("ascii", &ascii),
+        ("ascii_messy", &messy),
+        ("accented", &accented),
+        ("cjk", &cjk),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            items,
+            |b, items| {
+                b.iter(|| {
+                    for item in items {
+                        black_box(normalize_string(black_box(item), true));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_normalize_ascii(c: &mut Criterion) {
+    let ascii = create_large_test_data();
+    let messy: Vec<String> = ascii
+        .iter()
+        .map(|item| format!("  {}\t\t{item}  \n", item.to_uppercase()))
+        .collect();
+
+    let mut group = c.benchmark_group("normalize_ascii");
+    for (name, items) in [("ascii", &ascii), ("ascii_messy", &messy)] {
+        group.bench_with_input(
+            BenchmarkId::new("normalize_string", name),
+            items,
+            |b, items| {
+                b.iter(|| {
+                    for item in items {
+                        black_box(normalize_string(black_box(item), true));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("normalize_ascii", name),
+            items,
+            |b, items| {
+                b.iter(|| {
+                    for item in items {
+                        black_box(normalize_ascii(black_box(item), true));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_bounded_distance(c: &mut Criterion) {
+    // Near-length pairs a few edits apart, as `maxDistance` filtering sees
+    // them.
+    let pair = |length: usize| {
+        let a: String = (0..length)
+            .map(|i| (b'a' + (i * 7 % 26) as u8) as char)
+            .collect();
+        let mut b = a.clone().into_bytes();
+        b[length / 3] = b'#';
+        b[2 * length / 3] = b'#';
+        b.push(b'#');
+        (a, String::from_utf8(b).unwrap())
+    };
+
+    let mut group = c.benchmark_group("bounded_distance");
+    let mut buffer = LevenshteinBuffer::default();
+    for length in [16, 64, 256] {
+        let (a, b) = pair(length);
+        group.bench_with_input(
+            BenchmarkId::new("full", length),
+            &(&a, &b),
+            |bench, (a, b)| {
+                bench.iter(|| {
+                    levenshtein_distance_with(
+                        black_box(a),
+                        black_box(b),
+                        &mut buffer,
+                    )
+                })
+            },
+        );
+        for max in [2, 4] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("max_{max}"), length),
+                &(&a, &b),
+                |bench, (a, b)| {
+                    bench.iter(|| {
+                        levenshtein_distance_bounded_with(
+                            black_box(a),
+                            black_box(b),
+                            max,
+                            &mut buffer,
+                        )
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
 criterion_group!(
     fuzzy_benches,
     bench_fuzzy_basic,
@@ -269,9 +569,32 @@ criterion_group!(
     bench_search_small,
     bench_search_large,
     bench_search_with_limit,
+    bench_search_large_with_limit,
     bench_search_high_threshold,
     bench_search_no_normalize,
-    bench_search_unicode
+    bench_search_unicode,
+    bench_search_large_unicode,
+    bench_search_large_all_results,
+    bench_search_indices_large_all_results,
+    bench_search_parallel_threshold
+);
+
+criterion_group!(
+    normalize_benches,
+    bench_normalize_large,
+    bench_normalize,
+    bench_normalize_ascii
 );
 
-criterion_main!(fuzzy_benches, search_benches);
+criterion_group!(
+    distance_benches,
+    bench_bounded_distance,
+    bench_near_identical_distance
+);
+
+criterion_main!(
+    fuzzy_benches,
+    search_benches,
+    normalize_benches,
+    distance_benches
+);
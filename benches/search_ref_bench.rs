@@ -0,0 +1,95 @@
+//! Compares `search`, which clones matched items into `SearchResult`s, with
+//! the borrowing `search_ref`. Besides the timings, the number of heap
+//! allocations one call of each makes is printed before benchmarking.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fast_fuzzy_rs::{search, search_ref, SearchOptions};
+
+/// The system allocator, counting every allocation it makes.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Number of allocations made while running `f`.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn create_items() -> Vec<String> {
+    let fruits = ["apple", "banana", "cherry", "grape", "mango", "orange"];
+    (0..1_000)
+        .map(|i| format!("{}_{i}", fruits[i % fruits.len()]))
+        .collect()
+}
+
+fn bench_search_ref(c: &mut Criterion) {
+    let owned = create_items();
+    let borrowed: Vec<&str> = owned
+        .iter()
+        .map(String::as_str)
+        .collect();
+    let query = "apple";
+    let options = SearchOptions {
+        // Every item matches, so `search` clones each one.
+        limit: Some(None),
+        // Stay sequential, so thread pool bookkeeping doesn't show up in the
+        // counts.
+        parallel_threshold: Some(u32::MAX),
+        ..Default::default()
+    };
+
+    let items = owned.clone();
+    let cloned = allocations(|| {
+        search(query.to_string(), items, Some(options.clone())).unwrap()
+    });
+    let borrowing =
+        allocations(|| search_ref(query, &borrowed, &options).unwrap());
+    println!(
+        "allocations per call over {} items: search {cloned}, \
+         search_ref {borrowing}",
+        owned.len()
+    );
+
+    let mut group = c.benchmark_group("search_ref");
+    group.bench_function("search", |b| {
+        b.iter(|| {
+            search(
+                black_box(query.to_string()),
+                black_box(owned.clone()),
+                black_box(Some(options.clone())),
+            )
+        })
+    });
+    group.bench_function("search_ref", |b| {
+        b.iter(|| {
+            search_ref(
+                black_box(query),
+                black_box(&borrowed),
+                black_box(&options),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(search_ref_benches, bench_search_ref);
+criterion_main!(search_ref_benches);
@@ -54,10 +54,59 @@ if (!nativeBinding) {
   throw new Error(\`Failed to load native binding\`)
 }
 
-const { search, fuzzy } = nativeBinding
+const {
+  search,
+  searchWithCount,
+  bestMatch,
+  bestMatchWithEdits,
+  searchIndices,
+  searchArrays,
+  searchStream,
+  searchGrouped,
+  searchGroupedByInitial,
+  searchAny,
+  searchMulti,
+  searchAliases,
+  searchBy,
+  suggestThreshold,
+  similarityMatrix,
+  similarityPairs,
+  fuzzy,
+  score,
+  scoreDetailed,
+  isMatch,
+  similarity,
+  fuzzyBytes,
+  normalizeAscii,
+  setNormalizationCacheSize,
+  Searcher,
+} = nativeBinding
 
 module.exports.search = search
+module.exports.searchWithCount = searchWithCount
+module.exports.bestMatch = bestMatch
+module.exports.bestMatchWithEdits = bestMatchWithEdits
+module.exports.searchIndices = searchIndices
+module.exports.searchArrays = searchArrays
+module.exports.searchStream = searchStream
+module.exports.searchGrouped = searchGrouped
+module.exports.searchGroupedByInitial = searchGroupedByInitial
+module.exports.searchAny = searchAny
+module.exports.searchMulti = searchMulti
+module.exports.searchAliases = searchAliases
+module.exports.searchBy = searchBy
+module.exports.suggestThreshold = suggestThreshold
+module.exports.similarityMatrix = similarityMatrix
+module.exports.similarityPairs = similarityPairs
 module.exports.fuzzy = fuzzy
+module.exports.score = score
+module.exports.scoreDetailed = scoreDetailed
+module.exports.isMatch = isMatch
+module.exports.similarity = similarity
+module.exports.fuzzyBytes = fuzzyBytes
+module.exports.normalizeAscii = normalizeAscii
+module.exports.setNormalizationCacheSize = setNormalizationCacheSize
+module.exports.Searcher = Searcher
 `;
 
 fs.writeFileSync("index.js", indexContent);
@@ -68,17 +117,187 @@ const dtsContent = `/* tslint:disable */
 
 /* auto-generated by NAPI-RS */
 
+export interface AlgorithmWeight {
+  name: "levenshtein" | "osa" | "word-level" | "jaro-winkler" | "alignment";
+  weight?: number;
+}
+
 export interface SearchOptions {
-  limit?: number;
+  limit?: number | null;
+  offset?: number;
   threshold?: number;
   normalize?: boolean;
   ignoreCase?: boolean;
+  explain?: boolean;
+  requireAllTokens?: boolean;
+  tokenThreshold?: number;
+  maxInputLength?: number;
+  turkishI?: boolean;
+  stem?: boolean;
+  stopwords?: Array<string>;
+  exactSubstring?: boolean;
+  weights?: Array<number>;
+  includeRawScore?: boolean;
+  minQueryLength?: number;
+  emptyQueryReturnsAll?: boolean;
+  alphanumericOnly?: boolean;
+  scoreFormula?: "distance" | "ratio";
+  compatibility?: boolean;
+  parallelThreshold?: number;
+  ignoreChars?: string;
+  boostSuffix?: number;
+  foldConfusables?: boolean;
+  lengthPenalty?: number;
+  combineFields?: "max" | "sum";
+  maxDistance?: number;
+  algorithm?: "levenshtein" | "osa" | "word-level" | "alignment" | Array<AlgorithmWeight>;
+  phonetic?: boolean;
+  includeNormalized?: boolean;
+  graphemes?: boolean;
+  cacheNormalization?: boolean;
+  sortKeys?: number[];
+  accentCost?: number;
+  naturalOrder?: boolean;
+  scoreDenominator?: "max" | "min" | "query" | "item";
+  preserveWhitespace?: boolean;
+  precision?: number;
+  locale?: string;
+  positionDecay?: number;
+  latinDiacriticsOnly?: boolean;
+  keepLetters?: string;
+  prefixBoostWeight?: number;
+  prefixBoostLength?: number;
+  includeTies?: boolean;
+  expandLigatures?: boolean;
+  streamOrder?: "corpus" | "score";
+  foldDigits?: boolean;
+  sampleLimit?: number;
+  sampleSeed?: number;
+  normalizer?: (s: string) => string;
+  wildcard?: boolean;
+  minTrigramOverlap?: number;
+  gapOpen?: number;
+  gapExtend?: number;
+  trim?: boolean;
+  wordCountBonus?: number;
+  stripControl?: boolean;
+  includeTokenScores?: boolean;
+  foldSuperscripts?: boolean;
+  scoreFloor?: number;
+  scoreCeil?: number;
+  unicodeWords?: boolean;
+}
+
+export interface ScoreBoost {
+  name: string;
+  value: number;
+}
+
+export interface ScoreExplanation {
+  distance: number;
+  maxLength: number;
+  algorithm: string;
+  boosts: Array<ScoreBoost>;
 }
 
 export interface SearchResult {
   item: string;
   score: number;
   index: number;
+  explanation?: ScoreExplanation;
+  queryIndex?: number;
+  rawScore?: number;
+  normalizedItem?: string;
+  aliasIndex?: number;
+  tokenScores?: Array<number>;
+}
+
+export interface EditOp {
+  op: "insert" | "delete" | "substitute";
+  position: number;
+  from?: string;
+  to?: string;
+}
+
+export interface MatchWithEdits {
+  result: SearchResult;
+  edits: Array<EditOp>;
+}
+
+export interface SearchResults {
+  results: Array<SearchResult>;
+  total: number;
+  itemCount: number;
+  approximate: boolean;
+}
+
+export interface ScoredIndex {
+  index: number;
+  score: number;
+}
+
+export interface ScoreDetails {
+  distance: number;
+  similarity: number;
+}
+
+export interface SimilarityPair {
+  i: number;
+  j: number;
+  score: number;
+}
+
+export interface SearchArrays {
+  indices: Uint32Array;
+  scores: Float64Array;
+}
+
+export interface SearchKey {
+  key: string;
+  weight?: number;
+}
+
+export interface SearchByResult {
+  index: number;
+  score: number;
+  field: string;
+}
+
+export interface ScoreGroup {
+  label: string;
+  min?: number;
+  max?: number;
+  results: Array<SearchResult>;
+}
+
+export interface InitialGroup {
+  initial: string;
+  results: Array<SearchResult>;
+}
+
+export interface SearcherOptions {
+  normalize?: boolean;
+  ignoreCase?: boolean;
+  cacheSize?: number;
+  minSharedGrams?: number;
+  prefixIndex?: boolean;
+  maxInputLength?: number;
+  turkishI?: boolean;
+  stem?: boolean;
+  stopwords?: Array<string>;
+  alphanumericOnly?: boolean;
+  compatibility?: boolean;
+  ignoreChars?: string;
+  foldConfusables?: boolean;
+  preserveWhitespace?: boolean;
+  locale?: string;
+  latinDiacriticsOnly?: boolean;
+  keepLetters?: string;
+  expandLigatures?: boolean;
+  foldDigits?: boolean;
+  trim?: boolean;
+  stripControl?: boolean;
+  foldSuperscripts?: boolean;
 }
 
 export function search(
@@ -87,11 +306,142 @@ export function search(
   options?: SearchOptions | undefined | null
 ): Array<SearchResult>;
 
+export function searchWithCount(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): SearchResults;
+
+export function bestMatch(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): SearchResult | null;
+
+export function bestMatchWithEdits(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): MatchWithEdits | null;
+
+export function searchIndices(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): Array<ScoredIndex>;
+
+export function searchArrays(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): SearchArrays;
+
+export function searchStream(
+  query: string,
+  items: Array<string>,
+  onResult: (result: SearchResult) => void,
+  options?: SearchOptions | undefined | null
+): Promise<number>;
+
+export function searchGrouped(
+  query: string,
+  items: Array<string>,
+  edges: Array<number>,
+  options?: SearchOptions | undefined | null
+): Array<ScoreGroup>;
+
+export function searchGroupedByInitial(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): Array<InitialGroup>;
+
+export function searchAny(
+  queries: Array<string>,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): Array<SearchResult>;
+
+export function searchMulti(
+  queries: Array<string>,
+  items: Array<string>,
+  aggregator: "max" | "mean" | "min",
+  options?: SearchOptions | undefined | null
+): Array<SearchResult>;
+
+export function searchAliases(
+  query: string,
+  groups: Array<Array<string>>,
+  options?: SearchOptions | undefined | null
+): Array<SearchResult>;
+
+export function searchBy(
+  query: string,
+  records: Array<object>,
+  keys: Array<SearchKey>,
+  options?: SearchOptions | undefined | null
+): Array<SearchByResult>;
+
+export function suggestThreshold(
+  query: string,
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): number;
+
+export function similarityMatrix(
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): Array<Array<number>>;
+
+export function similarityPairs(
+  items: Array<string>,
+  options?: SearchOptions | undefined | null
+): Array<SimilarityPair>;
+
 export function fuzzy(
   str1: string,
   str2: string,
-  normalize?: boolean | undefined | null
+  options?: boolean | SearchOptions | undefined | null
 ): number;
+
+export function isMatch(
+  str1: string,
+  str2: string,
+  threshold: number,
+  options?: SearchOptions | undefined | null
+): boolean;
+
+export function score(
+  query: string,
+  item: string,
+  options?: SearchOptions | undefined | null
+): number;
+
+export function scoreDetailed(
+  a: string,
+  b: string,
+  options?: SearchOptions | undefined | null
+): ScoreDetails;
+
+export function similarity(str1: string, str2: string): number;
+
+export function fuzzyBytes(a: Buffer, b: Buffer): number;
+
+export function normalizeAscii(input: string, toLowercase?: boolean | undefined | null): string;
+
+export function setNormalizationCacheSize(size: number): void;
+
+export class Searcher {
+  constructor(items: Array<string>, options?: SearcherOptions | undefined | null);
+  query(query: string, options?: SearchOptions | undefined | null): Array<SearchResult>;
+  prefixSearch(query: string, options?: SearchOptions | undefined | null): Array<SearchResult>;
+  setItems(items: Array<string>): void;
+  addItems(items: Array<string>): void;
+  removeItem(index: number): boolean;
+  update(index: number, newItem: string): boolean;
+  clear(): void;
+  get cacheHits(): number;
+}
 `;
 
 fs.writeFileSync("index.d.ts", dtsContent);
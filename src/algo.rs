@@ -1,3 +1,7 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::EditOp;
+
 /// Computes the [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
 /// between two strings.
 ///
@@ -9,11 +13,14 @@
 ///
 /// - If both input strings are ASCII, an optimized byte-based implementation is used.
 /// - Otherwise, the strings are compared as Unicode scalar values (`char`).
+/// - Either way, the common prefix and suffix are stripped first, so only
+///   the part where the strings differ goes through the `O(n·m)` DP. Nearly
+///   identical strings, however long, cost little more than comparing them.
 ///
 /// # Examples
 ///
 /// ```
-/// use mylib::levenshtein_distance;
+/// use fast_fuzzy_rs::levenshtein_distance;
 ///
 /// assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
 /// assert_eq!(levenshtein_distance("flaw", "lawn"), 2);
@@ -21,6 +28,66 @@
 /// assert_eq!(levenshtein_distance("same", "same"), 0);
 /// ```
 pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    levenshtein_distance_with(a, b, &mut LevenshteinBuffer::default())
+}
+
+/// Reusable scratch space for [`levenshtein_distance_with`].
+///
+/// Holding on to one buffer across many comparisons avoids allocating the
+/// two DP rows (and, for non-ASCII input, the decoded `char`s) on every call.
+/// The buffer grows to fit the longest second argument it has seen.
+#[derive(Debug, Default)]
+pub struct LevenshteinBuffer {
+    prev: Vec<usize>,
+    curr: Vec<usize>,
+    /// The row before `prev`, which only [`osa_distance_with`] needs.
+    prev2: Vec<usize>,
+    a_chars: Vec<char>,
+    b_chars: Vec<char>,
+}
+
+impl LevenshteinBuffer {
+    /// Creates a buffer with room for second arguments up to `len` characters.
+    pub fn with_capacity(len: usize) -> Self {
+        Self {
+            prev: Vec::with_capacity(len + 1),
+            curr: Vec::with_capacity(len + 1),
+            prev2: Vec::new(),
+            a_chars: Vec::new(),
+            b_chars: Vec::with_capacity(len),
+        }
+    }
+
+    /// Ensures room for second arguments up to `len` characters without
+    /// reallocating.
+    pub fn reserve(&mut self, len: usize) {
+        let reserve = |v: &mut Vec<usize>| {
+            v.reserve((len + 1).saturating_sub(v.len()));
+        };
+        reserve(&mut self.prev);
+        reserve(&mut self.curr);
+        self.b_chars
+            .reserve(len.saturating_sub(self.b_chars.len()));
+    }
+}
+
+/// Same as [`levenshtein_distance`], but reuses `buffer` instead of
+/// allocating scratch space for each call.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{levenshtein_distance_with, LevenshteinBuffer};
+///
+/// let mut buffer = LevenshteinBuffer::default();
+/// assert_eq!(levenshtein_distance_with("kitten", "sitting", &mut buffer), 3);
+/// assert_eq!(levenshtein_distance_with("café", "cafe", &mut buffer), 1);
+/// ```
+pub fn levenshtein_distance_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
     if a == b {
         return 0;
     }
@@ -31,20 +98,779 @@ pub fn levenshtein_distance(a: &str, b: &str) -> usize {
         return a.chars().count();
     }
 
+    let LevenshteinBuffer {
+        prev,
+        curr,
+        a_chars,
+        b_chars,
+        ..
+    } = buffer;
+
     if a.is_ascii() && b.is_ascii() {
-        return lev_bytes(a.as_bytes(), b.as_bytes());
+        return lev_bytes_into(a.as_bytes(), b.as_bytes(), prev, curr);
+    }
+
+    a_chars.clear();
+    a_chars.extend(a.chars());
+    b_chars.clear();
+    b_chars.extend(b.chars());
+    lev_slice_into(a_chars, b_chars, prev, curr)
+}
+
+/// A query decoded once for comparing against many items, so
+/// [`levenshtein_distance_prepared`] doesn't check it for ASCII and collect
+/// its `char`s again for every item.
+#[derive(Debug, Default)]
+pub(crate) struct PreparedQuery {
+    text: String,
+    chars: Vec<char>,
+    ascii: bool,
+}
+
+impl PreparedQuery {
+    pub(crate) fn new(text: &str) -> Self {
+        let mut query = Self::default();
+        query.prepare(text);
+        query
+    }
+
+    /// Decodes `text` in place of the current query, unless it already is
+    /// the current query.
+    pub(crate) fn prepare(&mut self, text: &str) {
+        if !text.is_empty() && self.text == text {
+            return;
+        }
+        text.clone_into(&mut self.text);
+        self.chars.clear();
+        self.chars.extend(text.chars());
+        self.ascii = text.is_ascii();
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Same as [`levenshtein_distance_with`] for a query prepared up front.
+pub(crate) fn levenshtein_distance_prepared(
+    query: &PreparedQuery,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
+    let a = query.text();
+    if a == b {
+        return 0;
+    }
+    if a.is_empty() {
+        return b.chars().count();
+    }
+    if b.is_empty() {
+        return query.chars.len();
+    }
+
+    let LevenshteinBuffer {
+        prev,
+        curr,
+        b_chars,
+        ..
+    } = buffer;
+
+    if query.ascii && b.is_ascii() {
+        return lev_bytes_into(a.as_bytes(), b.as_bytes(), prev, curr);
     }
 
-    let ac: Vec<char> = a.chars().collect();
-    let bc: Vec<char> = b.chars().collect();
-    lev_chars(&ac, &bc)
+    b_chars.clear();
+    b_chars.extend(b.chars());
+    lev_slice_into(&query.chars, b_chars, prev, curr)
 }
 
-/// Internal helper for computing Levenshtein distance on ASCII byte slices.
-fn lev_bytes(a: &[u8], b: &[u8]) -> usize {
+/// Same as [`levenshtein_distance_with`], but gives up as soon as the
+/// distance is known to exceed `max`, returning `None`.
+///
+/// Strings whose lengths differ by more than `max` are rejected without any
+/// DP. For the others only the cells within `max` of the diagonal are
+/// computed, and the DP stops as soon as a whole row exceeds `max`, so
+/// filtering a corpus by a small edit distance costs `O(len · max)` per item
+/// instead of `O(len²)`.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{levenshtein_distance_bounded_with, LevenshteinBuffer};
+///
+/// let mut buffer = LevenshteinBuffer::default();
+/// let bounded = |a, b, max, buffer: &mut LevenshteinBuffer| {
+///     levenshtein_distance_bounded_with(a, b, max, buffer)
+/// };
+/// assert_eq!(bounded("kitten", "sitting", 3, &mut buffer), Some(3));
+/// assert_eq!(bounded("kitten", "sitting", 2, &mut buffer), None);
+/// assert_eq!(bounded("", "abc", 5, &mut buffer), Some(3));
+/// ```
+pub fn levenshtein_distance_bounded_with(
+    a: &str,
+    b: &str,
+    max: usize,
+    buffer: &mut LevenshteinBuffer,
+) -> Option<usize> {
+    if a == b {
+        return Some(0);
+    }
+
+    let LevenshteinBuffer {
+        prev,
+        curr,
+        a_chars,
+        b_chars,
+        ..
+    } = buffer;
+
+    if a.is_ascii() && b.is_ascii() {
+        return lev_banded_into(a.as_bytes(), b.as_bytes(), max, prev, curr);
+    }
+
+    a_chars.clear();
+    a_chars.extend(a.chars());
+    b_chars.clear();
+    b_chars.extend(b.chars());
+    lev_banded_into(a_chars, b_chars, max, prev, curr)
+}
+
+/// Computes the Optimal String Alignment distance between two strings: the
+/// Levenshtein distance, plus transpositions of two adjacent characters
+/// counted as a single edit.
+///
+/// Unlike full Damerau-Levenshtein, no substring may be edited more than
+/// once, so a transposed pair can't have another character inserted between
+/// them. `"CA"` → `"ABC"` therefore takes 3 edits here, but only 2 with
+/// Damerau-Levenshtein (`"CA"` → `"AC"` → `"ABC"`). In exchange OSA needs
+/// only one extra DP row instead of a table indexed by alphabet.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{levenshtein_distance, osa_distance};
+///
+/// assert_eq!(osa_distance("ab", "ba"), 1);
+/// assert_eq!(levenshtein_distance("ab", "ba"), 2);
+/// assert_eq!(osa_distance("CA", "ABC"), 3);
+/// ```
+pub fn osa_distance(a: &str, b: &str) -> usize {
+    osa_distance_with(a, b, &mut LevenshteinBuffer::default())
+}
+
+/// Same as [`osa_distance`], but reuses `buffer` instead of allocating
+/// scratch space for each call.
+pub fn osa_distance_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
+    if a == b {
+        return 0;
+    }
+    if a.is_empty() {
+        return b.chars().count();
+    }
+    if b.is_empty() {
+        return a.chars().count();
+    }
+
+    let LevenshteinBuffer {
+        prev,
+        curr,
+        prev2,
+        a_chars,
+        b_chars,
+    } = buffer;
+
+    if a.is_ascii() && b.is_ascii() {
+        return osa_into(a.as_bytes(), b.as_bytes(), prev2, prev, curr);
+    }
+
+    a_chars.clear();
+    a_chars.extend(a.chars());
+    b_chars.clear();
+    b_chars.extend(b.chars());
+    osa_into(a_chars, b_chars, prev2, prev, curr)
+}
+
+/// Computes the Levenshtein distance between two strings over extended
+/// grapheme clusters rather than `char`s.
+///
+/// A flag, an emoji joined with zero-width joiners or a letter followed by
+/// combining marks is several `char`s but one user-perceived character, so
+/// replacing one costs a single edit here instead of one per `char`.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{grapheme_distance, levenshtein_distance};
+///
+/// // Two regional indicators each, both of which differ.
+/// assert_eq!(levenshtein_distance("🇺🇸", "🇫🇷"), 2);
+/// assert_eq!(grapheme_distance("🇺🇸", "🇫🇷"), 1);
+/// assert_eq!(grapheme_distance("kitten", "sitting"), 3);
+/// ```
+pub fn grapheme_distance(a: &str, b: &str) -> usize {
+    grapheme_distance_with(a, b, &mut LevenshteinBuffer::default())
+}
+
+/// Same as [`grapheme_distance`], but reuses the DP rows of `buffer`.
+///
+/// The grapheme boundaries themselves are still collected per call.
+pub fn grapheme_distance_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
+    if a == b {
+        return 0;
+    }
+    // Every ASCII char is its own grapheme except in CRLF, so ASCII without
+    // '\r' can skip segmentation.
+    if a.is_ascii() && b.is_ascii() && !a.contains('\r') && !b.contains('\r') {
+        return levenshtein_distance_with(a, b, buffer);
+    }
+
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    lev_slice_into(&a, &b, &mut buffer.prev, &mut buffer.curr)
+}
+
+/// Computes the Levenshtein distance between two strings over their
+/// whitespace-separated words rather than `char`s.
+///
+/// Each distinct word is one symbol, so inserting, deleting or replacing a
+/// whole word costs a single edit however long it is, which suits matching
+/// sentences that differ by a few words.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::word_distance;
+///
+/// assert_eq!(word_distance("the quick fox", "the fox"), 1);
+/// assert_eq!(word_distance("the quick fox", "the slow fox"), 1);
+/// assert_eq!(word_distance("a  b", "a b"), 0);
+/// ```
+pub fn word_distance(a: &str, b: &str) -> usize {
+    word_distance_with(a, b, &mut LevenshteinBuffer::default())
+}
+
+/// Same as [`word_distance`], but reuses the DP rows of `buffer`.
+///
+/// The words themselves are still collected per call.
+pub fn word_distance_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
+    let a: Vec<&str> = a.split_whitespace().collect();
+    let b: Vec<&str> = b.split_whitespace().collect();
+    lev_slice_into(&a, &b, &mut buffer.prev, &mut buffer.curr)
+}
+
+/// [`word_distance_with`] over the words found at Unicode word boundaries
+/// (UAX #29) instead of whitespace, so punctuation separates words and every
+/// Han ideograph is a word of its own.
+pub(crate) fn unicode_word_distance_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
+    let a: Vec<&str> = a.unicode_words().collect();
+    let b: Vec<&str> = b.unicode_words().collect();
+    lev_slice_into(&a, &b, &mut buffer.prev, &mut buffer.curr)
+}
+
+/// [`osa_distance_with`] over grapheme clusters, as in [`grapheme_distance`].
+pub(crate) fn grapheme_osa_distance_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> usize {
+    if a == b {
+        return 0;
+    }
+
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    let LevenshteinBuffer {
+        prev, curr, prev2, ..
+    } = buffer;
+    osa_into(&a, &b, prev2, prev, curr)
+}
+
+/// Normalized Levenshtein similarity between two strings, from `0.0`
+/// (nothing in common) to `1.0` (identical).
+///
+/// Computed as `1 - distance / max_len`, where `max_len` is the byte length of
+/// the longer string. Equal strings score `1.0`, including two empty ones; an
+/// empty string against a non-empty one scores `0.0`. No normalization is
+/// applied, so callers wanting case- or accent-insensitive scores should run
+/// [`crate::normalize_string`] first.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::similarity;
+///
+/// assert_eq!(similarity("same", "same"), 1.0);
+/// assert_eq!(similarity("", ""), 1.0);
+/// assert_eq!(similarity("abc", ""), 0.0);
+/// assert_eq!(similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+/// ```
+pub fn similarity(a: &str, b: &str) -> f64 {
+    similarity_with(a, b, &mut LevenshteinBuffer::default())
+}
+
+/// Same as [`similarity`], but reuses `buffer` for the distance computation.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{similarity_with, LevenshteinBuffer};
+///
+/// let mut buffer = LevenshteinBuffer::default();
+/// assert_eq!(similarity_with("flaw", "lawn", &mut buffer), 0.5);
+/// assert_eq!(similarity_with("", "abc", &mut buffer), 0.0);
+/// ```
+pub fn similarity_with(
+    a: &str,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let distance = levenshtein_distance_with(a, b, buffer);
+    let max_len = a.len().max(b.len()) as f64;
+
+    1.0 - (distance as f64 / max_len)
+}
+
+/// Same as [`similarity_with`] for a query prepared up front.
+pub(crate) fn similarity_prepared(
+    query: &PreparedQuery,
+    b: &str,
+    buffer: &mut LevenshteinBuffer,
+) -> f64 {
+    let a = query.text();
+    if a == b {
+        return 1.0;
+    }
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let distance = levenshtein_distance_prepared(query, b, buffer);
+    let max_len = a.len().max(b.len()) as f64;
+
+    1.0 - (distance as f64 / max_len)
+}
+
+/// Normalized Levenshtein similarity between two byte strings, scored like
+/// [`similarity`] but with each byte counting as one symbol.
+///
+/// The bytes don't have to be valid UTF-8, which suits keys such as hashes or
+/// token ids that aren't text; nothing is normalized.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::byte_similarity;
+///
+/// assert_eq!(byte_similarity(b"kitten", b"sitting"), 1.0 - 3.0 / 7.0);
+/// assert_eq!(byte_similarity(&[0xff, 0xfe], &[0xff, 0x00]), 0.5);
+/// assert_eq!(byte_similarity(b"", b""), 1.0);
+/// ```
+pub fn byte_similarity(a: &[u8], b: &[u8]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let (mut prev, mut curr) = (Vec::new(), Vec::new());
+    let distance = lev_bytes_into(a, b, &mut prev, &mut curr);
+    let max_len = a.len().max(b.len()) as f64;
+
+    1.0 - (distance as f64 / max_len)
+}
+
+/// Computes a shortest sequence of single-`char` edits turning `a` into `b`,
+/// the steps behind [`levenshtein_distance`], ordered by position in `a`.
+///
+/// Unlike the distance alone, this keeps the whole DP matrix to trace the
+/// edits back, so memory grows with the product of the lengths instead of
+/// linearly: `4 × (n + 1) × (m + 1)` bytes, where `n` and `m` count the
+/// `char`s left once the common prefix and suffix are trimmed. When several
+/// scripts are equally short, substitutions are preferred over a delete and
+/// an insert.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::levenshtein_edits;
+///
+/// let edits = levenshtein_edits("kitten", "sitting");
+/// let ops: Vec<(&str, u32)> = edits
+///     .iter()
+///     .map(|edit| (edit.op.as_str(), edit.position))
+///     .collect();
+/// assert_eq!(ops, [("substitute", 0), ("substitute", 4), ("insert", 6)]);
+/// assert!(levenshtein_edits("same", "same").is_empty());
+/// ```
+pub fn levenshtein_edits(a: &str, b: &str) -> Vec<EditOp> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Only the middle needs tracing; positions are shifted back by the
+    // trimmed prefix.
+    let prefix = a
+        .iter()
+        .zip(&b)
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = trim_common(&a, &b);
     let (n, m) = (a.len(), b.len());
+
+    let width = m + 1;
+    let mut dp = vec![0u32; (n + 1) * width];
+    for i in 0..=n {
+        for j in 0..=m {
+            dp[i * width + j] = match (i, j) {
+                (0, j) => j as u32,
+                (i, 0) => i as u32,
+                (i, j) => {
+                    let cost = u32::from(a[i - 1] != b[j - 1]);
+                    (dp[(i - 1) * width + j] + 1)
+                        .min(dp[i * width + j - 1] + 1)
+                        .min(dp[(i - 1) * width + j - 1] + cost)
+                }
+            };
+        }
+    }
+
+    let edit = |op: &str,
+                position: usize,
+                from: Option<char>,
+                to: Option<char>| EditOp {
+        op: op.to_string(),
+        position: (prefix + position) as u32,
+        from: from.map(String::from),
+        to: to.map(String::from),
+    };
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let here = dp[i * width + j];
+        if i > 0 && j > 0 {
+            let cost = u32::from(a[i - 1] != b[j - 1]);
+            if here == dp[(i - 1) * width + j - 1] + cost {
+                if cost == 1 {
+                    edits.push(edit(
+                        "substitute",
+                        i - 1,
+                        Some(a[i - 1]),
+                        Some(b[j - 1]),
+                    ));
+                }
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && here == dp[(i - 1) * width + j] + 1 {
+            edits.push(edit("delete", i - 1, Some(a[i - 1]), None));
+            i -= 1;
+        } else {
+            edits.push(edit("insert", i, None, Some(b[j - 1])));
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+/// Computes the [Jaro similarity](https://en.wikipedia.org/wiki/Jaro%E2%80%93Winkler_distance)
+/// of two strings, in `0.0..=1.0`.
+///
+/// Instead of counting edits, Jaro counts the `char`s the strings share
+/// within a window around the same position, and how many of those are out
+/// of order. It is forgiving of transpositions and suits short strings such
+/// as names.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::jaro_similarity;
+///
+/// assert_eq!(jaro_similarity("same", "same"), 1.0);
+/// assert_eq!(jaro_similarity("abc", "xyz"), 0.0);
+/// assert!((jaro_similarity("martha", "marhta") - 0.944).abs() < 0.001);
+/// ```
+pub fn jaro_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::with_capacity(a.len());
+    for (i, &c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == c) {
+            b_matched[j] = true;
+            a_matches.push(c);
+        }
+    }
+
+    let matches = a_matches.len();
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let b_matches = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, &matched)| matched)
+        .map(|(&c, _)| c);
+    let transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(&x, y)| x != *y)
+        .count()
+        / 2;
+
+    let m = matches as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m)
+        / 3.0
+}
+
+/// Computes the Jaro-Winkler similarity of two strings, in `0.0..=1.0`: the
+/// [`jaro_similarity`] boosted for a common prefix of up to 4 `char`s, so
+/// strings that start the same rank higher.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{jaro_similarity, jaro_winkler_similarity};
+///
+/// let (a, b) = ("martha", "marhta");
+/// assert!((jaro_winkler_similarity(a, b) - 0.961).abs() < 0.001);
+/// assert!(jaro_winkler_similarity(a, b) > jaro_similarity(a, b));
+/// ```
+pub fn jaro_winkler_similarity(a: &str, b: &str) -> f64 {
+    apply_winkler_boost(jaro_similarity(a, b), a, b, 0.1, 4)
+}
+
+/// Applies the Winkler prefix bonus to any similarity `score` of `a` and
+/// `b`: with a common prefix of `l` `char`s, counting at most `max_prefix`
+/// of them, the score becomes `score + l * prefix_weight * (1 - score)`.
+///
+/// The result is capped at `1.0`, so a large `prefix_weight` can't push it
+/// past a perfect score. [`jaro_winkler_similarity`] is this over
+/// [`jaro_similarity`] with a weight of `0.1` and up to 4 `char`s.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{apply_winkler_boost, similarity};
+///
+/// let score = similarity("martha", "marhta");
+/// let boosted = apply_winkler_boost(score, "martha", "marhta", 0.1, 4);
+/// assert!((boosted - (score + 0.3 * (1.0 - score))).abs() < 1e-12);
+/// assert_eq!(apply_winkler_boost(0.5, "abcd", "abce", 1.0, 4), 1.0);
+/// assert_eq!(apply_winkler_boost(0.5, "abc", "xyz", 0.1, 4), 0.5);
+/// ```
+pub fn apply_winkler_boost(
+    score: f64,
+    a: &str,
+    b: &str,
+    prefix_weight: f64,
+    max_prefix: usize,
+) -> f64 {
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(max_prefix)
+        .take_while(|(x, y)| x == y)
+        .count();
+    (score + prefix as f64 * prefix_weight * (1.0 - score)).min(1.0)
+}
+
+/// Levenshtein distance over `char`s where substituting `x` for `y` costs
+/// `sub_cost(x, y)` instead of `1`. Insertions and deletions still cost `1`,
+/// so `sub_cost` should stay within `0.0..=1.0`.
+pub(crate) fn weighted_levenshtein_distance(
+    a: &str,
+    b: &str,
+    sub_cost: impl Fn(char, char) -> f64,
+) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let m = b.len();
+    let mut prev: Vec<f64> = (0..=m).map(|j| j as f64).collect();
+    let mut curr = vec![0.0; m + 1];
+
+    for (i, &ai) in a.iter().enumerate() {
+        curr[0] = (i + 1) as f64;
+        for j in 1..=m {
+            let cost = if ai == b[j - 1] {
+                0.0
+            } else {
+                sub_cost(ai, b[j - 1])
+            };
+            let del = prev[j] + 1.0;
+            let ins = curr[j - 1] + 1.0;
+            let sub = prev[j - 1] + cost;
+            curr[j] = del.min(ins).min(sub);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[m]
+}
+
+/// Cheapest alignment of `a` and `b` over `char`s with affine gap costs, by
+/// Gotoh's algorithm: a substitution costs `1`, and a run of `k` inserted or
+/// deleted chars costs `gap_open + (k - 1) * gap_extend`, so one long gap can
+/// cost much less than the same number of scattered ones. With both costs at
+/// `1` this is the Levenshtein distance.
+///
+/// Takes `O(len(a) · len(b))` time, like Levenshtein, with three DP rows of
+/// `len(b)` entries instead of two.
+pub(crate) fn affine_gap_distance(
+    a: &str,
+    b: &str,
+    gap_open: f64,
+    gap_extend: f64,
+) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let gap = |k: usize| {
+        if k == 0 {
+            0.0
+        } else {
+            gap_open + (k - 1) as f64 * gap_extend
+        }
+    };
+    let m = b.len();
+
+    // `best[j]`: cheapest alignment of the prefixes so far; `deleting[j]`:
+    // cheapest one ending in a deletion from `a`, which may be extended.
+    let mut best: Vec<f64> = (0..=m).map(gap).collect();
+    let mut deleting = vec![f64::INFINITY; m + 1];
+    let mut next_best = vec![0.0; m + 1];
+
+    for (i, &ai) in a.iter().enumerate() {
+        next_best[0] = gap(i + 1);
+        deleting[0] = next_best[0];
+        // Cheapest alignment of this row ending in an insertion from `b`.
+        let mut inserting = f64::INFINITY;
+        for j in 1..=m {
+            deleting[j] = (best[j] + gap_open).min(deleting[j] + gap_extend);
+            inserting =
+                (next_best[j - 1] + gap_open).min(inserting + gap_extend);
+            let substitute = best[j - 1] + f64::from(u8::from(ai != b[j - 1]));
+            next_best[j] = substitute
+                .min(deleting[j])
+                .min(inserting);
+        }
+        std::mem::swap(&mut best, &mut next_best);
+    }
+    best[m]
+}
+
+/// Levenshtein distance over `char`s from `pattern` to `item`, where a `*` in
+/// `pattern` matches any run of chars and a `?` any single char, both for
+/// free. Only the other, literal chars of `pattern` are matched fuzzily.
+///
+/// Returns `None` if `item` can't fit the pattern at all, i.e. it has fewer
+/// chars than `pattern` has `?`s.
+pub(crate) fn wildcard_distance(pattern: &str, item: &str) -> Option<usize> {
+    let item: Vec<char> = item.chars().collect();
+    let m = item.len();
+    // `usize::MAX` marks a prefix of `item` the pattern so far can't match.
     let mut prev: Vec<usize> = (0..=m).collect();
-    let mut curr: Vec<usize> = vec![0; m + 1];
+    let mut curr = vec![0; m + 1];
+
+    for p in pattern.chars() {
+        match p {
+            '*' => {
+                curr[0] = prev[0];
+                for j in 1..=m {
+                    curr[j] = prev[j].min(curr[j - 1]);
+                }
+            }
+            '?' => {
+                curr[0] = usize::MAX;
+                curr[1..].copy_from_slice(&prev[..m]);
+            }
+            _ => {
+                curr[0] = prev[0].saturating_add(1);
+                for j in 1..=m {
+                    let cost = usize::from(p != item[j - 1]);
+                    let del = prev[j].saturating_add(1);
+                    let ins = curr[j - 1].saturating_add(1);
+                    let sub = prev[j - 1].saturating_add(cost);
+                    curr[j] = del.min(ins).min(sub);
+                }
+            }
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    Some(prev[m]).filter(|&distance| distance != usize::MAX)
+}
+
+/// Internal helper stripping the longest common prefix and suffix of `a`
+/// and `b`. An optimal alignment can always match those parts up for free,
+/// so the edit distance of what remains is the same as of the whole.
+fn trim_common<'a, T: PartialEq>(a: &'a [T], b: &'a [T]) -> (&'a [T], &'a [T]) {
+    let prefix = a
+        .iter()
+        .zip(b)
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (&a[..a.len() - suffix], &b[..b.len() - suffix])
+}
+
+/// Internal helper for computing Levenshtein distance on byte slices, such as
+/// ASCII strings, using `prev` and `curr` as the two DP rows. Like the other
+/// Levenshtein helpers, it only runs the DP on what [`trim_common`] leaves.
+fn lev_bytes_into(
+    a: &[u8],
+    b: &[u8],
+    prev: &mut Vec<usize>,
+    curr: &mut Vec<usize>,
+) -> usize {
+    let (a, b) = trim_common(a, b);
+    let (n, m) = (a.len(), b.len());
+    prev.clear();
+    prev.extend(0..=m);
+    curr.clear();
+    curr.resize(m + 1, 0);
 
     for i in 1..=n {
         curr[0] = i;
@@ -56,16 +882,25 @@ fn lev_bytes(a: &[u8], b: &[u8]) -> usize {
             let sub = prev[j - 1] + cost;
             curr[j] = del.min(ins).min(sub);
         }
-        std::mem::swap(&mut prev, &mut curr);
+        std::mem::swap(prev, curr);
     }
     prev[m]
 }
 
-/// Internal helper for computing Levenshtein distance on Unicode scalar values.
-fn lev_chars(a: &[char], b: &[char]) -> usize {
+/// Internal helper for computing Levenshtein distance on Unicode scalar
+/// values or grapheme clusters, using `prev` and `curr` as the two DP rows.
+fn lev_slice_into<T: Copy + PartialEq>(
+    a: &[T],
+    b: &[T],
+    prev: &mut Vec<usize>,
+    curr: &mut Vec<usize>,
+) -> usize {
+    let (a, b) = trim_common(a, b);
     let (n, m) = (a.len(), b.len());
-    let mut prev: Vec<usize> = (0..=m).collect();
-    let mut curr: Vec<usize> = vec![0; m + 1];
+    prev.clear();
+    prev.extend(0..=m);
+    curr.clear();
+    curr.resize(m + 1, 0);
 
     for i in 1..=n {
         curr[0] = i;
@@ -77,7 +912,502 @@ fn lev_chars(a: &[char], b: &[char]) -> usize {
             let sub = prev[j - 1] + cost;
             curr[j] = del.min(ins).min(sub);
         }
-        std::mem::swap(&mut prev, &mut curr);
+        std::mem::swap(prev, curr);
+    }
+    prev[m]
+}
+
+/// Internal helper for [`levenshtein_distance_bounded_with`]: a banded DP
+/// that only fills the cells within `max` of the diagonal, `O(len · max)`
+/// instead of `O(len²)`.
+///
+/// A path through any other cell already takes more than `max` edits, so
+/// the band gives the exact distance whenever it is at most `max`, and some
+/// value above `max` otherwise. It also stops at the first row whose band
+/// exceeds `max`: no later row can get below it.
+fn lev_banded_into<T: Copy + PartialEq>(
+    a: &[T],
+    b: &[T],
+    max: usize,
+    prev: &mut Vec<usize>,
+    curr: &mut Vec<usize>,
+) -> Option<usize> {
+    let (a, b) = trim_common(a, b);
+    let (n, m) = (a.len(), b.len());
+    if n.abs_diff(m) > max {
+        return None;
+    }
+
+    // No distance exceeds the longer length, so a larger `max` only widens
+    // the band for nothing. Cells outside the band hold `outside`.
+    let max = max.min(n.max(m));
+    let outside = max + 1;
+    prev.clear();
+    prev.extend((0..=m).map(|j| if j <= max { j } else { outside }));
+    curr.clear();
+    curr.resize(m + 1, outside);
+
+    for i in 1..=n {
+        let lo = i.saturating_sub(max).max(1);
+        let hi = (i + max).min(m);
+        curr[lo - 1] = if lo == 1 { i.min(outside) } else { outside };
+        if i + max <= m {
+            // Just past the previous row's band.
+            prev[hi] = outside;
+        }
+
+        let mut row_min = curr[lo - 1];
+        let ai = a[i - 1];
+        for j in lo..=hi {
+            let cost = usize::from(ai != b[j - 1]);
+            let del = prev[j] + 1;
+            let ins = curr[j - 1] + 1;
+            let sub = prev[j - 1] + cost;
+            curr[j] = del.min(ins).min(sub);
+            row_min = row_min.min(curr[j]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(prev, curr);
+    }
+    (prev[m] <= max).then_some(prev[m])
+}
+
+/// Internal helper for [`osa_distance_with`], keeping the three most recent
+/// DP rows in `prev2`, `prev` and `curr`.
+fn osa_into<T: Copy + PartialEq>(
+    a: &[T],
+    b: &[T],
+    prev2: &mut Vec<usize>,
+    prev: &mut Vec<usize>,
+    curr: &mut Vec<usize>,
+) -> usize {
+    let (n, m) = (a.len(), b.len());
+    prev2.clear();
+    prev2.resize(m + 1, 0);
+    prev.clear();
+    prev.extend(0..=m);
+    curr.clear();
+    curr.resize(m + 1, 0);
+
+    for i in 1..=n {
+        curr[0] = i;
+        let ai = a[i - 1];
+        for j in 1..=m {
+            let cost = usize::from(ai != b[j - 1]);
+            let del = prev[j] + 1;
+            let ins = curr[j - 1] + 1;
+            let sub = prev[j - 1] + cost;
+            curr[j] = del.min(ins).min(sub);
+            if i > 1 && j > 1 && ai == b[j - 2] && a[i - 2] == b[j - 1] {
+                curr[j] = curr[j].min(prev2[j - 2] + 1);
+            }
+        }
+        std::mem::swap(prev2, prev);
+        std::mem::swap(prev, curr);
     }
     prev[m]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift generator drawing numbers below a bound, which keeps
+    /// random inputs reproducible without pulling in a dependency.
+    fn xorshift(mut state: u64) -> impl FnMut(usize) -> usize {
+        move |bound| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        }
+    }
+
+    /// The textbook full-matrix Levenshtein DP, without any of the shortcuts
+    /// the real implementations take.
+    fn untrimmed_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+        let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        d[0] = (0..=b.len()).collect();
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                d[i][j] = (d[i - 1][j] + 1)
+                    .min(d[i][j - 1] + 1)
+                    .min(d[i - 1][j - 1] + cost);
+            }
+        }
+        d[a.len()][b.len()]
+    }
+
+    #[test]
+    fn test_reused_buffer_matches_fresh_allocation() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("a much longer string", "short"),
+            ("café", "coffee"),
+            ("", "abc"),
+            ("flaw", "lawn"),
+        ];
+
+        let mut buffer = LevenshteinBuffer::default();
+        for (a, b) in pairs {
+            assert_eq!(
+                levenshtein_distance_with(a, b, &mut buffer),
+                levenshtein_distance(a, b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bounded_matches_full_distance() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("a much longer string", "short"),
+            ("café", "coffee"),
+            ("", "abc"),
+            ("flaw", "lawn"),
+            ("same", "same"),
+        ];
+
+        let mut buffer = LevenshteinBuffer::default();
+        for (a, b) in pairs {
+            let distance = levenshtein_distance(a, b);
+            for max in 0..=20 {
+                assert_eq!(
+                    levenshtein_distance_bounded_with(a, b, max, &mut buffer),
+                    (distance <= max).then_some(distance),
+                    "{a:?} vs {b:?} with max {max}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_banded_matches_full_distance_on_random_inputs() {
+        let mut next = xorshift(0x2545_f491_4f6c_dd1d);
+        // Few distinct letters give many near matches; the non-ASCII ones
+        // take the `char` path.
+        let alphabet = ['a', 'b', 'c', 'd', 'é', 'ß'];
+
+        let mut buffer = LevenshteinBuffer::default();
+        for _ in 0..2_000 {
+            let a: Vec<char> = (0..next(24))
+                .map(|_| alphabet[next(alphabet.len())])
+                .collect();
+            // Mostly a few random edits away from `a`, so that distances
+            // around `max` come up often.
+            let mut b = a.clone();
+            for _ in 0..next(10) {
+                let c = alphabet[next(alphabet.len())];
+                match next(3) {
+                    0 => b.insert(next(b.len() + 1), c),
+                    1 if !b.is_empty() => {
+                        b.remove(next(b.len()));
+                    }
+                    _ if !b.is_empty() => {
+                        let at = next(b.len());
+                        b[at] = c;
+                    }
+                    _ => b.push(c),
+                }
+            }
+            let a: String = a.into_iter().collect();
+            let b: String = b.into_iter().collect();
+            let distance = levenshtein_distance(&a, &b);
+            for max in 0..=8 {
+                assert_eq!(
+                    levenshtein_distance_bounded_with(&a, &b, max, &mut buffer),
+                    (distance <= max).then_some(distance),
+                    "{a:?} vs {b:?} with max {max}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_trimming_keeps_long_string_distances() {
+        let untrimmed = |a: &str, b: &str| {
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            untrimmed_distance(&a, &b)
+        };
+        let long = "This is a very long string that contains many words and \
+                    characters to test the performance of the fuzzy matching";
+        let pairs = [
+            (long, long.replace("many", "some")),
+            (long, long.replace("This", "That")),
+            (long, long.replace("matching", "search")),
+            (long, long.replace("a", "ä")),
+            (long, format!("{long}s")),
+            (long, long[10..].to_string()),
+            // The common prefix and suffix may overlap in the shorter string.
+            ("aaaa", "aaaaaa".to_string()),
+            ("abcab", "ab".to_string()),
+            ("", "abc".to_string()),
+        ];
+
+        let mut buffer = LevenshteinBuffer::default();
+        for (a, b) in &pairs {
+            let expected = untrimmed(a, b);
+            assert_eq!(levenshtein_distance_with(a, b, &mut buffer), expected);
+            assert_eq!(levenshtein_distance_with(b, a, &mut buffer), expected);
+        }
+        assert_eq!(
+            levenshtein_distance(long, &long.replace("many", "some")),
+            4
+        );
+    }
+
+    #[test]
+    fn test_trimmed_distances_match_untrimmed_on_random_inputs() {
+        let mut next = xorshift(0x9e37_79b9_7f4a_7c15);
+        // Alternate between ASCII and the `char` path; spaces make words for
+        // `word_distance`.
+        let alphabets: [&[char]; 2] = [&['a', 'b', ' '], &['a', 'é', 'ß', ' ']];
+
+        let mut buffer = LevenshteinBuffer::default();
+        for round in 0..2_000 {
+            let alphabet = alphabets[round % 2];
+            let kept = next(7);
+            let mut word = |len: usize| -> String {
+                (0..len)
+                    .map(|_| alphabet[next(alphabet.len())])
+                    .collect()
+            };
+            // Shared ends around two random middles, which may themselves
+            // share a prefix or suffix with each other or the ends. `b`
+            // keeps only part of the prefix, so the ends don't line up.
+            let (prefix, suffix) = (word(6), word(6));
+            let a = format!("{prefix}{}{suffix}", word(4));
+            let kept: String = prefix.chars().take(kept).collect();
+            let b = format!("{kept}{}{suffix}", word(5));
+
+            let chars = |s: &str| s.chars().collect::<Vec<_>>();
+            let expected = untrimmed_distance(&chars(&a), &chars(&b));
+            assert_eq!(
+                levenshtein_distance_with(&a, &b, &mut buffer),
+                expected,
+                "{a:?} vs {b:?}"
+            );
+            for max in 0..=6 {
+                assert_eq!(
+                    levenshtein_distance_bounded_with(&a, &b, max, &mut buffer),
+                    (expected <= max).then_some(expected),
+                    "{a:?} vs {b:?} with max {max}"
+                );
+            }
+
+            let bytes = untrimmed_distance(a.as_bytes(), b.as_bytes());
+            let longest = a.len().max(b.len()) as f64;
+            assert_eq!(
+                byte_similarity(a.as_bytes(), b.as_bytes()),
+                1.0 - bytes as f64 / longest
+            );
+
+            fn graphemes(s: &str) -> Vec<&str> {
+                s.graphemes(true).collect()
+            }
+            assert_eq!(
+                grapheme_distance_with(&a, &b, &mut buffer),
+                untrimmed_distance(&graphemes(&a), &graphemes(&b))
+            );
+
+            fn words(s: &str) -> Vec<&str> {
+                s.split_whitespace().collect()
+            }
+            assert_eq!(
+                word_distance_with(&a, &b, &mut buffer),
+                untrimmed_distance(&words(&a), &words(&b))
+            );
+        }
+    }
+
+    #[test]
+    fn test_affine_gaps_favour_one_long_gap() {
+        let one_gap = affine_gap_distance("abcdefgh", "abcdXXXXefgh", 1.0, 0.5);
+        let scattered =
+            affine_gap_distance("abcdefgh", "aXbcXdeXfgXh", 1.0, 0.5);
+        assert_eq!(one_gap, 2.5);
+        assert_eq!(scattered, 4.0);
+
+        // Unit gap costs give the Levenshtein distance back.
+        let mut next = xorshift(11);
+        for _ in 0..200 {
+            let word = |next: &mut dyn FnMut(usize) -> usize| {
+                let len = next(8);
+                (0..len)
+                    .map(|_| (b'a' + next(3) as u8) as char)
+                    .collect::<String>()
+            };
+            let (a, b) = (word(&mut next), word(&mut next));
+            assert_eq!(
+                affine_gap_distance(&a, &b, 1.0, 1.0),
+                levenshtein_distance(&a, &b) as f64,
+                "{a:?} {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wildcard_distance_frees_wildcards() {
+        assert_eq!(wildcard_distance("a*e", "apple"), Some(0));
+        assert_eq!(wildcard_distance("a*e", "ae"), Some(0));
+        assert_eq!(wildcard_distance("a?e", "ace"), Some(0));
+        assert_eq!(wildcard_distance("a?e", "apple"), Some(2));
+        // Literal chars are still matched fuzzily.
+        assert_eq!(wildcard_distance("apl*", "apple pie"), Some(1));
+        assert_eq!(wildcard_distance("a?e", "ae"), Some(1));
+        // Each `?` needs a char of its own.
+        assert_eq!(wildcard_distance("??", "a"), None);
+        assert_eq!(wildcard_distance("*", ""), Some(0));
+        assert_eq!(
+            wildcard_distance("abc", "abd"),
+            Some(levenshtein_distance("abc", "abd"))
+        );
+    }
+
+    #[test]
+    fn test_prepared_query_matches_levenshtein() {
+        let words = ["", "kitten", "sitting", "café", "cafe", "北京", "naïve"];
+        let mut buffer = LevenshteinBuffer::default();
+        let mut query = PreparedQuery::default();
+        for a in words {
+            query.prepare(a);
+            for b in words {
+                assert_eq!(
+                    levenshtein_distance_prepared(&query, b, &mut buffer),
+                    levenshtein_distance(a, b),
+                    "{a:?} vs {b:?}"
+                );
+                assert_eq!(
+                    similarity_prepared(&query, b, &mut buffer),
+                    similarity(a, b)
+                );
+            }
+        }
+        assert_eq!(PreparedQuery::new("naïve").text(), "naïve");
+    }
+
+    #[test]
+    fn test_osa_counts_adjacent_transpositions() {
+        let mut buffer = LevenshteinBuffer::default();
+        let mut osa = |a, b| osa_distance_with(a, b, &mut buffer);
+
+        assert_eq!(osa("ab", "ba"), 1);
+        assert_eq!(osa("abcdef", "abdcef"), 1);
+        assert_eq!(osa("kitten", "sitting"), 3);
+        assert_eq!(osa("café", "caéf"), 1);
+        assert_eq!(osa("", "abc"), 3);
+        // Full Damerau-Levenshtein gives 2 (CA → AC → ABC), but OSA may not
+        // insert between the transposed pair.
+        assert_eq!(osa("CA", "ABC"), 3);
+    }
+
+    #[test]
+    fn test_edits_transform_source_into_target() {
+        let apply = |a: &str, edits: &[EditOp]| {
+            let mut chars: Vec<String> = a.chars().map(String::from).collect();
+            // Applying back to front keeps earlier positions valid.
+            for edit in edits.iter().rev() {
+                let position = edit.position as usize;
+                match edit.op.as_str() {
+                    "insert" => {
+                        chars.insert(position, edit.to.clone().unwrap())
+                    }
+                    "delete" => drop(chars.remove(position)),
+                    _ => chars[position] = edit.to.clone().unwrap(),
+                }
+            }
+            chars.concat()
+        };
+
+        let edits = levenshtein_edits("kitten", "sitting");
+        assert_eq!(
+            edits,
+            [
+                EditOp {
+                    op: "substitute".to_string(),
+                    position: 0,
+                    from: Some("k".to_string()),
+                    to: Some("s".to_string()),
+                },
+                EditOp {
+                    op: "substitute".to_string(),
+                    position: 4,
+                    from: Some("e".to_string()),
+                    to: Some("i".to_string()),
+                },
+                EditOp {
+                    op: "insert".to_string(),
+                    position: 6,
+                    from: None,
+                    to: Some("g".to_string()),
+                },
+            ]
+        );
+
+        for (a, b) in [
+            ("kitten", "sitting"),
+            ("flaw", "lawn"),
+            ("", "abc"),
+            ("café", "cafe"),
+            ("abc", ""),
+            ("prefix-abc-suffix", "prefix-xbcy-suffix"),
+            ("aaaa", "aaaaaa"),
+            ("same", "same"),
+        ] {
+            let edits = levenshtein_edits(a, b);
+            assert_eq!(edits.len(), levenshtein_distance(a, b), "{a} → {b}");
+            assert_eq!(apply(a, &edits), b, "{a} → {b}");
+        }
+        // Positions still count from the start of the untrimmed string.
+        assert_eq!(
+            levenshtein_edits("the cat sat", "the bat sat")[0].position,
+            4
+        );
+    }
+
+    #[test]
+    fn test_jaro_winkler_reference_values() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-3;
+        assert!(close(jaro_similarity("dixon", "dicksonx"), 0.767));
+        assert!(close(jaro_winkler_similarity("dixon", "dicksonx"), 0.813));
+        assert!(close(jaro_winkler_similarity("dwayne", "duane"), 0.840));
+        assert_eq!(jaro_winkler_similarity("", ""), 1.0);
+        assert_eq!(jaro_winkler_similarity("a", ""), 0.0);
+        assert_eq!(jaro_winkler_similarity("ab", "ba"), 0.0);
+    }
+
+    #[test]
+    fn test_weighted_substitutions() {
+        let half = |_, _| 0.5;
+        assert_eq!(
+            weighted_levenshtein_distance("kitten", "sitting", half),
+            2.0
+        );
+        assert_eq!(weighted_levenshtein_distance("abc", "abc", half), 0.0);
+        assert_eq!(weighted_levenshtein_distance("", "ab", half), 2.0);
+
+        let full = |_, _| 1.0;
+        assert_eq!(
+            weighted_levenshtein_distance("kitten", "sitting", full),
+            levenshtein_distance("kitten", "sitting") as f64
+        );
+    }
+
+    #[test]
+    fn test_graphemes_count_as_one_edit() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        assert_eq!(levenshtein_distance(family, "👨"), 4);
+        assert_eq!(grapheme_distance(family, "👨"), 1);
+        assert_eq!(grapheme_distance("a🇺🇸b", "ab"), 1);
+        assert_eq!(grapheme_distance("cafe\u{301}", "cafe"), 1);
+        assert_eq!(grapheme_distance("a\r\nb", "ab"), 1);
+        assert_eq!(levenshtein_distance("a\r\nb", "ab"), 2);
+    }
+}
@@ -0,0 +1,27 @@
+use napi_derive::napi;
+
+/// One component of a blended score, see [`crate::SearchOptions::algorithm`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface AlgorithmWeight {
+///   name: "levenshtein" | "osa" | "word-level" | "jaro-winkler" | "alignment";
+///   weight?: number; // default: 1
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AlgorithmWeight {
+    /// Which similarity this component computes.
+    pub name: String,
+
+    /// Share of this component in the blended score, relative to the other
+    /// components' weights. Must be finite and non-negative.
+    /// Defaults to `1.0`.
+    pub weight: Option<f64>,
+}
@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+/// A small least-recently-used cache.
+///
+/// Entries are kept in a `VecDeque` ordered from most to least recently used,
+/// so lookups are linear in the number of entries. This is intended for the
+/// small capacities used by [`crate::Searcher`], where a linear scan over a few
+/// dozen keys is cheaper than maintaining a hash map alongside the ordering.
+///
+/// A capacity of `0` disables caching entirely.
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    entries: VecDeque<(K, V)>,
+}
+
+impl<K: PartialEq, V> LruCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the value stored for `key`, marking it as most recently used.
+    pub(crate) fn get(&mut self, key: &K) -> Option<&V> {
+        let position = self
+            .entries
+            .iter()
+            .position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(position)?;
+        self.entries.push_front(entry);
+        self.entries.front().map(|(_, v)| v)
+    }
+
+    /// Inserts `value` for `key`, evicting the least recently used entry if
+    /// the cache is full.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(position) = self
+            .entries
+            .iter()
+            .position(|(k, _)| *k == key)
+        {
+            self.entries.remove(position);
+        } else if self.entries.len() == self.capacity {
+            self.entries.pop_back();
+        }
+
+        self.entries.push_front((key, value));
+    }
+
+    /// Removes every entry from the cache.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"c"), Some(&3));
+    }
+}
@@ -0,0 +1,29 @@
+use napi_derive::napi;
+
+/// One step of an edit script, see [`crate::levenshtein_edits`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface EditOp {
+///   op: "insert" | "delete" | "substitute";
+///   position: number; // char offset in the source string
+///   from?: string;    // char removed or replaced, unset for inserts
+///   to?: string;      // char inserted or written, unset for deletes
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditOp {
+    /// `"insert"`, `"delete"` or `"substitute"`.
+    pub op: String,
+
+    /// Offset, in `char`s of the unedited source string, where the edit
+    /// applies. An insert goes before the `char` at this offset.
+    pub position: u32,
+
+    /// The source `char` deleted or replaced; `None` for inserts.
+    pub from: Option<String>,
+
+    /// The target `char` inserted or written; `None` for deletes.
+    pub to: Option<String>,
+}
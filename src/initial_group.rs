@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use napi_derive::napi;
+
+use crate::SearchResult;
+
+/// The key of the [`InitialGroup`] for items that don't start with a letter.
+pub const OTHER_INITIAL: &str = "#";
+
+/// Search results whose normalized items start with the same letter, one
+/// group of [`crate::search_grouped_by_initial`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface InitialGroup {
+///   initial: string;         // e.g. "a", or "#" for digits and symbols
+///   results: SearchResult[]; // sorted by descending score
+/// }
+/// ```
+#[napi(object)]
+pub struct InitialGroup {
+    /// The first letter of every item in the group, or [`OTHER_INITIAL`].
+    pub initial: String,
+
+    /// The results in this group, in ranking order.
+    pub results: Vec<SearchResult>,
+}
+
+/// Splits ranked `results` by the letter `initial` finds at the start of
+/// each one, keeping the ranking order within each group.
+///
+/// Groups are ordered by code point, after the [`OTHER_INITIAL`] group for
+/// results whose initial is `None` or not alphabetic. Only non-empty groups
+/// are returned.
+pub(crate) fn group_by_initial(
+    results: Vec<SearchResult>,
+    mut initial: impl FnMut(&SearchResult) -> Option<char>,
+) -> Vec<InitialGroup> {
+    let mut groups: BTreeMap<Option<char>, Vec<SearchResult>> = BTreeMap::new();
+    for result in results {
+        let letter = initial(&result).filter(|c| c.is_alphabetic());
+        groups
+            .entry(letter)
+            .or_default()
+            .push(result);
+    }
+
+    groups
+        .into_iter()
+        .map(|(letter, results)| InitialGroup {
+            initial: letter
+                .map_or_else(|| OTHER_INITIAL.to_string(), String::from),
+            results,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(item: &str, score: f64) -> SearchResult {
+        SearchResult {
+            item: item.to_string(),
+            score,
+            index: 0,
+            explanation: None,
+            query_index: None,
+            raw_score: None,
+            normalized_item: None,
+            alias_index: None,
+            token_scores: None,
+        }
+    }
+
+    #[test]
+    fn test_non_letters_share_the_catch_all_group() {
+        let results = vec![
+            result("beta", 0.9),
+            result("1st", 0.8),
+            result("alpha", 0.7),
+            result("bravo", 0.6),
+            result("", 0.5),
+            result("(a)", 0.4),
+            result("émile", 0.3),
+        ];
+        let groups = group_by_initial(results, |r| r.item.chars().next());
+
+        let items: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|g| {
+                let items = g
+                    .results
+                    .iter()
+                    .map(|r| r.item.as_str())
+                    .collect();
+                (g.initial.as_str(), items)
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("#", vec!["1st", "", "(a)"]),
+                ("a", vec!["alpha"]),
+                ("b", vec!["beta", "bravo"]),
+                ("é", vec!["émile"]),
+            ]
+        );
+    }
+}
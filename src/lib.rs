@@ -11,35 +11,647 @@
 //!   query: string,
 //!   items: string[],
 //!   options?: {
-//!     limit?: number;        // default: 10
+//!     limit?: number | null; // default: 10; null for no limit
+//!     offset?: number;       // default: 0
 //!     threshold?: number;    // default: 0.0
 //!     normalize?: boolean;   // default: true
 //!     ignoreCase?: boolean;  // default: true
+//!     explain?: boolean;     // default: false
+//!     requireAllTokens?: boolean; // default: false
+//!     tokenThreshold?: number;    // default: 0.6
+//!     maxInputLength?: number;    // default: 10000
+//!     turkishI?: boolean;         // default: false
+//!     stem?: boolean;             // default: false
+//!     stopwords?: string[];       // default: []
+//!     exactSubstring?: boolean;   // default: false
+//!     weights?: number[];         // default: []
+//!     includeRawScore?: boolean;  // default: false
+//!     minQueryLength?: number;    // default: 0
+//!     emptyQueryReturnsAll?: boolean; // default: false
+//!     alphanumericOnly?: boolean; // default: false
+//!     scoreFormula?: "distance" | "ratio"; // default: "distance"
+//!     compatibility?: boolean;    // default: false
+//!     parallelThreshold?: number; // default: 1000
+//!     ignoreChars?: string;       // default: ""
+//!     boostSuffix?: number;       // default: 0
+//!     foldConfusables?: boolean;  // default: false
+//!     lengthPenalty?: number;     // default: 0
+//!     combineFields?: "max" | "sum"; // default: "max"
+//!     maxDistance?: number;       // default: unset
+//!     algorithm?: "levenshtein" | "osa" | "word-level" | "alignment"
+//!       | AlgorithmWeight[];
+//!                                 // default: "levenshtein"
+//!     phonetic?: boolean;         // default: false
+//!     includeNormalized?: boolean; // default: false
+//!     graphemes?: boolean;        // default: false
+//!     cacheNormalization?: boolean; // default: false
+//!     sortKeys?: number[];        // default: []
+//!     accentCost?: number;        // default: unset
+//!     naturalOrder?: boolean;     // default: false
+//!     scoreDenominator?: "max" | "min" | "query" | "item";
+//!                                 // default: "max"
+//!     preserveWhitespace?: boolean; // default: false
+//!     precision?: number;         // default: unset
+//!     locale?: string;            // default: unset
+//!     positionDecay?: number;     // default: 1
+//!     latinDiacriticsOnly?: boolean; // default: false
+//!     keepLetters?: string;       // default: ""
+//!     prefixBoostWeight?: number; // default: 0
+//!     prefixBoostLength?: number; // default: 4
+//!     includeTies?: boolean;      // default: false
+//!     expandLigatures?: boolean;  // default: false
+//!     streamOrder?: "corpus" | "score"; // default: "corpus"
+//!     foldDigits?: boolean;       // default: false
+//!     sampleLimit?: number;       // default: unset
+//!     sampleSeed?: number;        // default: 0
+//!     normalizer?: (s: string) => string; // default: unset
+//!     wildcard?: boolean;         // default: false
+//!     minTrigramOverlap?: number; // default: 0
+//!     gapOpen?: number;           // default: 1
+//!     gapExtend?: number;         // default: 0.5
+//!     trim?: boolean;             // default: false
+//!     wordCountBonus?: number;    // default: 0
+//!     stripControl?: boolean;     // default: false
+//!     includeTokenScores?: boolean; // default: false
+//!     foldSuperscripts?: boolean; // default: false
+//!     scoreFloor?: number;        // default: 0
+//!     scoreCeil?: number;         // default: 1
+//!     unicodeWords?: boolean;     // default: false
 //!   }
-//! ): Array<{ item: string; score: number; index: number }>
+//! ): Array<{
+//!   item: string;
+//!   score: number;
+//!   index: number;
+//!   rawScore?: number; // with includeRawScore
+//!   normalizedItem?: string; // with includeNormalized
+//!   explanation?: {
+//!     distance: number;
+//!     maxLength: number;
+//!     algorithm: string;
+//!     boosts: Array<{ name: string; value: number }>;
+//!   };
+//! }>
 //!
-//! // fuzzy(a, b, normalize?)
-//! declare function fuzzy(a: string, b: string, normalize?: boolean): number
+//! // searchWithCount(query, items, options?) — `total` counts every result
+//! // that passed `threshold`, before `limit` truncated the list, and
+//! // `itemCount` is the corpus size, telling an empty corpus apart from one
+//! // where nothing matched. `approximate` is set when `sampleLimit` left
+//! // items unscored.
+//! declare function searchWithCount(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): {
+//!   results: SearchResult[];
+//!   total: number;
+//!   itemCount: number;
+//!   approximate: boolean;
+//! }
+//!
+//! // bestMatch(query, items, options?) — the top result, or null when nothing
+//! // passes `threshold`.
+//! declare function bestMatch(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): SearchResult | null
+//!
+//! // bestMatchWithEdits(query, items, options?) — `bestMatch` plus the
+//! // edits turning the normalized query into the normalized match.
+//! declare function bestMatchWithEdits(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): {
+//!   result: SearchResult;
+//!   edits: Array<{
+//!     op: "insert" | "delete" | "substitute";
+//!     position: number; // char offset in the query
+//!     from?: string;
+//!     to?: string;
+//!   }>;
+//! } | null
+//!
+//! // searchIndices(query, items, options?) — like `search`, without copying
+//! // the item strings back to JS.
+//! declare function searchIndices(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): Array<{ index: number; score: number }>
+//!
+//! // searchArrays(query, items, options?) — like `searchIndices`, as two
+//! // parallel typed arrays: result `i` is `items[indices[i]]`, scoring
+//! // `scores[i]`.
+//! declare function searchArrays(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): { indices: Uint32Array; scores: Float64Array }
+//!
+//! // searchStream(query, items, onResult, options?) — scores off the JS
+//! // thread and calls `onResult` for each match as it is found, in corpus
+//! // order unless `streamOrder: "score"`; resolves with the result count.
+//! declare function searchStream(
+//!   query: string,
+//!   items: string[],
+//!   onResult: (result: SearchResult) => void,
+//!   options?: SearchOptions
+//! ): Promise<number>
+//!
+//! // searchGroupedByInitial(query, items, options?) — results grouped by the
+//! // first letter of the normalized item, "#" (first) for anything else.
+//! declare function searchGroupedByInitial(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): Array<{ initial: string; results: SearchResult[] }>
+//!
+//! // searchGrouped(query, items, edges, options?) — results split into score
+//! // buckets, highest first; each bucket includes its lower edge.
+//! declare function searchGrouped(
+//!   query: string,
+//!   items: string[],
+//!   edges: number[],        // e.g. [0.9, 0.7] → ">=0.9", "0.7-0.9", "<0.7"
+//!   options?: SearchOptions
+//! ): Array<{
+//!   label: string;
+//!   min?: number;
+//!   max?: number;
+//!   results: SearchResult[];
+//! }>
+//!
+//! // searchAny(queries, items, options?) — best score over all queries;
+//! // each result also carries `queryIndex`, the query that matched best.
+//! declare function searchAny(
+//!   queries: string[],
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): Array<SearchResult & { queryIndex?: number }>
+//!
+//! // searchMulti(queries, items, aggregator, options?) — combines each
+//! // item's scores against every query: its best ("max"), worst ("min") or
+//! // average ("mean"); `queryIndex` is the deciding query for max and min.
+//! declare function searchMulti(
+//!   queries: string[],
+//!   items: string[],
+//!   aggregator: "max" | "mean" | "min",
+//!   options?: SearchOptions
+//! ): Array<SearchResult & { queryIndex?: number }>
+//!
+//! // searchAliases(query, groups, options?) — each group of aliases scores
+//! // its best alias and is returned as its first one; `index` is the
+//! // group's and `aliasIndex` the alias that matched.
+//! declare function searchAliases(
+//!   query: string,
+//!   groups: string[][], // e.g. [["United States", "USA", "US"], ...]
+//!   options?: SearchOptions
+//! ): Array<SearchResult & { aliasIndex?: number }>
+//!
+//! // searchBy(query, records, keys, options?) — searches several string
+//! // properties of each record, weighting them per key.
+//! declare function searchBy(
+//!   query: string,
+//!   records: object[],
+//!   keys: Array<{ key: string; weight?: number }>, // weight default: 1
+//!   options?: SearchOptions
+//! ): Array<{
+//!   index: number;
+//!   score: number;
+//!   field: string; // key of the best-scoring field
+//! }>
+//!
+//! // suggestThreshold(query, items, options?) — a `threshold` in the widest
+//! // gap between the items' scores; a heuristic for auto-tuning UIs.
+//! declare function suggestThreshold(
+//!   query: string,
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): number
+//!
+//! // similarityMatrix(items, options?) — all-pairs scores, symmetric with
+//! // 1.0 on the diagonal; O(n²), for clustering small sets.
+//! declare function similarityMatrix(
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): number[][]
+//!
+//! // similarityPairs(items, options?) — the pairs `i < j` of
+//! // `similarityMatrix` scoring at least `threshold`, without the dense
+//! // n × n output; still O(n²) time, spread across threads.
+//! declare function similarityPairs(
+//!   items: string[],
+//!   options?: SearchOptions
+//! ): Array<{ i: number; j: number; score: number }>
+//!
+//! // fuzzy(a, b, options?) — `options` is either the legacy `normalize` flag
+//! // or the same options as `search`, e.g. `{ ignoreCase: false }`.
+//! declare function fuzzy(
+//!   a: string,
+//!   b: string,
+//!   options?: boolean | SearchOptions
+//! ): number
+//!
+//! // score(query, item, options?) — the score `search` gives `item` for
+//! // `query` with the same options, ignoring `threshold` and `weights`.
+//! declare function score(
+//!   query: string,
+//!   item: string,
+//!   options?: SearchOptions
+//! ): number
+//!
+//! // scoreDetailed(a, b, options?) — `fuzzy(a, b, options)` along with the
+//! // edit distance it was computed from; identical strings are distance 0.
+//! declare function scoreDetailed(
+//!   a: string,
+//!   b: string,
+//!   options?: SearchOptions
+//! ): { distance: number; similarity: number }
+//!
+//! // isMatch(a, b, threshold, options?) — whether `fuzzy(a, b, options)` is
+//! // at least `threshold`, stopping early once it can't be.
+//! declare function isMatch(
+//!   a: string,
+//!   b: string,
+//!   threshold: number,
+//!   options?: SearchOptions
+//! ): boolean
+//!
+//! // similarity(a, b) — normalized Levenshtein similarity, without any
+//! // normalization of the inputs.
+//! declare function similarity(a: string, b: string): number
+//!
+//! // fuzzyBytes(a, b) — `similarity` over raw bytes, one edit per byte, for
+//! // keys like hashes that aren't valid UTF-8; nothing is normalized.
+//! declare function fuzzyBytes(a: Buffer, b: Buffer): number
+//!
+//! // normalizeAscii(input, toLowercase?) — lowercases ASCII letters (unless
+//! // `toLowercase` is false) and collapses whitespace, skipping all Unicode
+//! // normalization; the result is unspecified for non-ASCII input.
+//! declare function normalizeAscii(
+//!   input: string,
+//!   toLowercase?: boolean
+//! ): string
+//!
+//! // setNormalizationCacheSize(size) — empties and resizes the cache used by
+//! // `cacheNormalization`; 0 disables it.
+//! declare function setNormalizationCacheSize(size: number): void
+//!
+//! // Reusable searcher over a fixed corpus, with a cache of recent queries.
+//! declare class Searcher {
+//!   constructor(
+//!     items: string[],
+//!     options?: {
+//!       normalize?: boolean;   // default: true
+//!       ignoreCase?: boolean;  // default: true
+//!       cacheSize?: number;    // default: 32
+//!       minSharedGrams?: number; // default: unset (score every item)
+//!       prefixIndex?: boolean;   // default: false
+//!       maxInputLength?: number; // default: 10000
+//!       turkishI?: boolean;      // default: false
+//!       stem?: boolean;          // default: false
+//!       stopwords?: string[];    // default: []
+//!       alphanumericOnly?: boolean; // default: false
+//!       compatibility?: boolean;  // default: false
+//!       ignoreChars?: string;     // default: ""
+//!       foldConfusables?: boolean; // default: false
+//!       preserveWhitespace?: boolean; // default: false
+//!       locale?: string;          // default: unset
+//!       latinDiacriticsOnly?: boolean; // default: false
+//!       keepLetters?: string;     // default: ""
+//!       expandLigatures?: boolean; // default: false
+//!       foldDigits?: boolean;     // default: false
+//!       trim?: boolean;           // default: false
+//!       stripControl?: boolean;   // default: false
+//!       foldSuperscripts?: boolean; // default: false
+//!     }
+//!   );
+//!   query(
+//!     query: string,
+//!     options?: {
+//!       limit?: number | null;
+//!       offset?: number;
+//!       threshold?: number;
+//!       explain?: boolean;
+//!       minQueryLength?: number;
+//!     }
+//!   ): Array<SearchResult>;
+//!   // Items whose normalized form starts with the normalized query,
+//!   // shortest first; `score` is the fraction of the item the query covers.
+//!   prefixSearch(
+//!     query: string,
+//!     options?: { limit?: number | null; offset?: number }
+//!   ): Array<SearchResult>;
+//!   setItems(items: string[]): void;
+//!   addItems(items: string[]): void; // appends; indices continue from the end
+//!   removeItem(index: number): boolean; // tombstones; indices stay stable
+//!   update(index: number, newItem: string): boolean; // replaces in place
+//!   clear(): void;
+//!   readonly cacheHits: number;
+//! }
 //! ```
 //!
 //! ## Notes
 //! - When `normalize` is enabled, strings are Unicode-normalized and diacritics
-//!   are removed; when `ignoreCase` is enabled, comparison is case-insensitive.
-//! - Scores are in `0.0..=1.0` (higher is better). `threshold` filters out
-//!   results below the given score. `limit` truncates the final sorted list.
+//!   are removed; when `ignoreCase` is enabled, comparison is case-insensitive
+//!   using Unicode case folding (`"straße"` matches `"STRASSE"`). The two are
+//!   independent: `ignoreCase: false` with `normalize` still matches `"Café"`
+//!   to `"Cafe"`, but not to `"cafe"`. `turkishI`
+//!   switches to the Turkish rules for dotted and dotless I, as does a
+//!   Turkish or Azeri `locale` such as `"tr"`. `compatibility`
+//!   switches to NFKD/NFKC so full-width and half-width forms (common in CJK
+//!   text) match their ordinary counterparts. `foldConfusables` maps
+//!   non-ASCII look-alikes (Cyrillic `а`, Greek `ο`, …) to the characters they
+//!   imitate, using the Unicode confusables table. `latinDiacriticsOnly`
+//!   limits accent stripping to Latin letters, so `"café"` still matches
+//!   `"cafe"` while Hebrew points or Greek tonos have to match, and
+//!   `keepLetters` protects individual letters such as `"ñ"` from it.
+//!   `expandLigatures` spells out `æ`, `œ` and ligatures such as `ﬁ`, so
+//!   `"encyclopædia"` matches `"encyclopaedia"`, and `foldSuperscripts`
+//!   spells out superscripts, subscripts and fractions, so `"m²"` matches
+//!   `"m2"` and `"½"` matches `"1/2"`.
+//! - Scores are in `0.0..=1.0` (higher is better) unless `weights` scale
+//!   them or `scoreFloor` and `scoreCeil` narrow them: any score above
+//!   `0.0` is raised to at least `scoreFloor` and every score is capped at
+//!   `scoreCeil`, both before `weights` and `threshold` apply.
+//!   `threshold` filters out results below the given score. `offset`
+//!   skips that many results from the sorted list and `limit` truncates what
+//!   remains, so together with `searchWithCount` they support pagination.
+//! - `includeTies` lets `limit` keep every result tied with the last one it
+//!   would keep, so with `limit: 3` and scores `[1, 0.8, 0.5, 0.5, 0.5,
+//!   0.2]` five results come back instead of an arbitrary two of the three
+//!   `0.5`s.
+//! - A missing `limit` keeps the default of 10 results, `limit: null`
+//!   returns every match and `limit: 0` returns none. Fractional limits are
+//!   rounded down and `Infinity` means no limit, while negative and `NaN`
+//!   limits are rejected with an error instead of wrapping around.
+//! - `alphanumericOnly` keeps only letters and digits after the other
+//!   normalization steps, so part numbers like `"ABC-123"` and `"abc 123"`
+//!   match exactly. It also removes whitespace, which makes each string a
+//!   single token for `requireAllTokens` and `stopwords`.
+//! - `preserveWhitespace` skips whitespace collapsing, so leading,
+//!   trailing and repeated spaces, tabs and newlines are compared as they
+//!   are, e.g. when matching code. Token-based options still split on
+//!   whitespace.
+//! - `stripControl` removes control and zero-width characters, like the
+//!   U+200B and U+FEFF that text copied from web pages often carries,
+//!   before any other normalization step. Whitespace is kept.
+//! - `trim` drops leading and trailing whitespace even when it isn't
+//!   collapsed, so with `normalize: false` or `preserveWhitespace` a query
+//!   like `"  apple "` still matches `"apple"` exactly, while the spaces
+//!   inside are kept.
+//! - `ignoreChars` removes the given characters from the query and items
+//!   after case folding, so `ignoreChars: "-"` makes `"555-1234"` match
+//!   `"5551234"`.
+//! - `foldDigits` replaces every ASCII digit with the same placeholder, so
+//!   numbers only match by their shape: `"room 101"` matches `"room 202"`
+//!   exactly, for matching strings generated from a template.
+//! - `stopwords` drops the listed words and `stem` reduces the remaining words
+//!   to their English stems, so `"running shoes"` matches `"run shoe"`. Both
+//!   run after the other normalization steps and before scoring.
+//! - `normalizer` replaces all of the above with a JS function of your own,
+//!   e.g. `(s) => s.toUpperCase()`. It is called once per item on every
+//!   search, and each call crosses from Rust into JS and back, which can cost
+//!   more than the scoring; to search the same items repeatedly, normalize
+//!   them once yourself and build a `Searcher` over the results.
+//! - `requireAllTokens` turns search into a filter: every whitespace-separated
+//!   query token must match some item token with at least `tokenThreshold`,
+//!   otherwise the item scores `0.0`. Matching items score the average of their
+//!   per-token best matches. With a `positionDecay` below `1` that average is
+//!   weighted towards the first query tokens, so `"red car"` and `"car red"`
+//!   can score differently.
+//! - `maxInputLength` guards against pathological inputs: a longer query is
+//!   rejected with an error, and longer items score `0.0` without being
+//!   compared. `fuzzy`, `similarity` and `fuzzyBytes` apply the default cap
+//!   to both of their arguments.
+//! - `minQueryLength` returns no results while the normalized query is
+//!   shorter than the given number of characters, like a "type at least 2
+//!   characters" hint. With any minimum above `0` this includes the empty
+//!   query. `searchAny` ignores the short queries and keeps the others.
+//! - An empty query scores `0.0` against every non-empty item, so it returns
+//!   nothing useful. `emptyQueryReturnsAll` makes it match every item with
+//!   `1.0` instead, in corpus order. `minQueryLength` is checked first.
+//! - `exactSubstring` replaces fuzzy scoring with a containment check: items
+//!   containing the (normalized) query score `1.0` and the rest `0.0`. Ties
+//!   keep corpus order, so matches come back in their original order.
+//! - `wildcard` reads `*` in the query as any run of chars and `?` as any
+//!   single char, and scores only the rest fuzzily, so `"a*e"` matches
+//!   `"apple"` and `"a?e"` matches `"ace"` exactly. Items must fit the
+//!   pattern: one shorter than its `?`s scores `0.0`.
+//! - `minTrigramOverlap` skips the edit-distance DP, and the result, for
+//!   items sharing fewer distinct character trigrams with the query. A
+//!   cutoff of `1` or `2` is a cheap speed-up over corpora of mostly
+//!   unrelated items; higher ones start dropping close matches, since one
+//!   typo changes up to three trigrams.
+//! - `weights` scales each item's score by a caller-supplied factor before
+//!   `threshold` and sorting, so a popular item can outrank a slightly closer
+//!   match. `score` is the weighted value; `includeRawScore` also reports the
+//!   unweighted one as `rawScore`. A `weights` array whose length differs from
+//!   `items` is an error.
+//! - `scoreFormula` picks how the edit distance becomes a score. `"distance"`
+//!   divides it by the longer length, so one typo in a 3-letter word costs a
+//!   third of the score. `"ratio"` divides it by the sum of both lengths, as
+//!   RapidFuzz's `ratio` does, which halves the penalty and ranks short
+//!   strings more forgivingly. Unlike RapidFuzz, a substitution still counts
+//!   as a single edit.
+//! - `scoreDenominator` picks the length `"distance"` divides by: the
+//!   longer string (`"max"`), the shorter (`"min"`), or always the query's
+//!   or the item's. `"app"` scores `0.6` against `"apple"` with `"max"` or
+//!   `"item"`, and `0.33` with `"min"` or `"query"`, which divide the same
+//!   two edits by 3. Scores below `0.0` are clamped.
+//! - Searches over at least `parallelThreshold` items (1000 by default) are
+//!   scored on all cores; smaller ones stay on the calling thread, where they
+//!   finish before a thread pool would pay off. Results are the same either
+//!   way.
+//! - `boostSuffix` adds a bonus to items ending with the normalized query, so
+//!   a file-extension query like `"pdf"` favours `"report.pdf"`. Boosted
+//!   scores are capped at `1.0`; `explain` lists the bonus under `boosts`.
+//! - `prefixBoostWeight` adds the Winkler prefix bonus of Jaro-Winkler to
+//!   any algorithm: each shared leading character, up to
+//!   `prefixBoostLength` (4 by default), closes that fraction of the gap to
+//!   `1.0`, so `"martha"` scores higher against `"marhta"` than against
+//!   `"amrtha"`. `0.1` is Jaro-Winkler's scale; the result is capped at
+//!   `1.0` and listed under `boosts` by `explain`.
+//! - `lengthPenalty` multiplies the score by `(shorter / longer) ^ exponent`,
+//!   so `"cat"` stops matching `"cataclysmic"` well while `"cat"`/`"car"`
+//!   keep their score. It applies before `boostSuffix`, and the exponent
+//!   must not be negative.
+//! - `includeTokenScores` adds `tokenScores` to each result: for each query
+//!   token, the score of its most similar item token, the same per-token
+//!   scores `requireAllTokens` averages. With `"red car"`, `"red bus"` gets
+//!   `[1, 0]`, showing which query word went unmatched.
+//! - `wordCountBonus` mixes a word-count match into token-based scores
+//!   (`requireAllTokens` or `"word-level"`), so for `"john smith"` the
+//!   two-word `"John Smith"` outranks `"John Smith Jr"` although both match
+//!   every query word. `explain` lists it under `boosts` as `"word-count"`.
+//! - `searchBy` scores each key's field, multiplies it by the key's weight
+//!   and keeps the best one, or with `combineFields: "sum"` adds them up so
+//!   records matching in several fields rank higher. Missing or non-string
+//!   fields score `0.0`.
+//! - `maxDistance` drops items more than the given number of edits away from
+//!   the query, measured on the normalized strings. The check stops early for
+//!   far-apart strings, so it also speeds up large searches.
+//! - `algorithm: "osa"` scores with the Optimal String Alignment distance,
+//!   where swapping two adjacent characters (`"form"`/`"from"`) is one edit
+//!   instead of two. Unlike full Damerau-Levenshtein, a swapped pair can't
+//!   be edited again, so `"CA"` → `"ABC"` still takes 3 edits.
+//! - `algorithm: "word-level"` computes the Levenshtein distance over
+//!   whitespace-separated words instead of characters, for matching
+//!   sentences: `"the quick fox"` is one deleted word away from `"the fox"`
+//!   and scores `1 - 1/3`, however long the word. Lengths and `maxDistance`
+//!   are counted in words too.
+//! - `algorithm` also takes a list of `{ name, weight }` entries and scores
+//!   with the weighted mean of their similarities, e.g.
+//!   `[{ name: "levenshtein" }, { name: "jaro-winkler" }]` to balance typo
+//!   tolerance with a bonus for a shared prefix. Weights are relative and
+//!   default to 1. `"jaro-winkler"` is only available in a list, since it
+//!   isn't an edit distance that `maxDistance` could bound.
+//! - `algorithm: "alignment"` aligns the strings with affine gap costs
+//!   (Gotoh's variant of Needleman-Wunsch): a run of `k` inserted or deleted
+//!   chars costs `gapOpen + (k - 1) * gapExtend`, by default `1` and `0.5`,
+//!   so one long gap costs less than as many scattered ones. The cost is
+//!   divided by that of the worst alignment: substituting every char when
+//!   the lengths are equal, otherwise the shorter string plus one gap for
+//!   the rest, so unrelated strings score `0`. Like Levenshtein it takes
+//!   `O(len(query) · len(item))` time; `maxDistance` still counts
+//!   Levenshtein edits.
+//! - `sampleLimit` caps how many items are scored: larger corpora are
+//!   searched through a uniform random sample of that many items, so the
+//!   results are approximate and a better match outside the sample is
+//!   missed. The sample is drawn by reservoir sampling seeded with
+//!   `sampleSeed`, so a given seed always picks the same items and repeated
+//!   searches agree. Every function searching a list of items samples it,
+//!   from `search` and `bestMatch` to `searchAny`, `searchAliases`,
+//!   `searchBy` and `suggestThreshold`; only `similarityMatrix` and
+//!   `similarityPairs`, which compare items with each other, ignore it.
+//!   `searchWithCount` reports `approximate: true` when items were left out,
+//!   and its `total` only counts sampled matches.
+//! - With a `limit`, only the best `offset + limit` matches are kept while
+//!   scanning, so memory stays small even when `threshold` lets most items
+//!   through. Results are identical to sorting every match.
+//! - `phonetic` gives `1.0` to items whose words share Soundex codes with the
+//!   query's (`"Robert"`/`"Rupert"` are both `R163`); everything else is
+//!   scored as usual. Only ASCII letters are coded, so keep `normalize` on.
+//! - `includeNormalized` adds `normalizedItem` to each result: the item as it
+//!   was compared, after every normalization step, for debugging matches.
+//! - `graphemes` measures distances in user-perceived characters: replacing
+//!   the flag in `"flag 🇺🇸"` with `"🇫🇷"` is one edit instead of two, and a
+//!   family emoji counts as one character rather than five.
+//! - `unicodeWords` makes token-based scoring split words at Unicode word
+//!   boundaries rather than whitespace: `"new-york"` matches the tokens of
+//!   `"New York"`, and `"東京"` is two tokens where whitespace finds one.
+//! - `cacheNormalization` remembers normalized items across searches, up to
+//!   `setNormalizationCacheSize` entries (10000 by default), evicting the
+//!   least recently used. Entries are keyed by the string and every
+//!   normalization option, so they are shared only between searches that
+//!   would normalize the same way. The cache is global and locked per lookup,
+//!   which parallel searches over a cold cache can contend on; a `Searcher`
+//!   remains the faster choice for a fixed corpus.
+//! - `sortKeys` breaks ties between equal scores, higher key first, e.g. to
+//!   list the most recent of several equally good matches first. Without
+//!   it, ties keep their corpus order. Keys cost one array lookup per
+//!   comparison; there is no JS comparator callback, since calling into
+//!   JS for every tie would cost more than the search itself.
+//! - `accentCost` keeps diacritics instead of stripping them, and makes
+//!   swapping a letter for an accented form of it (`e`/`é`) cost that much
+//!   instead of a full edit. `"resume"` then still matches `"résumé"`
+//!   closely, but no longer perfectly; `explain` reports the unweighted
+//!   edit count.
+//! - `naturalOrder` orders the remaining ties by comparing the items with
+//!   numbers read as numbers, so `"item2"` lands before `"item10"` instead
+//!   of after it. It only runs on ties, after `sortKeys`.
+//! - `precision` rounds returned scores to that many decimal places, so
+//!   `5 / 6` comes back as `0.83` with `precision: 2` and can be compared
+//!   with `===`. Results are filtered and ranked on the exact scores first.
+//! - Rust callers can use `search_ref`, which borrows `&[&str]` items and
+//!   returns `(item, score, index)` tuples pointing into them instead of
+//!   cloning every match.
+//! - Rust callers can enable the `serde` feature to serialize
+//!   `SearchResult`s and read `SearchOptions` from config files; field names
+//!   are the camelCase ones shown above.
+//! - `explain` attaches the raw edit distance and length behind each score,
+//!   which helps when tuning `threshold`.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use rayon::prelude::*;
 
 mod algo;
+mod algorithm_weight;
+mod cache;
+mod edit_op;
+mod initial_group;
+mod match_with_edits;
 mod normalization;
+mod normalization_cache;
+mod normalization_options;
+mod normalizer;
+mod phonetic;
+mod prefix_trie;
+mod ranking;
+mod sample;
+mod score_details;
+mod score_explanation;
+mod score_group;
+mod scored_index;
+mod scorer;
+mod search_arrays;
+mod search_by;
+mod search_by_result;
+mod search_key;
 mod search_options;
 mod search_result;
+mod search_results;
+mod search_stream;
+mod searcher;
+mod searcher_options;
+mod similarity_pair;
+mod top_k;
+mod trigram_index;
 
-pub use algo::levenshtein_distance;
-pub use normalization::normalize_string;
+pub use algo::{
+    apply_winkler_boost, byte_similarity, grapheme_distance,
+    grapheme_distance_with, jaro_similarity, jaro_winkler_similarity,
+    levenshtein_distance, levenshtein_distance_bounded_with,
+    levenshtein_distance_with, levenshtein_edits, osa_distance,
+    osa_distance_with, similarity, similarity_with, word_distance,
+    word_distance_with, LevenshteinBuffer,
+};
+pub use algorithm_weight::AlgorithmWeight;
+pub use edit_op::EditOp;
+pub use initial_group::{InitialGroup, OTHER_INITIAL};
+pub use match_with_edits::MatchWithEdits;
+pub use normalization::{
+    normalize_ascii, normalize_string, normalize_with, normalize_with_map,
+};
+pub use normalization_cache::DEFAULT_NORMALIZATION_CACHE_SIZE;
+pub use normalization_options::{NormalizationForm, NormalizationOptions};
+pub use normalizer::Normalizer;
+pub use phonetic::soundex;
+pub use score_details::ScoreDetails;
+pub use score_explanation::{ScoreBoost, ScoreExplanation};
+pub use score_group::ScoreGroup;
+pub use scored_index::ScoredIndex;
+pub use search_arrays::SearchArrays;
+pub use search_by_result::SearchByResult;
+pub use search_key::SearchKey;
 pub use search_options::SearchOptions;
 pub use search_result::SearchResult;
+pub use search_results::SearchResults;
+pub use search_stream::SearchStream;
+pub use searcher::Searcher;
+pub use searcher_options::SearcherOptions;
+pub use similarity_pair::SimilarityPair;
+
+use initial_group::group_by_initial;
+use ranking::{Corpus, Ranking};
+use sample::{is_sampled, Sample};
+use score_group::group_by_score;
+use scorer::Scorer;
+use top_k::TopK;
 
 #[napi]
 pub fn search(
@@ -47,86 +659,3397 @@ pub fn search(
     items: Vec<String>,
     options: Option<SearchOptions>,
 ) -> Result<Vec<SearchResult>> {
-    let opts = options.unwrap_or_default();
+    Ok(search_with_count(query, items, options)?.results)
+}
 
-    let normalized_query = if opts.normalize.unwrap_or(true) {
-        normalize_string(&query, opts.ignore_case.unwrap_or(true))
-    } else if opts.ignore_case.unwrap_or(true) {
-        query.to_lowercase()
-    } else {
-        query
+/// Same as [`search`], but also reports how many items passed `threshold`
+/// before `limit` was applied, which is what a paginating UI needs.
+#[napi]
+pub fn search_with_count(
+    query: String,
+    mut items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<SearchResults> {
+    let opts = options.unwrap_or_default();
+    let Some(normalizer) = opts.normalizer else {
+        return search_scored(&Scorer::new(&opts)?, &query, items, &opts);
     };
 
-    let mut results: Vec<SearchResult> = items
-        .iter()
-        .enumerate()
-        .filter_map(|(index, item)| {
-            let normalized_item = if opts.normalize.unwrap_or(true) {
-                normalize_string(item, opts.ignore_case.unwrap_or(true))
-            } else if opts.ignore_case.unwrap_or(true) {
-                item.to_lowercase()
-            } else {
-                item.clone()
-            };
-
-            let score =
-                calculate_similarity(&normalized_query, &normalized_item);
-
-            if score >= opts.threshold.unwrap_or(0.0) {
-                Some(SearchResult {
-                    item: item.clone(),
-                    score,
-                    index: index as u32,
-                })
-            } else {
-                None
-            }
+    let scorer = Scorer::prenormalized(&opts)?;
+    let mut found = search_scored(
+        &scorer,
+        &normalizer.call(&query)?,
+        normalizer.call_all(&items)?,
+        &opts,
+    )?;
+    restore_items(&mut found.results, &mut items);
+    Ok(found)
+}
+
+/// The body of [`search_with_count`], once `scorer` is set up.
+fn search_scored(
+    scorer: &Scorer,
+    query: &str,
+    mut items: Vec<String>,
+    opts: &SearchOptions,
+) -> Result<SearchResults> {
+    scorer.check_query(query)?;
+    check_item_arrays(opts, items.len())?;
+    check_limit(opts)?;
+
+    let normalized_query = scorer.prepare(query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(SearchResults {
+            results: Vec::new(),
+            total: 0,
+            item_count: items.len() as u32,
+            approximate: false,
+        });
+    }
+
+    let (scored, total) = score_ranked(scorer, &normalized_query, &items, opts);
+    let item_count = items.len() as u32;
+    // Scoring and ranking only passed indices around; the page's items are
+    // moved out of `items` now instead of being cloned.
+    let results = scored
+        .into_iter()
+        .map(|s| {
+            let item = std::mem::take(&mut items[s.index as usize]);
+            search_result(scorer, opts, &normalized_query, s, item)
         })
         .collect();
 
-    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+    Ok(SearchResults {
+        results,
+        total,
+        item_count,
+        approximate: is_sampled(opts, items.len()),
+    })
+}
 
-    if let Some(limit) = opts.limit {
-        results.truncate(limit as usize);
+/// Puts the `items` a custom normalizer was run over back into `results`,
+/// which were found and filled in with their normalized forms.
+fn restore_items(results: &mut [SearchResult], items: &mut [String]) {
+    for result in results {
+        result.item = std::mem::take(&mut items[result.index as usize]);
     }
+}
 
-    Ok(results)
+/// Same as [`search`], but scores on a worker thread and passes each result
+/// to `on_result` as soon as it is found, so a UI can show early matches
+/// while the rest of the corpus is searched. Resolves with the number of
+/// results delivered, after the last callback has run.
+///
+/// Results arrive in corpus order rather than by score, and `offset` and
+/// `limit` count matches in that order. `streamOrder: "score"` delivers the
+/// same page as `search` instead, best first, but only once every item has
+/// been scored. Scoring pauses while 64 results are waiting for the
+/// callback, so a slow callback doesn't pile up the whole result set.
+#[napi(ts_return_type = "Promise<number>")]
+pub fn search_stream(
+    query: String,
+    items: Vec<String>,
+    #[napi(ts_arg_type = "(result: SearchResult) => void")]
+    on_result: JsFunction,
+    options: Option<SearchOptions>,
+) -> Result<AsyncTask<SearchStream>> {
+    let on_result = on_result.create_threadsafe_function(
+        search_stream::STREAM_QUEUE_SIZE,
+        search_stream::callback_args,
+    )?;
+    let task = SearchStream::new(
+        query,
+        items,
+        options.unwrap_or_default(),
+        on_result,
+    )?;
+    Ok(AsyncTask::new(task))
+}
+
+/// Turns a scored `item` into its [`SearchResult`], with the explanation and
+/// normalized form the options ask for and its scores rounded.
+fn search_result(
+    scorer: &Scorer,
+    opts: &SearchOptions,
+    normalized_query: &str,
+    scored: Scored,
+    item: String,
+) -> SearchResult {
+    let explanation =
+        (opts.explain.unwrap_or(false) && !scorer.too_long(&item)).then(|| {
+            let normalized_item = scorer.prepare(&item);
+            let mut explanation =
+                scorer.explain(normalized_query, &normalized_item);
+            explanation
+                .boosts
+                .extend(weight_boost(opts, scored.index as usize));
+            explanation
+        });
+
+    SearchResult {
+        normalized_item: normalized_item(scorer, opts, &item),
+        token_scores: token_scores(scorer, opts, normalized_query, &item),
+        item,
+        score: round_score(opts, scored.score),
+        index: scored.index,
+        explanation,
+        query_index: None,
+        raw_score: opts
+            .include_raw_score
+            .unwrap_or(false)
+            .then(|| round_score(opts, scored.raw_score)),
+        alias_index: None,
+    }
 }
 
+/// Same as [`search`], but returns only each result's index and score.
+///
+/// Skipping the item strings keeps the payload small for large result sets
+/// when the caller still has `items` and can look them up itself. `explain`
+/// and `includeRawScore` are ignored.
 #[napi]
-pub fn fuzzy(
-    str1: String,
-    str2: String,
-    normalize: Option<bool>,
-) -> Result<f64> {
-    let should_normalize = normalize.unwrap_or(true);
+pub fn search_indices(
+    query: String,
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<ScoredIndex>> {
+    let opts = options.unwrap_or_default();
+    Ok(ranked_indices(query, items, &opts)?
+        .into_iter()
+        .map(|s| ScoredIndex {
+            index: s.index,
+            score: round_score(&opts, s.score),
+        })
+        .collect())
+}
 
-    let s1 = if should_normalize {
-        normalize_string(&str1, true)
+/// Same as [`search_indices`], but returns the indices and scores as two
+/// parallel typed arrays, see [`SearchArrays`].
+///
+/// Marshaling an array of objects costs a JS object and two properties per
+/// result, which dominates the call for large result sets; a typed array
+/// is copied into JS in one go. Zip the arrays with `items` to get the
+/// matches.
+#[napi]
+pub fn search_arrays(
+    query: String,
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<SearchArrays> {
+    let opts = options.unwrap_or_default();
+    let (indices, scores): (Vec<u32>, Vec<f64>) =
+        ranked_indices(query, items, &opts)?
+            .into_iter()
+            .map(|s| (s.index, round_score(&opts, s.score)))
+            .unzip();
+    Ok(SearchArrays {
+        indices: indices.into(),
+        scores: scores.into(),
+    })
+}
+
+/// The ranked page of [`search_indices`] and [`search_arrays`].
+fn ranked_indices(
+    query: String,
+    items: Vec<String>,
+    opts: &SearchOptions,
+) -> Result<Vec<Scored>> {
+    let (scorer, query, items) = match opts.normalizer {
+        Some(normalizer) => (
+            Scorer::prenormalized(opts)?,
+            normalizer.call(&query)?,
+            normalizer.call_all(&items)?,
+        ),
+        None => (Scorer::new(opts)?, query, items),
+    };
+    scorer.check_query(&query)?;
+    check_item_arrays(opts, items.len())?;
+    check_limit(opts)?;
+
+    let normalized_query = scorer.prepare(&query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(Vec::new());
+    }
+
+    let (scored, _) = score_ranked(&scorer, &normalized_query, &items, opts);
+    Ok(scored)
+}
+
+/// Same as [`search`] for Rust callers, borrowing `items` instead of taking
+/// ownership of them.
+///
+/// Each result is the matched item itself, its score and its index, so
+/// nothing is allocated per result beyond the returned `Vec`, where
+/// [`search`] clones every matched string into a [`SearchResult`]. Options
+/// that only add fields to a `SearchResult`, such as `explain`, are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{search_ref, SearchOptions};
+///
+/// let items = ["apple", "apply", "banana"];
+/// let options = SearchOptions {
+///     threshold: Some(0.5),
+///     ..Default::default()
+/// };
+/// let results = search_ref("aple", &items, &options).unwrap();
+/// assert_eq!(results[0], ("apple", 0.8, 0));
+/// assert_eq!(results.len(), 2);
+/// ```
+pub fn search_ref<'a>(
+    query: &str,
+    items: &'a [&'a str],
+    options: &SearchOptions,
+) -> Result<Vec<(&'a str, f64, usize)>> {
+    let scorer = Scorer::new(options)?;
+    scorer.check_query(query)?;
+    check_item_arrays(options, items.len())?;
+    check_limit(options)?;
+
+    let normalized_query = scorer.prepare(query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(Vec::new());
+    }
+
+    let (scored, _) = score_ranked(&scorer, &normalized_query, items, options);
+    Ok(scored
+        .into_iter()
+        .map(|s| {
+            let index = s.index as usize;
+            (items[index], round_score(options, s.score), index)
+        })
+        .collect())
+}
+
+/// An item's position and scores before it is turned into a result.
+struct Scored {
+    index: u32,
+    score: f64,
+    raw_score: f64,
+}
+
+/// A [`Scored`] item of a search that tries several candidates per item,
+/// along with the one that scored best: the query in [`search_any`], the
+/// alias in [`search_aliases`].
+struct ScoredPick {
+    scored: Scored,
+    pick: Option<u32>,
+}
+
+/// Scores the items `sampleLimit` picks against a prepared query, keeping
+/// those whose weighted score passes `threshold`, in corpus order.
+fn score_items<S: AsRef<str> + Sync>(
+    scorer: &Scorer,
+    normalized_query: &str,
+    items: &[S],
+    opts: &SearchOptions,
+) -> Vec<Scored> {
+    filter_map_items(items, opts, |index, item| {
+        score_item(scorer, normalized_query, index, item.as_ref(), opts)
+    })
+}
+
+/// Scores a single item, or returns `None` if it doesn't pass `threshold`.
+fn score_item(
+    scorer: &Scorer,
+    normalized_query: &str,
+    index: usize,
+    item: &str,
+    opts: &SearchOptions,
+) -> Option<Scored> {
+    let raw_score = scorer.score_raw(normalized_query, item)?;
+    let score = raw_score * item_weight(opts, index);
+
+    (score >= opts.threshold.unwrap_or(0.0)).then_some(Scored {
+        index: index as u32,
+        score,
+        raw_score,
+    })
+}
+
+/// Runs `f` over the items `sampleLimit` picks, with their indices, keeping
+/// the `Some` results in corpus order. Goes parallel once there are at least
+/// `parallel_threshold` items.
+fn filter_map_items<I, T, F>(items: &[I], opts: &SearchOptions, f: F) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(usize, &I) -> Option<T> + Sync,
+{
+    let sample = Sample::new(opts, items.len());
+    filter_map_all(items, opts, |index, item| {
+        sample
+            .contains(index)
+            .then(|| f(index, item))
+            .flatten()
+    })
+}
+
+/// Same as [`filter_map_items`], but runs `f` over every item whatever
+/// `sampleLimit` is.
+fn filter_map_all<I, T, F>(items: &[I], opts: &SearchOptions, f: F) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(usize, &I) -> Option<T> + Sync,
+{
+    if goes_parallel(opts, items.len()) {
+        items
+            .par_iter()
+            .enumerate()
+            .filter_map(|(index, item)| f(index, item))
+            .collect()
     } else {
-        str1
+        items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| f(index, item))
+            .collect()
+    }
+}
+
+/// Whether a search over `item_count` items should be spread across threads.
+fn goes_parallel(opts: &SearchOptions, item_count: usize) -> bool {
+    let parallel_threshold = opts
+        .parallel_threshold
+        .unwrap_or(DEFAULT_PARALLEL_THRESHOLD);
+    item_count >= parallel_threshold as usize
+}
+
+/// Scores and ranks `items` against a prepared query, returning the page
+/// selected by `offset` and `limit` along with how many items passed
+/// `threshold`. Only the items `sampleLimit` picks are scored.
+///
+/// When `limit` cuts the results short, only the best `offset + limit` items
+/// are kept while scoring instead of collecting and sorting every match.
+fn score_ranked<'a, S>(
+    scorer: &Scorer,
+    normalized_query: &str,
+    items: &'a [S],
+    opts: &'a SearchOptions,
+) -> (Vec<Scored>, u32)
+where
+    S: AsRef<str> + Sync,
+    &'a [S]: Into<Corpus<'a>>,
+{
+    let offset = opts.offset.unwrap_or(0) as usize;
+    // Ties past the `k`th result can't be found in a bounded heap.
+    let k = result_limit(opts)
+        .filter(|_| !opts.include_ties.unwrap_or(false))
+        .map(|limit| offset.saturating_add(limit))
+        .filter(|&k| k < items.len());
+    let ranking = Ranking::new(opts, Some(items.into()));
+    let Some(k) = k else {
+        let scored = score_items(scorer, normalized_query, items, opts);
+        let total = scored.len() as u32;
+        return (rank(scored, opts, &ranking, |s| (s.score, s.index)), total);
     };
 
-    let s2 = if should_normalize {
-        normalize_string(&str2, true)
+    let sample = Sample::new(opts, items.len());
+    let score = |(index, item): (usize, &S)| {
+        if !sample.contains(index) {
+            return None;
+        }
+        score_item(scorer, normalized_query, index, item.as_ref(), opts)
+    };
+    let top = if goes_parallel(opts, items.len()) {
+        items
+            .par_iter()
+            .enumerate()
+            .filter_map(score)
+            .fold(
+                || TopK::new(k, ranking),
+                |mut top, scored| {
+                    top.push(scored);
+                    top
+                },
+            )
+            .reduce(|| TopK::new(k, ranking), TopK::merge)
     } else {
-        str2
+        let mut top = TopK::new(k, ranking);
+        items
+            .iter()
+            .enumerate()
+            .filter_map(score)
+            .for_each(|scored| top.push(scored));
+        top
+    };
+
+    let total = top.seen() as u32;
+    let mut scored = top.into_sorted_vec();
+    scored.drain(..offset.min(scored.len()));
+    (scored, total)
+}
+
+/// Returns the single best match for `query`, or `None` if no item passes
+/// `threshold`, e.g. for "did you mean" suggestions.
+///
+/// This keeps a running maximum instead of sorting every match, so it is
+/// cheaper than `search` with `limit: 1`. Ties go to the earliest item, as
+/// in [`search`]. `limit` and `offset` are ignored.
+#[napi]
+pub fn best_match(
+    query: String,
+    mut items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Option<SearchResult>> {
+    let opts = options.unwrap_or_default();
+    let Some(normalizer) = opts.normalizer else {
+        return best_match_scored(&Scorer::new(&opts)?, &query, items, &opts);
+    };
+
+    let scorer = Scorer::prenormalized(&opts)?;
+    let mut best = best_match_scored(
+        &scorer,
+        &normalizer.call(&query)?,
+        normalizer.call_all(&items)?,
+        &opts,
+    )?;
+    restore_items(best.as_mut_slice(), &mut items);
+    Ok(best)
+}
+
+/// The body of [`best_match`], once `scorer` is set up.
+fn best_match_scored(
+    scorer: &Scorer,
+    query: &str,
+    mut items: Vec<String>,
+    opts: &SearchOptions,
+) -> Result<Option<SearchResult>> {
+    scorer.check_query(query)?;
+    check_item_arrays(opts, items.len())?;
+
+    let normalized_query = scorer.prepare(query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(None);
+    }
+
+    let sample = Sample::new(opts, items.len());
+    let score = |(index, item): (usize, &String)| {
+        if !sample.contains(index) {
+            return None;
+        }
+        score_item(scorer, &normalized_query, index, item, opts)
     };
+    let ranking = Ranking::new(opts, Some(items.as_slice().into()));
+    let better = |a: Scored, b: Scored| match ranking
+        .compare((b.score, b.index), (a.score, a.index))
+    {
+        Ordering::Less => b,
+        _ => a,
+    };
+    let best = if goes_parallel(opts, items.len()) {
+        items
+            .par_iter()
+            .enumerate()
+            .filter_map(score)
+            .reduce_with(better)
+    } else {
+        items
+            .iter()
+            .enumerate()
+            .filter_map(score)
+            .reduce(better)
+    };
+
+    Ok(best.map(|best| {
+        let item = &mut items[best.index as usize];
+        let explanation = (opts.explain.unwrap_or(false)
+            && !scorer.too_long(item))
+        .then(|| {
+            let mut explanation =
+                scorer.explain(&normalized_query, &scorer.prepare(item));
+            explanation
+                .boosts
+                .extend(weight_boost(opts, best.index as usize));
+            explanation
+        });
+
+        SearchResult {
+            normalized_item: normalized_item(scorer, opts, item),
+            token_scores: token_scores(scorer, opts, &normalized_query, item),
+            item: std::mem::take(item),
+            score: round_score(opts, best.score),
+            index: best.index,
+            explanation,
+            query_index: None,
+            raw_score: opts
+                .include_raw_score
+                .unwrap_or(false)
+                .then(|| round_score(opts, best.raw_score)),
+            alias_index: None,
+        }
+    }))
+}
+
+/// Same as [`best_match`], but also returns the edits that turn the query
+/// into the match, e.g. for a spell-checker showing what it would change.
+///
+/// The edits are a shortest Levenshtein script over `char`s between the
+/// normalized query and the normalized item, whatever `algorithm` is set;
+/// see [`levenshtein_edits`]. Tracing them back needs the full DP matrix
+/// over what differs between the two once their common prefix and suffix
+/// are trimmed: about `4 × len(query) × len(item)` bytes in `char`s for the
+/// one match, so two unrelated strings of the default `maxInputLength`
+/// (10000) take about 400 MB. Lower `maxInputLength` to bound it. A match
+/// too long to compare has no edits.
+#[napi]
+pub fn best_match_with_edits(
+    query: String,
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Option<MatchWithEdits>> {
+    let opts = options.clone().unwrap_or_default();
+    let scorer = Scorer::new(&opts)?;
+    let Some(result) = best_match(query.clone(), items, options)? else {
+        return Ok(None);
+    };
+
+    let edits = if scorer.too_long(&result.item) {
+        Vec::new()
+    } else {
+        levenshtein_edits(
+            &scorer.prepare(&query),
+            &scorer.prepare(&result.item),
+        )
+    };
+    Ok(Some(MatchWithEdits { result, edits }))
+}
+
+/// Same as [`search`], but splits the results into score buckets delimited
+/// by `edges`, from the highest bucket to the lowest, for faceted UIs.
+///
+/// Each bucket includes its lower edge and excludes its upper one; see
+/// [`ScoreGroup`]. `limit` and `offset` apply before grouping, so pass
+/// `limit: None` to group every match.
+#[napi]
+pub fn search_grouped(
+    query: String,
+    items: Vec<String>,
+    edges: Vec<f64>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<ScoreGroup>> {
+    group_by_score(search(query, items, options)?, &edges)
+}
+
+/// Same as [`search`], but groups the results by the first letter of each
+/// normalized item, for dictionary-style UIs; see [`InitialGroup`].
+///
+/// With the default options `"Apple"` and `"éclair"` fall under `"a"` and
+/// `"e"`; without `ignoreCase` upper and lower case letters get separate
+/// groups. Items starting with a digit, a symbol or nothing at all share the
+/// [`OTHER_INITIAL`] group, which comes first; the other groups follow in
+/// code point order. Each group keeps the ranking order of [`search`], and
+/// `limit` and `offset` apply before grouping.
+#[napi]
+pub fn search_grouped_by_initial(
+    query: String,
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<InitialGroup>> {
+    let scorer = Scorer::new(&options.clone().unwrap_or_default())?;
+    let results = search(query, items, options)?;
+    Ok(group_by_initial(results, |result| {
+        scorer
+            .prepare(&result.item)
+            .chars()
+            .next()
+    }))
+}
+
+/// Searches `items` against several queries, keeping each item's best score.
+///
+/// This is OR-matching: an item ranks by whichever query it matches best,
+/// and that query's position in `queries` is recorded in
+/// [`SearchResult::query_index`]. When two queries tie, the earlier one wins.
+/// `threshold` and `limit` apply to the best scores. Queries shorter than
+/// `min_query_length` are ignored.
+#[napi]
+pub fn search_any(
+    queries: Vec<String>,
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SearchResult>> {
+    search_queries(queries, items, Aggregator::Max, options)
+}
+
+/// Searches `items` against several queries, combining each item's scores
+/// with `aggregator`, e.g. to find items similar to all of a set of examples.
+///
+/// `"max"` ranks items by the query they match best, like [`search_any`];
+/// `"min"` by the one they match worst, so only items close to every query
+/// rank high; `"mean"` by their average score. For `"max"` and `"min"`,
+/// [`SearchResult::query_index`] records the query that decided the score,
+/// the earlier one on ties, and `explain` describes that comparison. An
+/// average has no such query, so `"mean"` sets neither. `threshold` and
+/// `limit` apply to the combined scores. Queries shorter than
+/// `min_query_length` are ignored.
+#[napi]
+pub fn search_multi(
+    queries: Vec<String>,
+    items: Vec<String>,
+    #[napi(ts_arg_type = "\"max\" | \"mean\" | \"min\"")] aggregator: String,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SearchResult>> {
+    let aggregator = Aggregator::parse(&aggregator)?;
+    search_queries(queries, items, aggregator, options)
+}
+
+/// How [`search_multi`] combines an item's scores against each query.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Aggregator {
+    /// The best score.
+    Max,
+    /// The average score.
+    Mean,
+    /// The worst score.
+    Min,
+}
+
+impl Aggregator {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "max" => Ok(Self::Max),
+            "mean" => Ok(Self::Mean),
+            "min" => Ok(Self::Min),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown aggregator {other:?}"),
+            )),
+        }
+    }
+
+    /// Combines `(query index, score)` pairs into one score, along with the
+    /// query that decided it, the earliest on ties. `None` without scores.
+    fn combine(
+        self,
+        scores: impl Iterator<Item = (usize, f64)>,
+    ) -> Option<(Option<usize>, f64)> {
+        let pick = |keeps: fn(f64, f64) -> bool| {
+            move |best: Option<(Option<usize>, f64)>, (i, score)| match best {
+                Some((_, best_score)) if keeps(best_score, score) => best,
+                _ => Some((Some(i), score)),
+            }
+        };
+
+        match self {
+            Self::Max => scores.fold(None, pick(|best, score| best >= score)),
+            Self::Min => scores.fold(None, pick(|best, score| best <= score)),
+            Self::Mean => {
+                let (sum, count) = scores
+                    .fold((0.0, 0), |(sum, count), (_, score)| {
+                        (sum + score, count + 1)
+                    });
+                (count > 0).then(|| (None, sum / count as f64))
+            }
+        }
+    }
+}
+
+/// Shared implementation of [`search_any`] and [`search_multi`].
+fn search_queries(
+    queries: Vec<String>,
+    mut items: Vec<String>,
+    aggregator: Aggregator,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SearchResult>> {
+    let opts = options.unwrap_or_default();
+    let scorer = Scorer::new(&opts)?;
+
+    for query in &queries {
+        scorer.check_query(query)?;
+    }
+    check_item_arrays(&opts, items.len())?;
+    check_limit(&opts)?;
+    let include_raw_score = opts.include_raw_score.unwrap_or(false);
+
+    let normalized_queries: Vec<String> = queries
+        .iter()
+        .map(|query| scorer.prepare(query))
+        .collect();
+
+    let scored = filter_map_items(&items, &opts, |index, item| {
+        let normalized_item = if scorer.too_long(item) {
+            None
+        } else {
+            Some(scorer.prepare(item))
+        };
+
+        let scores = normalized_queries
+            .iter()
+            .enumerate()
+            .filter(|(_, query)| !scorer.too_short(query))
+            .filter_map(|(i, query)| {
+                let score = match &normalized_item {
+                    Some(item) => scorer.score_within(query, item),
+                    None => scorer.unmeasured_score(),
+                }?;
+                Some((i, score))
+            });
+        let (query_index, raw_score) = aggregator.combine(scores)?;
+        let score = raw_score * item_weight(&opts, index);
+
+        (score >= opts.threshold.unwrap_or(0.0)).then_some(ScoredPick {
+            scored: Scored {
+                index: index as u32,
+                score,
+                raw_score,
+            },
+            pick: query_index.map(|i| i as u32),
+        })
+    });
+
+    let ranking = Ranking::new(&opts, Some(items.as_slice().into()));
+    let ranked = rank(scored, &opts, &ranking, |p| {
+        (p.scored.score, p.scored.index)
+    });
+    let mut results: Vec<SearchResult> = ranked
+        .into_iter()
+        .map(|ScoredPick { scored: s, pick }| {
+            let item = &mut items[s.index as usize];
+            SearchResult {
+                normalized_item: normalized_item(&scorer, &opts, item),
+                item: std::mem::take(item),
+                score: s.score,
+                index: s.index,
+                explanation: None,
+                query_index: pick,
+                raw_score: include_raw_score.then_some(s.raw_score),
+                alias_index: None,
+                token_scores: None,
+            }
+        })
+        .collect();
+
+    if opts.explain.unwrap_or(false) {
+        for result in results
+            .iter_mut()
+            .filter(|r| !scorer.too_long(&r.item))
+        {
+            let Some(query_index) = result.query_index else {
+                continue;
+            };
+            let normalized_item = scorer.prepare(&result.item);
+            let query_index = query_index as usize;
+            let mut explanation = scorer
+                .explain(&normalized_queries[query_index], &normalized_item);
+            explanation
+                .boosts
+                .extend(weight_boost(&opts, result.index as usize));
+            result.explanation = Some(explanation);
+        }
+    }
+    round_results(&mut results, &opts);
 
-    Ok(calculate_similarity(&s1, &s2))
+    Ok(results)
 }
 
-fn calculate_similarity(str1: &str, str2: &str) -> f64 {
-    if str1 == str2 {
-        return 1.0;
+/// Searches groups of aliases, such as `["United States", "USA"]`, scoring
+/// each group by its best-matching alias so records don't need one item per
+/// alias.
+///
+/// Each result's `item` is the group's first, canonical alias and `index` is
+/// the group's position; [`SearchResult::alias_index`] records which alias
+/// matched, the earliest one on ties. `weights` and `sortKeys` have one
+/// entry per group. Empty groups score `0.0`, and `naturalOrder` is ignored.
+#[napi]
+pub fn search_aliases(
+    query: String,
+    groups: Vec<Vec<String>>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SearchResult>> {
+    let opts = options.unwrap_or_default();
+    let scorer = Scorer::new(&opts)?;
+    scorer.check_query(&query)?;
+    check_item_arrays(&opts, groups.len())?;
+    check_limit(&opts)?;
+
+    let normalized_query = scorer.prepare(&query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(Vec::new());
+    }
+
+    let include_raw_score = opts.include_raw_score.unwrap_or(false);
+    let threshold = opts.threshold.unwrap_or(0.0);
+    let scored = filter_map_items(&groups, &opts, |index, aliases| {
+        let (alias_index, raw_score) = aliases
+            .iter()
+            .enumerate()
+            .filter_map(|(i, alias)| {
+                Some((i, scorer.score_raw(&normalized_query, alias)?))
+            })
+            .fold(None, |best: Option<(usize, f64)>, (i, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((i, score)),
+            })
+            .unwrap_or((0, 0.0));
+        let score = raw_score * item_weight(&opts, index);
+
+        (score >= threshold).then_some(ScoredPick {
+            scored: Scored {
+                index: index as u32,
+                score,
+                raw_score,
+            },
+            pick: (alias_index < aliases.len()).then_some(alias_index as u32),
+        })
+    });
+
+    let ranking = Ranking::new(&opts, None);
+    let ranked = rank(scored, &opts, &ranking, |p| {
+        (p.scored.score, p.scored.index)
+    });
+    let mut results: Vec<SearchResult> = ranked
+        .into_iter()
+        .map(|ScoredPick { scored: s, pick }| {
+            let aliases = &groups[s.index as usize];
+            let alias = pick.map(|pick| aliases[pick as usize].as_str());
+            SearchResult {
+                item: aliases
+                    .first()
+                    .cloned()
+                    .unwrap_or_default(),
+                score: s.score,
+                index: s.index,
+                explanation: None,
+                query_index: None,
+                raw_score: include_raw_score.then_some(s.raw_score),
+                normalized_item: alias
+                    .and_then(|alias| normalized_item(&scorer, &opts, alias)),
+                alias_index: pick,
+                token_scores: None,
+            }
+        })
+        .collect();
+
+    if opts.explain.unwrap_or(false) {
+        for result in &mut results {
+            let Some(alias_index) = result.alias_index else {
+                continue;
+            };
+            let alias = &groups[result.index as usize][alias_index as usize];
+            if scorer.too_long(alias) {
+                continue;
+            }
+            let mut explanation =
+                scorer.explain(&normalized_query, &scorer.prepare(alias));
+            explanation
+                .boosts
+                .extend(weight_boost(&opts, result.index as usize));
+            result.explanation = Some(explanation);
+        }
     }
+    round_results(&mut results, &opts);
+
+    Ok(results)
+}
+
+/// Searches `records` by several of their string properties at once, e.g. a
+/// product's `name` and `description`.
+///
+/// Each key's field is scored like an item in [`search`] and multiplied by
+/// the key's weight; `combineFields` then picks the best field or sums them.
+/// `weights`, `threshold`, `offset` and `limit` apply to the combined score,
+/// while `explain` and `includeRawScore` are ignored.
+#[napi]
+pub fn search_by(
+    query: String,
+    records: Vec<Object>,
+    keys: Vec<SearchKey>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SearchByResult>> {
+    let records = records
+        .iter()
+        .map(|record| {
+            keys.iter()
+                .map(|key| field_text(record, &key.key))
+                .collect()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    search_by::search_fields(
+        &query,
+        &records,
+        &keys,
+        &options.unwrap_or_default(),
+    )
+}
 
-    if str1.is_empty() || str2.is_empty() {
-        return 0.0;
+/// The value of `record[key]` if it is a string.
+fn field_text(record: &Object, key: &str) -> Result<Option<String>> {
+    let value = record.get_named_property_unchecked::<napi::JsUnknown>(key)?;
+    if value.get_type()? != ValueType::String {
+        return Ok(None);
     }
+    Ok(Some(
+        value
+            .coerce_to_string()?
+            .into_utf8()?
+            .into_owned()?,
+    ))
+}
+
+/// Suggests a `threshold` for `query` by scoring every item and cutting at
+/// the largest gap between consecutive scores, e.g. for a UI that tunes the
+/// cut-off itself instead of asking the user.
+///
+/// This is a heuristic: it works when the scores split into a cluster of
+/// good matches and a cluster of poor ones, and can pick an arbitrary cut
+/// when they are spread evenly. The suggestion is the midpoint of the gap,
+/// so passing it as `threshold` keeps the upper cluster. Returns `0.0` when
+/// there are fewer than two distinct scores. `threshold`, `limit` and
+/// `offset` are ignored; `weights` apply.
+#[napi]
+pub fn suggest_threshold(
+    query: String,
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<f64> {
+    let opts = options.unwrap_or_default();
+    let scorer = Scorer::new(&opts)?;
+    scorer.check_query(&query)?;
+    check_item_arrays(&opts, items.len())?;
+
+    let normalized_query = scorer.prepare(&query);
+    let mut scores = filter_map_items(&items, &opts, |index, item| {
+        Some(
+            scorer.score_raw(&normalized_query, item)?
+                * item_weight(&opts, index),
+        )
+    });
+    scores.sort_by(|a, b| b.partial_cmp(a).unwrap());
 
-    let distance = levenshtein_distance(str1, str2);
-    let max_len = str1.len().max(str2.len()) as f64;
+    let widest = scores
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|(high, low)| high > low)
+        .max_by(|a, b| {
+            (a.0 - a.1)
+                .partial_cmp(&(b.0 - b.1))
+                .unwrap()
+        });
+    Ok(widest.map_or(0.0, |(high, low)| (high + low) / 2.0))
+}
 
-    1.0 - (distance as f64 / max_len)
+/// Computes the similarity of every pair of `items`, e.g. for clustering or
+/// deduplication. `matrix[i][j]` is the score of `items[j]` against
+/// `items[i]`; the matrix is symmetric with `1.0` on the diagonal.
+///
+/// This takes `n * (n - 1) / 2` comparisons and returns `n * n` numbers, so
+/// it is only practical for a few thousand items. Each pair is scored once,
+/// with the earlier item as the query, and rows are spread across threads
+/// past `parallel_threshold` items. Scores below `threshold` are set to
+/// `0.0`; `limit`, `offset`, `weights` and `sampleLimit` are ignored.
+#[napi]
+pub fn similarity_matrix(
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<Vec<f64>>> {
+    let opts = options.unwrap_or_default();
+    let threshold = opts.threshold.unwrap_or(0.0);
+    let upper = upper_triangle(&items, &opts, |_, _, score| {
+        Some(if score < threshold {
+            0.0
+        } else {
+            round_score(&opts, score)
+        })
+    })?;
+
+    let n = items.len();
+    let mut matrix = vec![vec![1.0; n]; n];
+    for (i, row) in upper.into_iter().enumerate() {
+        for (offset, score) in row.into_iter().enumerate() {
+            let j = i + 1 + offset;
+            matrix[i][j] = score;
+            matrix[j][i] = score;
+        }
+    }
+    Ok(matrix)
+}
+
+/// Same as [`similarity_matrix`], but only returns the pairs scoring at
+/// least `threshold`, ordered by `i` and then `j`, for clustering large sets
+/// where only strong links matter.
+///
+/// It still takes `n * (n - 1) / 2` comparisons, spread across threads row by
+/// row past `parallel_threshold` items, but memory grows with the number
+/// of matching pairs instead of `n * n`; with the default `threshold` of
+/// `0.0` every pair matches, so set one. Each pair is listed once with
+/// `i < j`, and the diagonal is left out. `limit`, `offset`, `weights` and
+/// `sampleLimit` are ignored.
+#[napi]
+pub fn similarity_pairs(
+    items: Vec<String>,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SimilarityPair>> {
+    let opts = options.unwrap_or_default();
+    let threshold = opts.threshold.unwrap_or(0.0);
+    let rows = upper_triangle(&items, &opts, |i, j, score| {
+        (score >= threshold).then(|| SimilarityPair {
+            i: i as u32,
+            j: j as u32,
+            score: round_score(&opts, score),
+        })
+    })?;
+    Ok(rows.into_iter().flatten().collect())
+}
+
+/// Scores every pair `i < j` of `items`, with the earlier item as the query,
+/// and keeps what `keep` makes of each score, one row per `i`. Rows are
+/// scored in parallel past `parallel_threshold` items.
+fn upper_triangle<T, F>(
+    items: &[String],
+    opts: &SearchOptions,
+    keep: F,
+) -> Result<Vec<Vec<T>>>
+where
+    T: Send,
+    F: Fn(usize, usize, f64) -> Option<T> + Sync,
+{
+    let scorer = Scorer::new(opts)?;
+    let prepared: Vec<Option<String>> = items
+        .iter()
+        .map(|item| (!scorer.too_long(item)).then(|| scorer.prepare(item)))
+        .collect();
+
+    Ok(filter_map_all(&prepared, opts, |i, query| {
+        let row = prepared[i + 1..]
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, item)| {
+                let score = match (query, item) {
+                    (Some(query), Some(item)) => scorer.score(query, item),
+                    _ => 0.0,
+                };
+                keep(i, i + 1 + offset, score)
+            })
+            .collect::<Vec<_>>();
+        Some(row)
+    }))
+}
+
+/// Empties the normalization cache used by `cacheNormalization` and bounds
+/// it to `size` entries; `0` disables it. The default size is
+/// [`DEFAULT_NORMALIZATION_CACHE_SIZE`].
+#[napi]
+pub fn set_normalization_cache_size(size: u32) {
+    normalization_cache::resize(size as usize);
+}
+
+/// Scores two strings against each other.
+///
+/// The third argument is either the legacy `normalize` flag, which toggles
+/// normalization with case folding, or a [`SearchOptions`] object. With
+/// options, `str2` is scored exactly as [`search`] would score it as an item
+/// for the query `str1`; options that only affect result lists, such as
+/// `limit` or `weights`, are ignored.
+#[napi]
+pub fn fuzzy(
+    str1: String,
+    str2: String,
+    options: Option<Either<bool, SearchOptions>>,
+) -> Result<f64> {
+    let should_normalize = match options {
+        Some(Either::A(normalize)) => normalize,
+        Some(Either::B(opts)) => return fuzzy_with(&str1, &str2, &opts),
+        None => true,
+    };
+
+    for input in [&str1, &str2] {
+        check_length(input, DEFAULT_MAX_INPUT_LENGTH as usize)?;
+    }
+
+    let s1 = if should_normalize {
+        normalize_string(&str1, true)
+    } else {
+        str1
+    };
+
+    let s2 = if should_normalize {
+        normalize_string(&str2, true)
+    } else {
+        str2
+    };
+
+    Ok(similarity(&s1, &s2))
+}
+
+/// [`fuzzy`] with an options object.
+fn fuzzy_with(str1: &str, str2: &str, opts: &SearchOptions) -> Result<f64> {
+    let scorer = Scorer::new(opts)?;
+    for input in [str1, str2] {
+        scorer.check_query(input)?;
+    }
+
+    let score = scorer.score(&scorer.prepare(str1), &scorer.prepare(str2));
+    Ok(round_score(opts, score))
+}
+
+/// Scores `item` against `query` exactly as [`search`] scores it, so a
+/// result's `score` can be reproduced for validation or client-side
+/// re-ranking.
+///
+/// Unlike [`fuzzy`], this runs `normalizer` and gives items that `search`
+/// would skip the score they would have there: `0.0` for one beyond
+/// `maxDistance` or under `minTrigramOverlap`, or for a query shorter than
+/// `minQueryLength`, and the usual score of an item too long to compare.
+/// `threshold` isn't applied, and `weights`, which belong to positions in
+/// `items`, are ignored.
+#[napi]
+pub fn score(
+    query: String,
+    item: String,
+    options: Option<SearchOptions>,
+) -> Result<f64> {
+    let opts = options.unwrap_or_default();
+    let Some(normalizer) = opts.normalizer else {
+        return score_scored(&Scorer::new(&opts)?, &query, &item, &opts);
+    };
+
+    score_scored(
+        &Scorer::prenormalized(&opts)?,
+        &normalizer.call(&query)?,
+        &normalizer.call(&item)?,
+        &opts,
+    )
+}
+
+/// The body of [`score`], once `scorer` is set up.
+fn score_scored(
+    scorer: &Scorer,
+    query: &str,
+    item: &str,
+    opts: &SearchOptions,
+) -> Result<f64> {
+    scorer.check_query(query)?;
+
+    let normalized_query = scorer.prepare(query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(0.0);
+    }
+    let score = scorer
+        .score_raw(&normalized_query, item)
+        .unwrap_or(0.0);
+    Ok(round_score(opts, score))
+}
+
+/// Same as [`fuzzy`] with options, but also returns the edit distance the
+/// similarity was computed from.
+///
+/// Identical strings are reported as distance `0` and similarity `1.0`
+/// (before boosts) without running the distance computation; other pairs
+/// report the distance the chosen algorithm computed, the one `explain`
+/// shows.
+#[napi]
+pub fn score_detailed(
+    a: String,
+    b: String,
+    options: Option<SearchOptions>,
+) -> Result<ScoreDetails> {
+    let opts = options.unwrap_or_default();
+    let scorer = Scorer::new(&opts)?;
+    for input in [&a, &b] {
+        scorer.check_query(input)?;
+    }
+
+    let (a, b) = (scorer.prepare(&a), scorer.prepare(&b));
+    Ok(ScoreDetails {
+        distance: scorer.distance_between(&a, &b),
+        similarity: round_score(&opts, scorer.score(&a, &b)),
+    })
+}
+
+/// Whether [`fuzzy`] with these options scores `str1` and `str2` at least
+/// `threshold`.
+///
+/// This is cheaper than comparing the score yourself: for plain edit-distance
+/// scoring the distance computation gives up as soon as it has seen too many
+/// edits to reach `threshold`, instead of finishing the whole matrix.
+#[napi]
+pub fn is_match(
+    str1: String,
+    str2: String,
+    threshold: f64,
+    options: Option<SearchOptions>,
+) -> Result<bool> {
+    let opts = options.unwrap_or_default();
+    let scorer = Scorer::new(&opts)?;
+    for input in [&str1, &str2] {
+        scorer.check_query(input)?;
+    }
+
+    let (a, b) = (scorer.prepare(&str1), scorer.prepare(&str2));
+    if opts.precision.is_some() {
+        // Rounding can lift a score onto the threshold, so compare the
+        // rounded score itself.
+        return Ok(round_score(&opts, scorer.score(&a, &b)) >= threshold);
+    }
+    Ok(scorer.reaches(&a, &b, threshold))
+}
+
+/// JavaScript binding for [`similarity`]: the normalized Levenshtein
+/// similarity of two strings, compared as given.
+#[napi(js_name = "similarity")]
+pub fn similarity_binding(str1: String, str2: String) -> Result<f64> {
+    for input in [&str1, &str2] {
+        check_length(input, DEFAULT_MAX_INPUT_LENGTH as usize)?;
+    }
+
+    Ok(similarity(&str1, &str2))
+}
+
+/// JavaScript binding for [`normalize_ascii`]: lowercases (by default) and
+/// collapses whitespace, skipping every Unicode step.
+#[napi(js_name = "normalizeAscii")]
+pub fn normalize_ascii_binding(
+    input: String,
+    to_lowercase: Option<bool>,
+) -> Result<String> {
+    check_length(&input, DEFAULT_MAX_INPUT_LENGTH as usize)?;
+    Ok(normalize_ascii(&input, to_lowercase.unwrap_or(true)))
+}
+
+/// Similarity of two byte buffers, for keys that aren't valid UTF-8 text.
+///
+/// Scores like [`similarity`], with each byte counting as one symbol; see
+/// [`byte_similarity`]. No normalization applies, so `ignoreCase` and
+/// accent stripping have no byte equivalent here.
+#[napi]
+pub fn fuzzy_bytes(a: Buffer, b: Buffer) -> Result<f64> {
+    let max_length = DEFAULT_MAX_INPUT_LENGTH as usize;
+    if a.len().max(b.len()) > max_length {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("input exceeds the maximum length of {max_length} bytes"),
+        ));
+    }
+
+    Ok(byte_similarity(&a, &b))
+}
+
+/// Sorts scored results by `ranking`, then applies `offset` and `limit`.
+fn rank<T>(
+    mut results: Vec<T>,
+    opts: &SearchOptions,
+    ranking: &Ranking,
+    key: impl Fn(&T) -> (f64, u32),
+) -> Vec<T> {
+    results.sort_by(|a, b| ranking.compare(key(a), key(b)));
+
+    let offset = (opts.offset.unwrap_or(0) as usize).min(results.len());
+    results.drain(..offset);
+
+    if let Some(limit) = page_length(opts, &results, |r| key(r).0) {
+        results.truncate(limit);
+    }
+
+    results
+}
+
+/// Rounds `score` to `precision` decimal places, if set.
+fn round_score(opts: &SearchOptions, score: f64) -> f64 {
+    match opts.precision {
+        Some(precision) if precision <= 15 => {
+            let factor = 10f64.powi(precision as i32);
+            (score * factor).round() / factor
+        }
+        _ => score,
+    }
+}
+
+/// Applies [`round_score`] to the scores of ranked results.
+fn round_results(results: &mut [SearchResult], opts: &SearchOptions) {
+    if opts.precision.is_none() {
+        return;
+    }
+    for result in results {
+        result.score = round_score(opts, result.score);
+        result.raw_score = result
+            .raw_score
+            .map(|score| round_score(opts, score));
+    }
+}
+
+/// The normalized form of `item` for [`SearchResult::normalized_item`], if
+/// `includeNormalized` asked for it.
+fn normalized_item(
+    scorer: &Scorer,
+    opts: &SearchOptions,
+    item: &str,
+) -> Option<String> {
+    (opts.include_normalized.unwrap_or(false) && !scorer.too_long(item))
+        .then(|| scorer.prepare(item))
+}
+
+/// The per-token scores of `item` for [`SearchResult::token_scores`], if
+/// `includeTokenScores` asked for them.
+fn token_scores(
+    scorer: &Scorer,
+    opts: &SearchOptions,
+    normalized_query: &str,
+    item: &str,
+) -> Option<Vec<f64>> {
+    opts.include_token_scores
+        .unwrap_or(false)
+        .then(|| {
+            let item = (!scorer.too_long(item)).then(|| scorer.prepare(item));
+            scorer
+                .token_scores(normalized_query, item.as_deref().unwrap_or(""))
+                .into_iter()
+                .map(|score| round_score(opts, score))
+                .collect()
+        })
+}
+
+/// Rejects a negative or `NaN` `limit`.
+fn check_limit(opts: &SearchOptions) -> Result<()> {
+    match opts.limit {
+        Some(Some(limit)) if limit.is_nan() || limit < 0.0 => Err(Error::new(
+            Status::InvalidArg,
+            format!("limit must be a non-negative number or null, got {limit}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The number of results `limit` keeps, or `None` for every result: the
+/// default when it is left out, none for an explicit `null` or an infinite
+/// limit, and fractional limits rounded down. Assumes [`check_limit`]
+/// passed.
+fn result_limit(opts: &SearchOptions) -> Option<usize> {
+    match opts.limit {
+        None => Some(DEFAULT_LIMIT as usize),
+        Some(None) => None,
+        Some(Some(limit)) if limit.is_infinite() => None,
+        // Saturates for limits beyond `usize::MAX`.
+        Some(Some(limit)) => Some(limit as usize),
+    }
+}
+
+/// How many of the sorted `results` the page keeps, or `None` for all of
+/// them: [`result_limit`], stretched past the results tied with the last one
+/// under `include_ties`.
+fn page_length<T>(
+    opts: &SearchOptions,
+    results: &[T],
+    score: impl Fn(&T) -> f64,
+) -> Option<usize> {
+    let limit = result_limit(opts)?;
+    if !opts.include_ties.unwrap_or(false)
+        || limit == 0
+        || limit >= results.len()
+    {
+        return Some(limit);
+    }
+
+    let boundary = score(&results[limit - 1]);
+    let ties = results[limit..]
+        .iter()
+        .take_while(|result| score(result) == boundary)
+        .count();
+    Some(limit + ties)
+}
+
+/// Rejects `weights` and `sortKeys` that don't have exactly one entry per
+/// item, weights that aren't finite, and sort keys that can't be ordered.
+fn check_item_arrays(opts: &SearchOptions, item_count: usize) -> Result<()> {
+    for (name, values) in
+        [("weights", &opts.weights), ("sort keys", &opts.sort_keys)]
+    {
+        match values {
+            Some(values) if values.len() != item_count => {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!(
+                        "expected {item_count} {name}, one per item, got {}",
+                        values.len()
+                    ),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    if opts
+        .sort_keys
+        .iter()
+        .flatten()
+        .any(|key| key.is_nan())
+    {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "sort keys must be numbers".to_string(),
+        ));
+    }
+    if opts
+        .weights
+        .iter()
+        .flatten()
+        .any(|weight| !weight.is_finite())
+    {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "weights must be finite numbers".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The weight of the item at `index`, `1.0` when no weights were given.
+fn item_weight(opts: &SearchOptions, index: usize) -> f64 {
+    opts.weights
+        .as_ref()
+        .map_or(1.0, |weights| weights[index])
+}
+
+/// The `"weight"` boost reported by `explain`, if the item has one.
+fn weight_boost(opts: &SearchOptions, index: usize) -> Option<ScoreBoost> {
+    opts.weights
+        .as_ref()
+        .map(|weights| ScoreBoost {
+            name: "weight".to_string(),
+            value: weights[index],
+        })
+}
+
+/// Default for [`SearchOptions::limit`], in results.
+pub const DEFAULT_LIMIT: u32 = 10;
+
+/// Default for [`SearchOptions::parallel_threshold`], in items.
+pub const DEFAULT_PARALLEL_THRESHOLD: u32 = 1_000;
+
+/// Default for [`SearchOptions::max_input_length`], in characters.
+pub const DEFAULT_MAX_INPUT_LENGTH: u32 = 10_000;
+
+/// Returns `true` if `input` is longer than `max_length` characters.
+fn exceeds_length(input: &str, max_length: usize) -> bool {
+    // A string can't have more chars than bytes, so only count when needed.
+    input.len() > max_length && input.chars().count() > max_length
+}
+
+/// Rejects inputs longer than `max_length` characters before any distance
+/// matrix row is allocated for them.
+fn check_length(input: &str, max_length: usize) -> Result<()> {
+    if exceeds_length(input, max_length) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+                "input exceeds the maximum length of {max_length} characters"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Maps the user-facing `normalize`/`ignoreCase`/`turkishI`/`compatibility`
+/// flags shared by [`search`] and [`Searcher`] onto
+/// [`NormalizationOptions`].
+///
+/// With `normalize` off, only case folding is applied, plus NFKC when
+/// `compatibility` is set.
+fn normalization_options(
+    normalize: bool,
+    ignore_case: bool,
+    turkish_i: bool,
+    compatibility: bool,
+) -> NormalizationOptions {
+    let form = match (normalize, compatibility) {
+        (true, false) => Some(NormalizationForm::Nfd),
+        (true, true) => Some(NormalizationForm::Nfkd),
+        (false, true) => Some(NormalizationForm::Nfkc),
+        (false, false) => None,
+    };
+
+    NormalizationOptions {
+        form,
+        strip_diacritics: normalize,
+        case_fold: ignore_case,
+        turkish_i,
+        collapse_whitespace: normalize,
+        ..Default::default()
+    }
+}
+
+/// The normalization fields that [`SearchOptions`] and [`SearcherOptions`]
+/// share, borrowed so that [`resolve_normalization`] can serve both.
+struct NormalizationFields<'a> {
+    normalize: Option<bool>,
+    ignore_case: Option<bool>,
+    turkish_i: Option<bool>,
+    locale: Option<&'a str>,
+    compatibility: Option<bool>,
+    preserve_whitespace: Option<bool>,
+    trim: Option<bool>,
+    strip_control: Option<bool>,
+    latin_diacritics_only: Option<bool>,
+    keep_letters: Option<&'a str>,
+    alphanumeric_only: Option<bool>,
+    fold_confusables: Option<bool>,
+    expand_ligatures: Option<bool>,
+    fold_superscripts: Option<bool>,
+    fold_digits: Option<bool>,
+    ignore_chars: Option<&'a str>,
+    stem: Option<bool>,
+    stopwords: Option<&'a [String]>,
+}
+
+impl<'a> From<&'a SearchOptions> for NormalizationFields<'a> {
+    fn from(opts: &'a SearchOptions) -> Self {
+        Self {
+            normalize: opts.normalize,
+            ignore_case: opts.ignore_case,
+            turkish_i: opts.turkish_i,
+            locale: opts.locale.as_deref(),
+            compatibility: opts.compatibility,
+            preserve_whitespace: opts.preserve_whitespace,
+            trim: opts.trim,
+            strip_control: opts.strip_control,
+            latin_diacritics_only: opts.latin_diacritics_only,
+            keep_letters: opts.keep_letters.as_deref(),
+            alphanumeric_only: opts.alphanumeric_only,
+            fold_confusables: opts.fold_confusables,
+            expand_ligatures: opts.expand_ligatures,
+            fold_superscripts: opts.fold_superscripts,
+            fold_digits: opts.fold_digits,
+            ignore_chars: opts.ignore_chars.as_deref(),
+            stem: opts.stem,
+            stopwords: opts.stopwords.as_deref(),
+        }
+    }
+}
+
+impl<'a> From<&'a SearcherOptions> for NormalizationFields<'a> {
+    fn from(opts: &'a SearcherOptions) -> Self {
+        Self {
+            normalize: opts.normalize,
+            ignore_case: opts.ignore_case,
+            turkish_i: opts.turkish_i,
+            locale: opts.locale.as_deref(),
+            compatibility: opts.compatibility,
+            preserve_whitespace: opts.preserve_whitespace,
+            trim: opts.trim,
+            strip_control: opts.strip_control,
+            latin_diacritics_only: opts.latin_diacritics_only,
+            keep_letters: opts.keep_letters.as_deref(),
+            alphanumeric_only: opts.alphanumeric_only,
+            fold_confusables: opts.fold_confusables,
+            expand_ligatures: opts.expand_ligatures,
+            fold_superscripts: opts.fold_superscripts,
+            fold_digits: opts.fold_digits,
+            ignore_chars: opts.ignore_chars.as_deref(),
+            stem: opts.stem,
+            stopwords: opts.stopwords.as_deref(),
+        }
+    }
+}
+
+/// Resolves the user-facing normalization options of [`search`] and
+/// [`Searcher`] into the [`NormalizationOptions`] both compare text with, so
+/// the two can't drift apart.
+///
+/// `keep_accents` keeps diacritics, composed so each accented letter is one
+/// `char`, as `accentCost` needs. It applies before the token filters, so
+/// ignored characters and stopwords are normalized the same way.
+fn resolve_normalization(
+    fields: NormalizationFields<'_>,
+    keep_accents: bool,
+) -> Result<NormalizationOptions> {
+    let mut base = normalization_options(
+        fields.normalize.unwrap_or(true),
+        fields.ignore_case.unwrap_or(true),
+        uses_turkish_i(fields.turkish_i, fields.locale),
+        fields.compatibility.unwrap_or(false),
+    );
+    base.collapse_whitespace &= !fields
+        .preserve_whitespace
+        .unwrap_or(false);
+    base.trim = fields.trim.unwrap_or(false);
+    base.strip_control = fields.strip_control.unwrap_or(false);
+    base.latin_diacritics_only = fields
+        .latin_diacritics_only
+        .unwrap_or(false);
+    base.keep_letters = keep_letters(fields.keep_letters);
+    if keep_accents {
+        base.strip_diacritics = false;
+        base.form = base.form.map(|form| match form {
+            NormalizationForm::Nfd => NormalizationForm::Nfc,
+            NormalizationForm::Nfkd => NormalizationForm::Nfkc,
+            other => other,
+        });
+    }
+
+    with_token_filters(
+        NormalizationOptions {
+            alphanumeric_only: fields
+                .alphanumeric_only
+                .unwrap_or(false),
+            fold_confusables: fields.fold_confusables.unwrap_or(false),
+            expand_ligatures: fields.expand_ligatures.unwrap_or(false),
+            fold_superscripts: fields
+                .fold_superscripts
+                .unwrap_or(false),
+            fold_digits: fields.fold_digits.unwrap_or(false),
+            ..base
+        },
+        fields.ignore_chars,
+        fields.stem.unwrap_or(false),
+        fields.stopwords,
+    )
+}
+
+/// Whether case folding follows the Turkish rules for the letter I, either
+/// because `turkishI` is set or because `locale` is Turkish or Azeri. Only
+/// the primary language subtag counts, so `"tr-TR"` and `"az_Latn"` do too.
+fn uses_turkish_i(turkish_i: Option<bool>, locale: Option<&str>) -> bool {
+    let language = locale
+        .and_then(|tag| tag.split(['-', '_']).next())
+        .unwrap_or("");
+    turkish_i.unwrap_or(false)
+        || language.eq_ignore_ascii_case("tr")
+        || language.eq_ignore_ascii_case("az")
+}
+
+/// The `keepLetters` set, with the other case of each letter as well, since
+/// letters are compared before case folding.
+fn keep_letters(letters: Option<&str>) -> HashSet<char> {
+    // A mapping to several chars, like 'ß' to "SS", has no letter to keep.
+    fn single(mut mapped: impl ExactSizeIterator<Item = char>) -> Option<char> {
+        (mapped.len() == 1)
+            .then(|| mapped.next())
+            .flatten()
+    }
+
+    let mut keep = HashSet::new();
+    for c in letters.unwrap_or_default().chars() {
+        keep.insert(c);
+        keep.extend(single(c.to_lowercase()));
+        keep.extend(single(c.to_uppercase()));
+    }
+    keep
+}
+
+/// Adds the user-facing `ignoreChars`, `stem` and `stopwords` filters to
+/// `options`.
+///
+/// Ignored characters and stopwords are normalized with the same settings as
+/// the text they filter. Requesting `stem` from a build without the
+/// `stemming` feature is an error rather than a silent no-op.
+fn with_token_filters(
+    mut options: NormalizationOptions,
+    ignore_chars: Option<&str>,
+    stem: bool,
+    stopwords: Option<&[String]>,
+) -> Result<NormalizationOptions> {
+    if stem && !cfg!(feature = "stemming") {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "stemming requires the `stemming` feature".to_string(),
+        ));
+    }
+
+    // Keep the raw character as well: whitespace, for one, normalizes away.
+    options.ignore_chars = ignore_chars
+        .unwrap_or_default()
+        .chars()
+        .flat_map(|c| {
+            let normalized = normalize_with(&c.to_string(), &options);
+            std::iter::once(c).chain(normalized.chars().collect::<Vec<_>>())
+        })
+        .collect();
+    options.stopwords = stopwords
+        .unwrap_or_default()
+        .iter()
+        .map(|word| normalize_with(word, &options))
+        .filter(|word| !word.is_empty())
+        .collect();
+    options.stem = stem;
+    Ok(options)
+}
+
+/// Reports the inputs behind [`similarity`] for the same pair.
+fn explain_similarity(str1: &str, str2: &str) -> ScoreExplanation {
+    ScoreExplanation {
+        distance: levenshtein_distance(str1, str2) as u32,
+        max_length: str1.len().max(str2.len()) as u32,
+        algorithm: "levenshtein".to_string(),
+        boosts: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_known_pair() {
+        let options = SearchOptions {
+            explain: Some(true),
+            ..Default::default()
+        };
+        let results = search(
+            "kitten".to_string(),
+            vec!["sitting".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        let explanation = results[0].explanation.as_ref().unwrap();
+        assert_eq!(explanation.distance, 3);
+        assert_eq!(explanation.max_length, 7);
+        assert_eq!(explanation.algorithm, "levenshtein");
+        assert!(explanation.boosts.is_empty());
+        assert_eq!(
+            results[0].score,
+            1.0 - explanation.distance as f64 / explanation.max_length as f64
+        );
+    }
+
+    #[test]
+    fn test_search_with_count_reports_total_before_limit() {
+        let items: Vec<String> = (0..50)
+            .map(|i| format!("item{i}"))
+            .collect();
+        let options = SearchOptions {
+            limit: Some(Some(10.0)),
+            threshold: Some(0.5),
+            ..Default::default()
+        };
+        let counted =
+            search_with_count("item".to_string(), items, Some(options))
+                .unwrap();
+
+        assert_eq!(counted.results.len(), 10);
+        assert_eq!(counted.total, 50);
+    }
+
+    #[test]
+    fn test_offset_skips_sorted_results() {
+        let items: Vec<String> = ["apple", "apply", "ample", "maple"]
+            .map(String::from)
+            .to_vec();
+        let all = search("apple".to_string(), items.clone(), None).unwrap();
+
+        let paged = |offset, limit| {
+            let options = SearchOptions {
+                offset: Some(offset),
+                limit,
+                ..Default::default()
+            };
+            search("apple".to_string(), items.clone(), Some(options)).unwrap()
+        };
+
+        let with_limit = paged(1, Some(Some(2.0)));
+        assert_eq!(with_limit.len(), 2);
+        assert_eq!(with_limit[0].index, all[1].index);
+        assert_eq!(with_limit[1].index, all[2].index);
+
+        let without_limit = paged(2, Some(None));
+        assert_eq!(without_limit.len(), 2);
+        assert_eq!(without_limit[0].index, all[2].index);
+
+        assert!(paged(10, Some(None)).is_empty());
+    }
+
+    #[test]
+    fn test_search_any_keeps_best_query() {
+        let results = search_any(
+            vec!["car".to_string(), "automobile".to_string()],
+            vec!["automobile".to_string(), "cart".to_string()],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(results[0].item, "automobile");
+        assert_eq!(results[0].score, 1.0);
+        assert_eq!(results[0].query_index, Some(1));
+        assert_eq!(results[1].item, "cart");
+        assert_eq!(results[1].query_index, Some(0));
+    }
+
+    #[test]
+    fn test_search_any_applies_threshold_to_best_score() {
+        let options = SearchOptions {
+            threshold: Some(0.9),
+            ..Default::default()
+        };
+        let results = search_any(
+            vec!["xyz".to_string(), "apple".to_string()],
+            vec!["apple".to_string(), "banana".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].item, "apple");
+    }
+
+    #[test]
+    fn test_search_multi_combines_query_scores() {
+        let multi = |aggregator: &str| {
+            search_multi(
+                vec!["cat".to_string(), "car".to_string()],
+                ["car", "cart", "cat", "dog"]
+                    .map(String::from)
+                    .to_vec(),
+                aggregator.to_string(),
+                None,
+            )
+            .unwrap()
+        };
+
+        // "car" and "cat" each match one query exactly and the other with
+        // one edit in three; "cart" is one edit in four from both.
+        let max = multi("max");
+        assert_eq!(max[0].item, "car");
+        assert_eq!(max[0].score, 1.0);
+        assert_eq!(max[0].query_index, Some(1));
+        assert_eq!(max[2].item, "cart");
+
+        let min = multi("min");
+        assert_eq!(min[0].item, "cart");
+        assert_eq!(min[0].score, 0.75);
+        assert_eq!(min[0].query_index, Some(0));
+        assert_eq!(min[1].score, 1.0 - 1.0 / 3.0);
+
+        let mean = multi("mean");
+        assert_eq!(mean[0].item, "car");
+        assert!((mean[0].score - 5.0 / 6.0).abs() < 1e-12);
+        assert_eq!(mean[0].query_index, None);
+        assert_eq!(mean[2].item, "cart");
+
+        assert!(search_multi(vec![], vec![], "sum".to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_sample_limit_is_reproducible_with_a_seed() {
+        let items: Vec<String> = (0..2_000)
+            .map(|i| format!("item {i}"))
+            .collect();
+        let sampled = |seed| {
+            search_with_count(
+                "item 1234".to_string(),
+                items.clone(),
+                Some(SearchOptions {
+                    sample_limit: Some(100),
+                    sample_seed: Some(seed),
+                    ..Default::default()
+                }),
+            )
+            .unwrap()
+        };
+
+        let indices = |results: SearchResults| -> Vec<u32> {
+            results
+                .results
+                .iter()
+                .map(|r| r.index)
+                .collect()
+        };
+        let first = sampled(42);
+        assert!(first.approximate);
+        assert_eq!(first.total, 100);
+        assert_eq!(first.item_count, 2_000);
+        let first = indices(first);
+        assert_eq!(indices(sampled(42)), first);
+        assert_ne!(indices(sampled(43)), first);
+
+        let full =
+            search_with_count("item 1234".to_string(), items, None).unwrap();
+        assert!(!full.approximate);
+        assert_eq!(full.results[0].item, "item 1234");
+    }
+
+    #[test]
+    fn test_min_trigram_overlap_matches_brute_force() {
+        let items: Vec<String> = [
+            "apple",
+            "apply",
+            "maple",
+            "ample",
+            "pineapple",
+            "applesauce",
+            "banana",
+            "bandana",
+            "cabana",
+            "orange",
+            "grape",
+            "grapefruit",
+            "strawberry",
+            "raspberry",
+            "blueberry",
+            "kiwi",
+            "mango",
+            "melon",
+        ]
+        .map(String::from)
+        .to_vec();
+        let search_with = |query: &str, min_trigram_overlap| {
+            search_with_count(
+                query.to_string(),
+                items.clone(),
+                Some(SearchOptions {
+                    threshold: Some(0.6),
+                    limit: Some(None),
+                    min_trigram_overlap,
+                    ..Default::default()
+                }),
+            )
+            .unwrap()
+            .results
+            .iter()
+            .map(|r| (r.index, r.score))
+            .collect::<Vec<_>>()
+        };
+
+        for query in ["aple", "banan", "orang", "grap", "blubery", "melno"] {
+            let brute_force = search_with(query, None);
+            assert!(!brute_force.is_empty(), "{query}");
+            assert_eq!(search_with(query, Some(1)), brute_force, "{query}");
+        }
+        // Too high a cutoff drops good matches.
+        assert!(search_with("kiwi", Some(5)).is_empty());
+        assert_eq!(search_with("kiwi", Some(4)).len(), 1);
+    }
+
+    #[test]
+    fn test_trim_without_normalization() {
+        let options = |trim| SearchOptions {
+            normalize: Some(false),
+            trim: Some(trim),
+            include_normalized: Some(true),
+            ..Default::default()
+        };
+        let items = vec!["  Café  au lait ".to_string()];
+
+        let untrimmed = search(
+            "  café  au lait".to_string(),
+            items.clone(),
+            Some(options(false)),
+        )
+        .unwrap();
+        assert!(untrimmed[0].score < 1.0);
+
+        let trimmed =
+            search("  café  au lait".to_string(), items, Some(options(true)))
+                .unwrap();
+        assert_eq!(
+            trimmed[0].normalized_item.as_deref(),
+            Some("café  au lait")
+        );
+        assert_eq!(trimmed[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_strip_control_matches_pasted_text() {
+        let options = |strip_control| SearchOptions {
+            strip_control: Some(strip_control),
+            ..Default::default()
+        };
+        let items = vec!["\u{FEFF}New\u{200B}York".to_string()];
+
+        let kept =
+            search("newyork".to_string(), items.clone(), Some(options(false)))
+                .unwrap();
+        assert!(kept[0].score < 1.0);
+
+        let stripped =
+            search("new\u{200B}york".to_string(), items, Some(options(true)))
+                .unwrap();
+        assert_eq!(stripped[0].score, 1.0);
+        assert_eq!(stripped[0].item, "\u{FEFF}New\u{200B}York");
+    }
+
+    #[test]
+    fn test_token_scores_align_with_query_tokens() {
+        let options = SearchOptions {
+            include_token_scores: Some(true),
+            threshold: Some(0.0),
+            ..Default::default()
+        };
+        let items = vec!["Reed Cart".to_string(), "red bus".to_string()];
+        let results =
+            search("red car".to_string(), items, Some(options)).unwrap();
+        let token_scores = |item: &str| {
+            results
+                .iter()
+                .find(|r| r.item == item)
+                .and_then(|r| r.token_scores.clone())
+        };
+
+        assert_eq!(token_scores("Reed Cart"), Some(vec![0.75, 0.75]));
+        assert_eq!(token_scores("red bus"), Some(vec![1.0, 0.0]));
+
+        let plain =
+            search("red car".to_string(), vec!["red car".to_string()], None)
+                .unwrap();
+        assert_eq!(plain[0].token_scores, None);
+    }
+
+    #[test]
+    fn test_score_matches_search() {
+        let items: Vec<String> = ["Café au lait", "cafeteria", "report.pdf"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = [
+            SearchOptions::default(),
+            SearchOptions {
+                algorithm: Some(Either::A("osa".to_string())),
+                prefix_boost_weight: Some(0.1),
+                precision: Some(3),
+                ..Default::default()
+            },
+            SearchOptions {
+                require_all_tokens: Some(true),
+                word_count_bonus: Some(0.1),
+                ..Default::default()
+            },
+            SearchOptions {
+                boost_suffix: Some(0.3),
+                length_penalty: Some(0.5),
+                ignore_case: Some(false),
+                ..Default::default()
+            },
+        ];
+
+        for opts in options {
+            let opts = SearchOptions {
+                threshold: Some(0.0),
+                limit: Some(None),
+                ..opts
+            };
+            for query in ["cafe", "Cafe au lait", "pdf"] {
+                let results = search(
+                    query.to_string(),
+                    items.clone(),
+                    Some(opts.clone()),
+                )
+                .unwrap();
+                for result in results {
+                    let single = score(
+                        query.to_string(),
+                        result.item.clone(),
+                        Some(opts.clone()),
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        single, result.score,
+                        "{query:?} {}",
+                        result.item
+                    );
+                }
+            }
+        }
+
+        // Items `search` would leave out score zero, with or without a
+        // threshold.
+        let strict = SearchOptions {
+            max_distance: Some(1),
+            threshold: Some(0.9),
+            ..Default::default()
+        };
+        let far =
+            score("apple".to_string(), "banana".to_string(), Some(strict));
+        assert_eq!(far.unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_score_detailed_reports_distance() {
+        let detailed = |a: &str, b: &str, options| {
+            score_detailed(a.to_string(), b.to_string(), options).unwrap()
+        };
+
+        let equal = detailed("apple", "Apple", None);
+        assert_eq!(equal.distance, 0);
+        assert_eq!(equal.similarity, 1.0);
+
+        let empty = detailed("", "abc", None);
+        assert_eq!(empty.distance, 3);
+        assert_eq!(empty.similarity, 0.0);
+        assert_eq!(detailed("", "", None).similarity, 1.0);
+
+        let typical = detailed("kitten", "sitting", None);
+        assert_eq!(typical.distance, 3);
+        assert_eq!(typical.similarity, 1.0 - 3.0 / 7.0);
+
+        let osa = SearchOptions {
+            algorithm: Some(Either::A("osa".to_string())),
+            ..Default::default()
+        };
+        let swapped = detailed("martha", "marhta", Some(osa.clone()));
+        assert_eq!(swapped.distance, 1);
+        assert_eq!(
+            swapped.similarity,
+            fuzzy(
+                "martha".to_string(),
+                "marhta".to_string(),
+                Some(Either::B(osa))
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_similarity_pairs_match_dense_matrix() {
+        let items: Vec<String> =
+            ["apple", "apply", "applet", "zebra", "", "zebras", "maple"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+
+        for (threshold, parallel_threshold) in
+            [(0.0, None), (0.5, Some(0)), (0.8, None), (1.0, Some(0))]
+        {
+            let options = SearchOptions {
+                threshold: Some(threshold),
+                parallel_threshold,
+                precision: Some(4),
+                ..Default::default()
+            };
+            let dense = similarity_matrix(items.clone(), Some(options.clone()))
+                .unwrap();
+            let expected: Vec<(u32, u32, f64)> = (0..items.len())
+                .flat_map(|i| (i + 1..items.len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| threshold == 0.0 || dense[i][j] > 0.0)
+                .map(|(i, j)| (i as u32, j as u32, dense[i][j]))
+                .collect();
+
+            let sparse = similarity_pairs(items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|pair| (pair.i, pair.j, pair.score))
+                .collect::<Vec<_>>();
+            assert_eq!(sparse, expected, "threshold {threshold}");
+        }
+        assert!(similarity_pairs(Vec::new(), None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sample_limit_applies_to_every_search() {
+        let items: Vec<String> = (0..2_000)
+            .map(|i| format!("item {i}"))
+            .collect();
+        let options = SearchOptions {
+            sample_limit: Some(100),
+            sample_seed: Some(7),
+            limit: Some(None),
+            ..Default::default()
+        };
+        let query = || "item 1234".to_string();
+        let indices = |results: Vec<SearchResult>| -> Vec<u32> {
+            let mut indices: Vec<u32> = results
+                .iter()
+                .map(|r| r.index)
+                .collect();
+            indices.sort_unstable();
+            indices
+        };
+
+        let sampled = indices(
+            search(query(), items.clone(), Some(options.clone())).unwrap(),
+        );
+        assert_eq!(sampled.len(), 100);
+
+        let any = search_any(
+            vec![query(), "item 42".to_string()],
+            items.clone(),
+            Some(options.clone()),
+        )
+        .unwrap();
+        assert_eq!(indices(any), sampled);
+
+        let groups: Vec<Vec<String>> = items
+            .iter()
+            .map(|item| vec![item.clone()])
+            .collect();
+        let aliases =
+            search_aliases(query(), groups, Some(options.clone())).unwrap();
+        assert_eq!(indices(aliases), sampled);
+
+        let best = best_match(query(), items.clone(), Some(options.clone()))
+            .unwrap()
+            .unwrap();
+        assert!(sampled.contains(&best.index));
+
+        // The sample is too small to sample again, so this scores it all.
+        let subset: Vec<String> = sampled
+            .iter()
+            .map(|&index| items[index as usize].clone())
+            .collect();
+        assert_eq!(
+            suggest_threshold(query(), items, Some(options)).unwrap(),
+            suggest_threshold(query(), subset, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_long_items_score_zero() {
+        let options = SearchOptions {
+            max_input_length: Some(8),
+            ..Default::default()
+        };
+        let results = search(
+            "apple".to_string(),
+            vec!["a".repeat(9), "apple".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(results[0].item, "apple");
+        assert_eq!(results[1].score, 0.0);
+    }
+
+    #[test]
+    fn test_long_query_is_rejected() {
+        let long = "a".repeat(DEFAULT_MAX_INPUT_LENGTH as usize + 1);
+        assert!(search(long.clone(), vec!["apple".to_string()], None).is_err());
+        assert!(fuzzy(long, "apple".to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_length_is_counted_in_chars() {
+        assert!(!exceeds_length("café", 4));
+        assert!(exceeds_length("cafés", 4));
+    }
+
+    #[test]
+    fn test_exact_substring_keeps_corpus_order() {
+        let options = SearchOptions {
+            exact_substring: Some(true),
+            threshold: Some(1.0),
+            ..Default::default()
+        };
+        let items: Vec<String> = ["Pineapple", "apply", "Apple pie", "grape"]
+            .map(String::from)
+            .to_vec();
+        let results = search("APP".to_string(), items, Some(options)).unwrap();
+
+        let indices: Vec<u32> = results
+            .iter()
+            .map(|r| r.index)
+            .collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert!(results.iter().all(|r| r.score == 1.0));
+    }
+
+    #[test]
+    fn test_weights_boost_lower_similarity_item() {
+        let options = SearchOptions {
+            weights: Some(vec![1.0, 2.0]),
+            include_raw_score: Some(true),
+            explain: Some(true),
+            ..Default::default()
+        };
+        let results = search(
+            "apple".to_string(),
+            vec!["apple".to_string(), "maple".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(results[0].item, "maple");
+        assert_eq!(results[0].score, 1.2);
+        assert_eq!(results[0].raw_score, Some(0.6));
+        let boosts = &results[0]
+            .explanation
+            .as_ref()
+            .unwrap()
+            .boosts;
+        assert_eq!(boosts[0].name, "weight");
+        assert_eq!(boosts[0].value, 2.0);
+        assert_eq!(results[1].raw_score, Some(1.0));
+    }
+
+    #[test]
+    fn test_mismatched_weights_are_rejected() {
+        let options = SearchOptions {
+            weights: Some(vec![1.0]),
+            ..Default::default()
+        };
+        let items = vec!["apple".to_string(), "maple".to_string()];
+        assert!(
+            search("apple".to_string(), items.clone(), Some(options)).is_err()
+        );
+
+        for weight in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let options = SearchOptions {
+                weights: Some(vec![1.0, weight]),
+                threshold: Some(0.0),
+                ..Default::default()
+            };
+            assert!(search(
+                "apple".to_string(),
+                items.clone(),
+                Some(options.clone())
+            )
+            .is_err());
+            assert!(suggest_threshold(
+                "apple".to_string(),
+                items.clone(),
+                Some(options)
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_min_query_length_drops_short_queries() {
+        let options = || SearchOptions {
+            min_query_length: Some(2),
+            ..Default::default()
+        };
+        let items = vec!["a".to_string(), "apple".to_string()];
+
+        let counted =
+            search_with_count("a".to_string(), items.clone(), Some(options()))
+                .unwrap();
+        assert!(counted.results.is_empty());
+        assert_eq!(counted.total, 0);
+
+        // Normalization runs first, so padding doesn't get a query through.
+        assert!(search(" a ".to_string(), items.clone(), Some(options()))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            search("ap".to_string(), items.clone(), Some(options()))
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let any = search_any(
+            vec!["a".to_string(), "apple".to_string()],
+            items,
+            Some(options()),
+        )
+        .unwrap();
+        assert!(any
+            .iter()
+            .all(|r| r.query_index == Some(1)));
+    }
+
+    #[test]
+    fn test_empty_query_returns_all_items() {
+        let items: Vec<String> = ["banana", "apple", "cherry"]
+            .map(String::from)
+            .to_vec();
+        let options = |empty_query_returns_all| SearchOptions {
+            empty_query_returns_all,
+            threshold: Some(0.5),
+            ..Default::default()
+        };
+
+        let default =
+            search("  ".to_string(), items.clone(), Some(options(None)))
+                .unwrap();
+        assert!(default.is_empty());
+
+        let all =
+            search("".to_string(), items, Some(options(Some(true)))).unwrap();
+        let indices: Vec<u32> = all.iter().map(|r| r.index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert!(all.iter().all(|r| r.score == 1.0));
+    }
+
+    #[test]
+    fn test_alphanumeric_only_matches_part_numbers() {
+        let options = SearchOptions {
+            alphanumeric_only: Some(true),
+            ..Default::default()
+        };
+        let results = search(
+            "ABC-123".to_string(),
+            vec!["abc124".to_string(), "abc123".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(results[0].item, "abc123");
+        assert_eq!(results[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_score_formula_changes_scores() {
+        let scores = |score_formula: &str| {
+            let options = SearchOptions {
+                score_formula: Some(score_formula.to_string()),
+                ..Default::default()
+            };
+            search(
+                "cat".to_string(),
+                vec!["cut".to_string(), "category".to_string()],
+                Some(options),
+            )
+            .unwrap()
+            .iter()
+            .map(|r| (r.index, r.score))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(scores("distance"), vec![(0, 1.0 - 1.0 / 3.0), (1, 0.375)]);
+        assert_eq!(
+            scores("ratio"),
+            vec![(0, 1.0 - 1.0 / 6.0), (1, 1.0 - 5.0 / 11.0)]
+        );
+    }
+
+    #[test]
+    fn test_search_grouped_buckets_results() {
+        let options = SearchOptions {
+            limit: Some(None),
+            ..Default::default()
+        };
+        let groups = search_grouped(
+            "apple".to_string(),
+            ["apple", "apply", "maple", "kiwi"]
+                .map(String::from)
+                .to_vec(),
+            vec![0.9, 0.7],
+            Some(options),
+        )
+        .unwrap();
+
+        let items: Vec<Vec<&str>> = groups
+            .iter()
+            .map(|g| {
+                g.results
+                    .iter()
+                    .map(|r| r.item.as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![vec!["apple"], vec!["apply"], vec!["maple", "kiwi"]]
+        );
+    }
+
+    #[test]
+    fn test_search_grouped_by_initial_uses_normalized_items() {
+        let items = ["Apple", "apply", "Éclair", "7up", "maple", "  ample"]
+            .map(String::from)
+            .to_vec();
+        let grouped = |options| {
+            search_grouped_by_initial(
+                "apple".to_string(),
+                items.clone(),
+                options,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|g| {
+                let items = g
+                    .results
+                    .into_iter()
+                    .map(|r| r.item)
+                    .collect::<Vec<_>>();
+                (g.initial, items)
+            })
+            .collect::<Vec<_>>()
+        };
+
+        let options = SearchOptions {
+            limit: Some(None),
+            ..Default::default()
+        };
+        assert_eq!(
+            grouped(Some(options.clone())),
+            vec![
+                ("#".to_string(), vec!["7up".to_string()]),
+                (
+                    "a".to_string(),
+                    ["Apple", "apply", "  ample"]
+                        .map(String::from)
+                        .to_vec()
+                ),
+                ("e".to_string(), vec!["Éclair".to_string()]),
+                ("m".to_string(), vec!["maple".to_string()]),
+            ]
+        );
+
+        let cased = grouped(Some(SearchOptions {
+            ignore_case: Some(false),
+            ..options
+        }));
+        let initials: Vec<&str> = cased
+            .iter()
+            .map(|(initial, _)| initial.as_str())
+            .collect();
+        assert_eq!(initials, vec!["#", "A", "E", "a", "m"]);
+    }
+
+    #[test]
+    fn test_compatibility_matches_full_width() {
+        let items = vec!["ＡＢＣ１２３".to_string()];
+        let score = |compatibility| {
+            let options = SearchOptions {
+                compatibility,
+                ..Default::default()
+            };
+            search("abc123".to_string(), items.clone(), Some(options)).unwrap()
+                [0]
+            .score
+        };
+
+        assert_eq!(score(Some(true)), 1.0);
+        assert!(score(None) < 1.0);
+    }
+
+    #[test]
+    fn test_search_indices_match_search() {
+        let items: Vec<String> = ["apple", "apply", "banana", "maple"]
+            .map(String::from)
+            .to_vec();
+        let options = || SearchOptions {
+            offset: Some(1),
+            threshold: Some(0.5),
+            ..Default::default()
+        };
+
+        let full =
+            search("aple".to_string(), items.clone(), Some(options())).unwrap();
+        let indices =
+            search_indices("aple".to_string(), items, Some(options())).unwrap();
+
+        assert_eq!(indices.len(), full.len());
+        for (scored, result) in indices.iter().zip(&full) {
+            assert_eq!(scored.index, result.index);
+            assert_eq!(scored.score, result.score);
+        }
+    }
+
+    #[test]
+    fn test_parallel_search_matches_sequential() {
+        let items: Vec<String> = (0..200)
+            .map(|i| format!("item {i}"))
+            .collect();
+        let results = |parallel_threshold| {
+            let options = SearchOptions {
+                parallel_threshold: Some(parallel_threshold),
+                require_all_tokens: Some(true),
+                limit: Some(None),
+                ..Default::default()
+            };
+            search("item 42".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .iter()
+                .map(|r| (r.index, r.score))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(results(0), results(u32::MAX));
+    }
+
+    #[test]
+    fn test_ignore_chars_match_phone_numbers() {
+        let options = SearchOptions {
+            ignore_chars: Some("- ".to_string()),
+            ..Default::default()
+        };
+        let results = search(
+            "555-1234".to_string(),
+            vec!["555 1235".to_string(), "5551234".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(results[0].item, "5551234");
+        assert_eq!(results[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_boost_suffix_changes_ranking() {
+        let items = vec!["pdfs".to_string(), "report.pdf".to_string()];
+        let best = |boost_suffix| {
+            let options = SearchOptions {
+                boost_suffix,
+                ..Default::default()
+            };
+            search("PDF".to_string(), items.clone(), Some(options)).unwrap()[0]
+                .item
+                .clone()
+        };
+
+        assert_eq!(best(None), "pdfs");
+        assert_eq!(best(Some(0.5)), "report.pdf");
+    }
+
+    #[test]
+    fn test_fold_confusables_matches_spoofed_item() {
+        let options = SearchOptions {
+            fold_confusables: Some(true),
+            ..Default::default()
+        };
+        let results = search(
+            "apple".to_string(),
+            vec!["appel".to_string(), "\u{0430}pple".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(results[0].index, 1);
+        assert_eq!(results[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_best_match_hit_and_miss() {
+        let items: Vec<String> = ["aple", "apple", "apple", "banana"]
+            .map(String::from)
+            .to_vec();
+        let options = || SearchOptions {
+            threshold: Some(0.8),
+            ..Default::default()
+        };
+
+        let best =
+            best_match("apple".to_string(), items.clone(), Some(options()))
+                .unwrap()
+                .unwrap();
+        assert_eq!(best.index, 1);
+        assert_eq!(best.score, 1.0);
+
+        let parallel = SearchOptions {
+            parallel_threshold: Some(0),
+            ..options()
+        };
+        let best =
+            best_match("apple".to_string(), items.clone(), Some(parallel))
+                .unwrap()
+                .unwrap();
+        assert_eq!(best.index, 1);
+
+        assert!(best_match("kiwi".to_string(), items, Some(options()))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_accent_insensitive_case_sensitive() {
+        let options = SearchOptions {
+            ignore_case: Some(false),
+            ..Default::default()
+        };
+        let items = vec!["Café".to_string(), "cafe".to_string()];
+        let results = search("Cafe".to_string(), items, Some(options)).unwrap();
+
+        assert_eq!(results[0].item, "Café");
+        assert_eq!(results[0].score, 1.0);
+        assert!(results[1].score < 1.0);
+    }
+
+    #[test]
+    fn test_max_distance_and_threshold_both_apply() {
+        let items: Vec<String> = ["apple", "apply", "appeal", "maple", "pear"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let search_with = |max_distance, threshold| {
+            let options = SearchOptions {
+                max_distance,
+                threshold,
+                ..Default::default()
+            };
+            search("appel".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|r| r.item)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(search_with(Some(2), None), ["appeal", "apple", "apply"]);
+        assert_eq!(search_with(Some(2), Some(0.7)), ["appeal"]);
+        assert_eq!(search_with(Some(0), None), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_limited_search_matches_full_ranking() {
+        let items: Vec<String> = (0..200)
+            .map(|i| format!("item {}", i % 37))
+            .collect();
+        let ranked = |limit, offset, parallel_threshold| {
+            let options = SearchOptions {
+                limit,
+                offset: Some(offset),
+                parallel_threshold: Some(parallel_threshold),
+                ..Default::default()
+            };
+            search_with_count(
+                "item 3".to_string(),
+                items.clone(),
+                Some(options),
+            )
+            .unwrap()
+        };
+
+        let all = ranked(Some(None), 0, u32::MAX);
+        for (limit, offset) in
+            [(0., 0), (1., 0), (10., 0), (10., 25), (5., 198)]
+        {
+            for parallel_threshold in [0, u32::MAX] {
+                let page =
+                    ranked(Some(Some(limit)), offset, parallel_threshold);
+                let expected: Vec<u32> = all
+                    .results
+                    .iter()
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .map(|r| r.index)
+                    .collect();
+                let indices: Vec<u32> = page
+                    .results
+                    .iter()
+                    .map(|r| r.index)
+                    .collect();
+                assert_eq!(indices, expected, "limit {limit}, offset {offset}");
+                assert_eq!(page.total, all.total);
+            }
+        }
+    }
+
+    #[test]
+    fn test_include_normalized_reports_normalized_item() {
+        let items = vec!["Crème  Brûlée".to_string()];
+        let results = |include_normalized| {
+            let options = SearchOptions {
+                include_normalized,
+                ..Default::default()
+            };
+            search("creme".to_string(), items.clone(), Some(options)).unwrap()
+        };
+
+        assert_eq!(
+            results(Some(true))[0]
+                .normalized_item
+                .as_deref(),
+            Some("creme brulee")
+        );
+        assert_eq!(results(None)[0].normalized_item, None);
+    }
+
+    #[test]
+    fn test_fuzzy_accepts_search_options() {
+        let fuzzy_with = |a: &str, b: &str, opts| {
+            fuzzy(a.to_string(), b.to_string(), Some(Either::B(opts))).unwrap()
+        };
+
+        let case_sensitive = SearchOptions {
+            ignore_case: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(fuzzy_with("Café", "Cafe", case_sensitive.clone()), 1.0);
+        assert_eq!(fuzzy_with("Café", "cafe", case_sensitive), 0.75);
+
+        let accent_sensitive = SearchOptions {
+            normalize: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(fuzzy_with("CAFÉ", "café", accent_sensitive.clone()), 1.0);
+        assert!(fuzzy_with("café", "cafe", accent_sensitive) < 1.0);
+
+        let legacy = |normalize| {
+            fuzzy("Café".to_string(), "cafe".to_string(), normalize).unwrap()
+        };
+        assert_eq!(legacy(None), 1.0);
+        assert_eq!(legacy(Some(Either::A(true))), 1.0);
+        assert!(legacy(Some(Either::A(false))) < 1.0);
+    }
+
+    #[test]
+    fn test_normalization_cache_never_changes_results() {
+        let items: Vec<String> = ["Crème Brûlée", "CRÈME", "creme", "Café"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let scores = |cache_normalization, ignore_case| {
+            let options = SearchOptions {
+                cache_normalization,
+                ignore_case: Some(ignore_case),
+                include_normalized: Some(true),
+                ..Default::default()
+            };
+            search("Creme".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.index, r.score, r.normalized_item))
+                .collect::<Vec<_>>()
+        };
+
+        for ignore_case in [true, false, true] {
+            let uncached = scores(None, ignore_case);
+            assert_eq!(scores(Some(true), ignore_case), uncached);
+            assert_eq!(scores(Some(true), ignore_case), uncached);
+        }
+    }
+
+    #[test]
+    fn test_sort_keys_reorder_ties() {
+        let items: Vec<String> = ["cart", "card", "care", "dog"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let ranked = |sort_keys: Option<Vec<f64>>, limit| {
+            let options = SearchOptions {
+                sort_keys,
+                limit: Some(Some(limit)),
+                ..Default::default()
+            };
+            search("car".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|r| r.index)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranked(None, 10.0), [0, 1, 2, 3]);
+        let keys = Some(vec![1.0, 3.0, 2.0, 9.0]);
+        assert_eq!(ranked(keys.clone(), 10.0), [1, 2, 0, 3]);
+        assert_eq!(ranked(keys, 2.0), [1, 2]);
+
+        let best = best_match(
+            "car".to_string(),
+            items.clone(),
+            Some(SearchOptions {
+                sort_keys: Some(vec![1.0, 3.0, 2.0, 9.0]),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        assert_eq!(best.unwrap().index, 1);
+
+        for bad in [vec![1.0], vec![0.0, 0.0, 0.0, f64::NAN]] {
+            let options = SearchOptions {
+                sort_keys: Some(bad),
+                ..Default::default()
+            };
+            assert!(search("car".to_string(), items.clone(), Some(options))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_suggest_threshold_cuts_bimodal_scores() {
+        let items: Vec<String> =
+            ["apple", "apply", "appl", "zebra", "xylophone", "quartz"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        let threshold =
+            suggest_threshold("apple".to_string(), items.clone(), None)
+                .unwrap();
+
+        let options = SearchOptions {
+            threshold: Some(threshold),
+            ..Default::default()
+        };
+        let kept: Vec<u32> =
+            search("apple".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|r| r.index)
+                .collect();
+        assert_eq!(kept, [0, 1, 2]);
+
+        let single = vec!["apple".to_string()];
+        assert_eq!(
+            suggest_threshold("apple".to_string(), single, None).unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_similarity_matrix_is_symmetric() {
+        let items: Vec<String> = ["apple", "apply", "zebra", ""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let matrix = similarity_matrix(items.clone(), None).unwrap();
+
+        assert_eq!(matrix.len(), items.len());
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 1.0);
+            for (j, &score) in row.iter().enumerate() {
+                assert_eq!(score, matrix[j][i]);
+            }
+        }
+        assert_eq!(
+            matrix[0][1],
+            fuzzy_with("apple", "apply", &Default::default()).unwrap()
+        );
+
+        let options = SearchOptions {
+            threshold: Some(0.5),
+            parallel_threshold: Some(0),
+            ..Default::default()
+        };
+        let matrix = similarity_matrix(items, Some(options)).unwrap();
+        assert_eq!(matrix[0][1], 0.8);
+        assert_eq!(matrix[0][2], 0.0);
+        assert!(similarity_matrix(Vec::new(), None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_natural_order_breaks_ties() {
+        let items: Vec<String> = ["item10", "item2", "item1", "other"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let ranked = |natural_order, limit| {
+            let options = SearchOptions {
+                natural_order: Some(natural_order),
+                exact_substring: Some(true),
+                limit: Some(Some(limit)),
+                ..Default::default()
+            };
+            search("item".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|r| r.item)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranked(false, 3.0), ["item10", "item2", "item1"]);
+        assert_eq!(ranked(true, 10.0), ["item1", "item2", "item10", "other"]);
+        assert_eq!(ranked(true, 2.0), ["item1", "item2"]);
+    }
+
+    #[test]
+    fn test_search_aliases_returns_canonical_name() {
+        let groups: Vec<Vec<String>> = vec![
+            vec!["United States".into(), "USA".into()],
+            vec!["United Kingdom".into(), "UK".into()],
+            vec![],
+        ];
+        let options = SearchOptions {
+            threshold: Some(0.5),
+            include_normalized: Some(true),
+            explain: Some(true),
+            ..Default::default()
+        };
+        let results =
+            search_aliases("usa".to_string(), groups.clone(), Some(options))
+                .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].item, "United States");
+        assert_eq!(results[0].index, 0);
+        assert_eq!(results[0].alias_index, Some(1));
+        assert_eq!(results[0].score, 1.0);
+        assert_eq!(results[0].normalized_item.as_deref(), Some("usa"));
+        assert_eq!(
+            results[0]
+                .explanation
+                .as_ref()
+                .unwrap()
+                .distance,
+            0
+        );
+
+        let results = search_aliases("x".to_string(), groups, None).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[2].item, "");
+        assert_eq!(results[2].alias_index, None);
+    }
+
+    #[test]
+    fn test_preserve_whitespace_keeps_tabs_and_double_spaces() {
+        let options = |preserve_whitespace| SearchOptions {
+            preserve_whitespace: Some(preserve_whitespace),
+            include_normalized: Some(true),
+            ..Default::default()
+        };
+        let items = vec!["\tif  (x)".to_string()];
+
+        let collapsed =
+            search("if (x)".to_string(), items.clone(), Some(options(false)))
+                .unwrap();
+        assert_eq!(collapsed[0].normalized_item.as_deref(), Some("if (x)"));
+        assert_eq!(collapsed[0].score, 1.0);
+
+        let preserved =
+            search("if (x)".to_string(), items.clone(), Some(options(true)))
+                .unwrap();
+        assert_eq!(preserved[0].normalized_item.as_deref(), Some("\tif  (x)"));
+        assert!(preserved[0].score < 1.0);
+
+        let exact = search("\tif  (x)".to_string(), items, Some(options(true)))
+            .unwrap();
+        assert_eq!(exact[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_precision_rounds_after_ranking() {
+        let items: Vec<String> = ["abcdef", "abcdex", "abcdxy"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = |precision, threshold| SearchOptions {
+            precision: Some(precision),
+            threshold: Some(threshold),
+            include_raw_score: Some(true),
+            ..Default::default()
+        };
+        let scores = |options| {
+            search("abcdex".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.score, r.raw_score.unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            scores(options(2, 0.0)),
+            [(1.0, 1.0), (0.83, 0.83), (0.67, 0.67)]
+        );
+        // 5/6 passes the threshold before it is rounded down to 0.8.
+        assert_eq!(scores(options(1, 0.833)), [(1.0, 1.0), (0.8, 0.8)]);
+        assert_eq!(
+            fuzzy_with("abcdef", "abcdex", &options(2, 0.0)).unwrap(),
+            0.83
+        );
+    }
+
+    #[test]
+    fn test_best_match_with_edits_spells_out_the_fix() {
+        let items: Vec<String> = ["Sitting", "mittens"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let found =
+            best_match_with_edits("kitten".to_string(), items.clone(), None)
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(found.result.item, "mittens");
+        let ops: Vec<(&str, u32)> = found
+            .edits
+            .iter()
+            .map(|edit| (edit.op.as_str(), edit.position))
+            .collect();
+        assert_eq!(ops, [("substitute", 0), ("insert", 6)]);
+
+        let options = SearchOptions {
+            threshold: Some(0.99),
+            ..Default::default()
+        };
+        assert!(
+            best_match_with_edits("xyz".to_string(), items, Some(options))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_turkish_locale_folds_dotted_and_dotless_i() {
+        let prepare = |locale: Option<&str>, input: &str| {
+            let opts = SearchOptions {
+                locale: locale.map(str::to_string),
+                ..Default::default()
+            };
+            Scorer::new(&opts)
+                .unwrap()
+                .prepare(input)
+        };
+
+        assert_eq!(prepare(None, "İ"), "i");
+        assert_eq!(prepare(None, "I"), "i");
+        assert_eq!(prepare(Some("tr"), "İ"), "i");
+        assert_eq!(prepare(Some("tr"), "I"), "ı");
+        assert_eq!(prepare(Some("TR-tr"), "I"), "ı");
+        assert_eq!(prepare(Some("en-US"), "I"), "i");
+        assert!(uses_turkish_i(None, Some("az_Latn")));
+        assert!(!uses_turkish_i(None, Some("fr")));
+    }
+
+    #[test]
+    fn test_empty_corpus_under_any_options() {
+        let variants = [
+            SearchOptions::default(),
+            SearchOptions {
+                limit: Some(Some(0.0)),
+                offset: Some(5),
+                threshold: Some(1.0),
+                ..Default::default()
+            },
+            SearchOptions {
+                explain: Some(true),
+                include_raw_score: Some(true),
+                include_normalized: Some(true),
+                natural_order: Some(true),
+                precision: Some(2),
+                ..Default::default()
+            },
+            SearchOptions {
+                require_all_tokens: Some(true),
+                exact_substring: Some(true),
+                weights: Some(Vec::new()),
+                sort_keys: Some(Vec::new()),
+                ..Default::default()
+            },
+            SearchOptions {
+                empty_query_returns_all: Some(true),
+                min_query_length: Some(3),
+                ..Default::default()
+            },
+            SearchOptions {
+                max_distance: Some(1),
+                phonetic: Some(true),
+                graphemes: Some(true),
+                ..Default::default()
+            },
+        ];
+
+        for opts in variants {
+            for query in ["", "x", "apple pie"] {
+                let counted = search_with_count(
+                    query.to_string(),
+                    Vec::new(),
+                    Some(opts.clone()),
+                )
+                .unwrap();
+                assert!(counted.results.is_empty());
+                assert_eq!((counted.total, counted.item_count), (0, 0));
+
+                let q = || query.to_string();
+                let o = || Some(opts.clone());
+                assert!(search_indices(q(), Vec::new(), o())
+                    .unwrap()
+                    .is_empty());
+                assert!(best_match(q(), Vec::new(), o())
+                    .unwrap()
+                    .is_none());
+                assert!(search_any(vec![q()], Vec::new(), o())
+                    .unwrap()
+                    .is_empty());
+                assert!(search_aliases(q(), Vec::new(), o())
+                    .unwrap()
+                    .is_empty());
+                assert_eq!(
+                    suggest_threshold(q(), Vec::new(), o()).unwrap(),
+                    0.0
+                );
+            }
+            assert!(similarity_matrix(Vec::new(), Some(opts.clone()))
+                .unwrap()
+                .is_empty());
+        }
+
+        let strict = SearchOptions {
+            threshold: Some(0.5),
+            ..Default::default()
+        };
+        let counted = search_with_count(
+            "zzz".to_string(),
+            vec!["apple".to_string()],
+            Some(strict),
+        )
+        .unwrap();
+        assert_eq!((counted.total, counted.item_count), (0, 1));
+    }
+
+    #[test]
+    fn test_is_match_at_boundary_thresholds() {
+        let is = |a: &str, b: &str, threshold: f64| {
+            is_match(a.to_string(), b.to_string(), threshold, None).unwrap()
+        };
+
+        // One edit over five characters scores exactly 0.8.
+        assert!(is("apple", "appla", 0.8));
+        assert!(!is("apple", "appla", 0.8 + 1e-9));
+        assert!(is("kitten", "sitting", 0.0));
+        assert!(!is("kitten", "sitting", 0.58));
+        assert!(is("Café", "cafe", 1.0));
+        assert!(is("", "", 1.0));
+        assert!(!is("", "abc", 0.1));
+
+        // 5/6 only reaches 0.9 once rounded to a whole number.
+        let rounded = SearchOptions {
+            precision: Some(0),
+            ..Default::default()
+        };
+        assert!(is_match(
+            "abcdef".to_string(),
+            "abcdex".to_string(),
+            0.9,
+            Some(rounded),
+        )
+        .unwrap());
+        assert!(!is("abcdef", "abcdex", 0.9));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_uses_camel_case() {
+        let config = r#"{
+            "limit": 2,
+            "ignoreCase": false,
+            "algorithm": [{ "name": "osa", "weight": 2 }]
+        }"#;
+        let opts: SearchOptions = serde_json::from_str(config).unwrap();
+        assert_eq!(opts.limit, Some(Some(2.0)));
+        let unlimited: SearchOptions =
+            serde_json::from_str(r#"{ "limit": null }"#).unwrap();
+        assert_eq!(unlimited.limit, Some(None));
+        assert_eq!(opts.ignore_case, Some(false));
+        assert!(opts.threshold.is_none());
+
+        let json = serde_json::to_value(SearchOptions::default()).unwrap();
+        assert_eq!(json["ignoreCase"], true);
+        assert_eq!(json["algorithm"], "levenshtein");
+        let back: SearchOptions = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(back).unwrap(), json);
+
+        let results = search(
+            "aple".to_string(),
+            vec!["apple".to_string(), "Apple".to_string()],
+            Some(SearchOptions {
+                explain: Some(true),
+                ..opts
+            }),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&results).unwrap();
+        assert!(json.contains(r#""queryIndex":null"#));
+        assert!(json.contains(r#""maxLength":5"#));
+        let back: Vec<SearchResult> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back[0].item, results[0].item);
+        assert_eq!(back[0].score, results[0].score);
+        assert_eq!(back[0].explanation, results[0].explanation);
+    }
+
+    #[test]
+    fn test_search_ref_matches_search() {
+        let items = ["item10", "apple", "item2", "apply", "Apple pie"];
+        let owned: Vec<String> = items
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let variants = [
+            SearchOptions::default(),
+            SearchOptions {
+                limit: Some(Some(2.0)),
+                offset: Some(1),
+                threshold: Some(0.3),
+                ..Default::default()
+            },
+            SearchOptions {
+                natural_order: Some(true),
+                sort_keys: Some(vec![0.0, 1.0, 0.0, 0.0, 2.0]),
+                precision: Some(2),
+                ..Default::default()
+            },
+        ];
+
+        for opts in variants {
+            for query in ["apple", "item", ""] {
+                let borrowed = search_ref(query, &items, &opts).unwrap();
+                let expected: Vec<(&str, f64, usize)> = search(
+                    query.to_string(),
+                    owned.clone(),
+                    Some(opts.clone()),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|r| (items[r.index as usize], r.score, r.index as usize))
+                .collect();
+                assert_eq!(borrowed, expected, "query {query:?}");
+            }
+        }
+
+        let strict = SearchOptions {
+            weights: Some(vec![1.0]),
+            ..Default::default()
+        };
+        assert!(search_ref("apple", &items, &strict).is_err());
+    }
+
+    #[test]
+    fn test_keep_letters_tells_nino_from_nino() {
+        let score = |keep: Option<&str>| {
+            let opts = SearchOptions {
+                keep_letters: keep.map(str::to_string),
+                ..Default::default()
+            };
+            fuzzy_with("NIÑO", "nino", &opts).unwrap()
+        };
+        assert_eq!(score(None), 1.0);
+        assert!(score(Some("ñ")) < 1.0);
+
+        assert_eq!(keep_letters(Some("ñß")), HashSet::from(['ñ', 'Ñ', 'ß']));
+    }
+
+    #[test]
+    fn test_limit_distinguishes_zero_null_and_missing() {
+        let items: Vec<String> = (0..30)
+            .map(|i| format!("item{i}"))
+            .collect();
+        let count = |limit| {
+            let options = SearchOptions {
+                limit,
+                ..Default::default()
+            };
+            search("item".to_string(), items.clone(), Some(options))
+                .map(|results| results.len())
+        };
+
+        assert_eq!(count(None).unwrap(), 10);
+        assert_eq!(count(Some(None)).unwrap(), 30);
+        assert_eq!(count(Some(Some(0.0))).unwrap(), 0);
+        assert_eq!(count(Some(Some(2.7))).unwrap(), 2);
+        assert_eq!(count(Some(Some(f64::INFINITY))).unwrap(), 30);
+        assert!(count(Some(Some(-1.0))).is_err());
+        assert!(count(Some(Some(f64::NAN))).is_err());
+    }
+
+    #[test]
+    fn test_include_ties_keeps_the_whole_boundary_group() {
+        let items: Vec<String> = [
+            "apple", "apply", "appla", "grape", "applo", "maple", "apple",
+        ]
+        .map(String::from)
+        .to_vec();
+        let indices = |include_ties, parallel_threshold| {
+            let options = SearchOptions {
+                limit: Some(Some(2.0)),
+                include_ties: Some(include_ties),
+                parallel_threshold: Some(parallel_threshold),
+                threshold: Some(0.5),
+                ..Default::default()
+            };
+            search("apply".to_string(), items.clone(), Some(options))
+                .unwrap()
+                .iter()
+                .map(|r| r.index)
+                .collect::<Vec<_>>()
+        };
+
+        // "apply" scores 1.0; "apple" twice, "appla" and "applo" all 0.8.
+        assert_eq!(indices(false, u32::MAX), [1, 0]);
+        assert_eq!(indices(true, u32::MAX), [1, 0, 2, 4, 6]);
+        assert_eq!(indices(true, 0), [1, 0, 2, 4, 6]);
+
+        let mut searcher = Searcher::new(items.clone(), None).unwrap();
+        let options = SearchOptions {
+            limit: Some(Some(2.0)),
+            include_ties: Some(true),
+            threshold: Some(0.5),
+            ..Default::default()
+        };
+        let results = searcher
+            .query("apply".to_string(), Some(options))
+            .unwrap();
+        assert_eq!(results.len(), 5);
+    }
+
+    #[test]
+    fn test_explain_off_by_default() {
+        let results =
+            search("kitten".to_string(), vec!["sitting".to_string()], None)
+                .unwrap();
+        assert!(results[0].explanation.is_none());
+    }
 }
@@ -0,0 +1,23 @@
+use napi_derive::napi;
+
+use crate::{EditOp, SearchResult};
+
+/// The best match of [`crate::best_match_with_edits`], with the edits that
+/// turn the query into it.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface MatchWithEdits {
+///   result: SearchResult; // same as `bestMatch` would return
+///   edits: EditOp[];      // normalized query → normalized item
+/// }
+/// ```
+#[napi(object)]
+pub struct MatchWithEdits {
+    /// The best match, as [`crate::best_match`] returns it.
+    pub result: SearchResult,
+
+    /// A shortest Levenshtein edit script from the normalized query to the
+    /// normalized item, in order of position.
+    pub edits: Vec<EditOp>,
+}
@@ -1,58 +1,792 @@
+use caseless::{default_case_fold_str, Caseless};
+use memchr::{memchr2, memchr3, memmem};
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::char::{
+    canonical_combining_class, compose, decompose_canonical,
+    decompose_compatible,
+};
 use unicode_normalization::UnicodeNormalization;
+use unicode_security::skeleton;
+
+use crate::{NormalizationForm, NormalizationOptions};
 
 /// Normalizes a string by:
 /// - Removing diacritical marks (accents).
-/// - Optionally converting to lowercase.
+/// - Optionally folding case.
 /// - Normalizing whitespace (multiple spaces, tabs, newlines → single space).
 ///
 /// This function uses **Unicode NFD decomposition** to separate base characters
-/// from diacritics and then filters out combining marks.
+/// from diacritics and then filters out combining marks. Case-insensitive
+/// comparison uses full Unicode case folding rather than `to_lowercase`, so
+/// `"ß"` and `"SS"` both become `"ss"`.
 ///
 /// # Arguments
 ///
 /// * `input` - The input string to normalize.
-/// * `to_lowercase` - If `true`, the result is converted to lowercase.
+/// * `to_lowercase` - If `true`, the result is case folded. This only affects
+///   case: diacritics are stripped either way.
+///
+/// # Performance
+///
+/// Pure-ASCII input, which NFD leaves unchanged, skips decomposition
+/// entirely and is case folded in place, leaving only whitespace
+/// collapsing to do.
 ///
 /// # Examples
 ///
 /// ```
-/// use mylib::normalize_string;
+/// use fast_fuzzy_rs::normalize_string;
 ///
 /// assert_eq!(normalize_string("Café", true), "cafe");
+/// assert_eq!(normalize_string("Café", false), "Cafe");
 /// assert_eq!(normalize_string("  Hello\tWorld\n", false), "Hello World");
+/// assert_eq!(normalize_string("STRASSE", true), normalize_string("straße", true));
 /// ```
 pub fn normalize_string(input: &str, to_lowercase: bool) -> String {
-    let mut result = input
-        .nfd()
-        .filter(|c| !is_combining_mark(*c))
-        .collect::<String>();
+    normalize_with(
+        input,
+        &NormalizationOptions {
+            case_fold: to_lowercase,
+            ..Default::default()
+        },
+    )
+}
+
+/// A faster [`normalize_string`] for input known to be ASCII: lowercases
+/// ASCII letters if `to_lowercase` is set and collapses whitespace, without
+/// any of the Unicode steps.
+///
+/// The result is unspecified for non-ASCII input. Currently such characters
+/// are kept as they are, neither case folded nor stripped of diacritics, so
+/// `"Café"` does *not* match `"cafe"`; use [`normalize_string`] whenever the
+/// input may contain them.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{normalize_ascii, normalize_string};
+///
+/// assert_eq!(normalize_ascii("  Hello\tWORLD\n", true), "hello world");
+/// assert_eq!(normalize_ascii("Hello  World", false), "Hello World");
+/// assert_eq!(
+///     normalize_ascii("Part No. 42", true),
+///     normalize_string("Part No. 42", true)
+/// );
+/// ```
+pub fn normalize_ascii(input: &str, to_lowercase: bool) -> String {
+    let mut result = if is_collapsed_ascii(input.as_bytes()) {
+        input.to_string()
+    } else {
+        let mut collapsed = String::with_capacity(input.len());
+        for word in input
+            .split(|c: char| c.is_ascii_whitespace() || c == '\x0B')
+            .filter(|word| !word.is_empty())
+        {
+            if !collapsed.is_empty() {
+                collapsed.push(' ');
+            }
+            collapsed.push_str(word);
+        }
+        collapsed
+    };
 
     if to_lowercase {
-        result = result.to_lowercase();
+        result.make_ascii_lowercase();
     }
+    result
+}
 
-    normalize_whitespace(&result)
+/// Normalizes a string according to `options`.
+///
+/// Steps run in this order: invisible character removal, Turkish I mapping,
+/// confusable folding, ligature expansion, superscript folding, Unicode
+/// normalization to `form`
+/// and diacritic stripping (both skipping `keep_letters`), case folding,
+/// alphanumeric filtering, ignored characters, digit folding, whitespace
+/// collapsing or trimming, stopword removal, stemming. The Turkish mapping
+/// runs first because NFD would otherwise split `'İ'` into `'I'` plus a
+/// combining dot.
+///
+/// Case folding comes after diacritic stripping so that it sees base
+/// letters: `'Σ'`, final `'ς'` and `'σ'` all fold to `'σ'` whatever accent
+/// they carried, and `'ß'` folds to `"ss"`. Under a decomposed form folding
+/// can't bring marks back, since every precomposed letter was already split
+/// up. With NFC, NFKC or no form, a few letters fold to a base letter plus
+/// marks (`'ΐ'`, or `'İ'` to `"i\u{307}"`); those are composed again under
+/// the composed forms and, with `strip_diacritics`, stripped like any other
+/// marks.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{normalize_with, NormalizationOptions};
+///
+/// let turkish = NormalizationOptions {
+///     turkish_i: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(normalize_with("DİYARBAKIR", &turkish), "diyarbakır");
+/// ```
+pub fn normalize_with(input: &str, options: &NormalizationOptions) -> String {
+    let visible;
+    let input = if options.strip_control && !is_visible_ascii(input) {
+        visible = input
+            .chars()
+            .filter(|&c| !is_invisible(c))
+            .collect::<String>();
+        visible.as_str()
+    } else {
+        input
+    };
+
+    let mut result = if options.case_fold && options.turkish_i {
+        input
+            .chars()
+            .map(turkish_fold_i)
+            .collect::<String>()
+    } else {
+        input.to_string()
+    };
+
+    if options.fold_confusables && !result.is_ascii() {
+        let mut folded = String::with_capacity(result.len());
+        for c in result.chars() {
+            fold_confusable(c, |p| folded.push(p));
+        }
+        result = folded;
+    }
+
+    if options.expand_ligatures && !result.is_ascii() {
+        let mut expanded = String::with_capacity(result.len());
+        for c in result.chars() {
+            expand_ligature(c, |l| expanded.push(l));
+        }
+        result = expanded;
+    }
+
+    if options.fold_superscripts && !result.is_ascii() {
+        let mut folded = String::with_capacity(result.len());
+        for c in result.chars() {
+            fold_superscript(c, |s| folded.push(s));
+        }
+        result = folded;
+    }
+
+    // ASCII is unchanged by every normalization form and has no marks to
+    // strip, so skip decomposing and recollecting it.
+    result = if result.is_ascii() {
+        result
+    } else if options.keep_letters.is_empty() {
+        apply_form(&result, options)
+    } else {
+        // Kept letters are starters, so normalizing the text between them
+        // separately gives the same result as normalizing it all at once.
+        let keep = &options.keep_letters;
+        let mut kept = String::with_capacity(result.len());
+        for segment in result.split_inclusive(|c| keep.contains(&c)) {
+            let letter = segment
+                .chars()
+                .next_back()
+                .filter(|c| keep.contains(c));
+            let rest =
+                &segment[..segment.len() - letter.map_or(0, char::len_utf8)];
+            kept.push_str(&apply_form(rest, options));
+            kept.extend(letter);
+        }
+        kept
+    };
+
+    if options.case_fold {
+        // Full case folding only differs from lowercasing outside ASCII.
+        if result.is_ascii() {
+            result.make_ascii_lowercase();
+        } else {
+            let folded = default_case_fold_str(&result);
+            result = if !is_decomposed(options.form)
+                && folded.chars().any(is_combining_mark)
+            {
+                let mut refolded = String::with_capacity(folded.len());
+                for c in result.chars() {
+                    fold_case(c, options, |f| refolded.push(f));
+                }
+                refolded
+            } else {
+                folded
+            };
+        }
+    }
+
+    if options.alphanumeric_only {
+        result.retain(char::is_alphanumeric);
+    }
+
+    if !options.ignore_chars.is_empty() {
+        result.retain(|c| !options.ignore_chars.contains(&c));
+    }
+
+    if options.fold_digits {
+        result = result.chars().map(fold_digit).collect();
+    }
+
+    if options.collapse_whitespace {
+        result = normalize_whitespace(&result);
+    } else if options.trim {
+        result = result.trim().to_string();
+    }
+
+    if has_token_filters(options) {
+        result = result
+            .split_whitespace()
+            .filter(|token| !options.stopwords.contains(*token))
+            .map(|token| stem_token(token, options))
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    result
 }
 
-/// Internal helper for detecting whether a character is a Unicode combining mark
-fn is_combining_mark(c: char) -> bool {
+/// Same as [`normalize_with`], but also returns, for each `char` of the
+/// normalized string, the byte offset in `input` of the character it came
+/// from.
+///
+/// This lets callers score against the normalized form while highlighting in
+/// the original string, even after diacritics were stripped or whitespace was
+/// collapsed. A collapsed whitespace run maps to its first character, and
+/// characters that expand when folded (e.g. `'ß'` → `"ss"`) map every
+/// resulting `char` to the same offset.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::{normalize_with_map, NormalizationOptions};
+///
+/// let (normalized, map) =
+///     normalize_with_map("Crème  brûlée", &NormalizationOptions::default());
+/// assert_eq!(normalized, "creme brulee");
+/// assert_eq!(map[2], 2); // 'e' came from 'è' at byte 2
+/// assert_eq!(map[5], 6); // the space came from the first of two spaces
+/// ```
+pub fn normalize_with_map(
+    input: &str,
+    options: &NormalizationOptions,
+) -> (String, Vec<usize>) {
+    let mut chars: Vec<(char, usize)> = Vec::with_capacity(input.len());
+
+    for (offset, c) in input.char_indices() {
+        if options.strip_control && is_invisible(c) {
+            continue;
+        }
+        let c = if options.case_fold && options.turkish_i {
+            turkish_fold_i(c)
+        } else {
+            c
+        };
+
+        let mut push = |c: char| match options.form {
+            _ if options.keep_letters.contains(&c) => chars.push((c, offset)),
+            Some(NormalizationForm::Nfc | NormalizationForm::Nfd) => {
+                decompose_canonical(c, |d| chars.push((d, offset)));
+            }
+            Some(NormalizationForm::Nfkc | NormalizationForm::Nfkd) => {
+                decompose_compatible(c, |d| chars.push((d, offset)));
+            }
+            None => chars.push((c, offset)),
+        };
+
+        let mut unscript = |c: char| {
+            if options.fold_superscripts {
+                fold_superscript(c, &mut push);
+            } else {
+                push(c);
+            }
+        };
+
+        let mut expand = |c: char| {
+            if options.expand_ligatures {
+                expand_ligature(c, &mut unscript);
+            } else {
+                unscript(c);
+            }
+        };
+
+        if options.fold_confusables {
+            fold_confusable(c, expand);
+        } else {
+            expand(c);
+        }
+    }
+
+    if options.form.is_some() {
+        canonical_reorder(&mut chars);
+    }
+
+    if matches!(
+        options.form,
+        Some(NormalizationForm::Nfc | NormalizationForm::Nfkc)
+    ) {
+        chars = canonical_compose(chars);
+    }
+
+    if options.strip_diacritics {
+        let mut keep = keeps_unmarked(options.latin_diacritics_only);
+        chars.retain(|&(c, _)| keep(c));
+    }
+
+    if options.case_fold {
+        let mut folded = Vec::with_capacity(chars.len());
+        for (c, offset) in chars {
+            fold_case(c, options, |f| folded.push((f, offset)));
+        }
+        chars = folded;
+    }
+
+    if options.alphanumeric_only {
+        chars.retain(|(c, _)| c.is_alphanumeric());
+    }
+
+    if !options.ignore_chars.is_empty() {
+        chars.retain(|(c, _)| !options.ignore_chars.contains(c));
+    }
+
+    if options.fold_digits {
+        for (c, _) in &mut chars {
+            *c = fold_digit(*c);
+        }
+    }
+
+    if options.collapse_whitespace {
+        chars = collapse_whitespace_mapped(chars);
+    } else if options.trim {
+        let end = chars
+            .iter()
+            .rposition(|(c, _)| !c.is_whitespace())
+            .map_or(0, |last| last + 1);
+        chars.truncate(end);
+        let start = chars
+            .iter()
+            .position(|(c, _)| !c.is_whitespace())
+            .unwrap_or(end);
+        chars.drain(..start);
+    }
+
+    if has_token_filters(options) {
+        chars = filter_tokens_mapped(chars, options);
+    }
+
+    chars.into_iter().unzip()
+}
+
+/// Internal helper mirroring the stopword/stemming step of
+/// [`normalize_with`] while keeping offsets. The `n`th char of a stemmed
+/// token maps to the `n`th char of the original token (or its last char once
+/// the stem is longer).
+fn filter_tokens_mapped(
+    chars: Vec<(char, usize)>,
+    options: &NormalizationOptions,
+) -> Vec<(char, usize)> {
+    let mut result = Vec::with_capacity(chars.len());
+
+    for token in chars.split(|(c, _)| c.is_whitespace()) {
+        if token.is_empty() {
+            continue;
+        }
+
+        let text: String = token.iter().map(|(c, _)| *c).collect();
+        if options.stopwords.contains(&text) {
+            continue;
+        }
+
+        if let Some(&(_, space_offset)) = result.last().map(|_| &token[0]) {
+            result.push((' ', space_offset));
+        }
+        let stemmed = stem_token(&text, options);
+        for (i, c) in stemmed.chars().enumerate() {
+            result.push((c, token[i.min(token.len() - 1)].1));
+        }
+    }
+
+    result
+}
+
+/// Internal helper reporting whether the token-level steps have any work.
+fn has_token_filters(options: &NormalizationOptions) -> bool {
+    (options.stem && cfg!(feature = "stemming"))
+        || !options.stopwords.is_empty()
+}
+
+/// Internal helper reducing a single token to its English stem.
+#[cfg(feature = "stemming")]
+fn stem_token<'a>(
+    token: &'a str,
+    options: &NormalizationOptions,
+) -> std::borrow::Cow<'a, str> {
+    use rust_stemmers::{Algorithm, Stemmer};
+
+    if options.stem {
+        Stemmer::create(Algorithm::English).stem(token)
+    } else {
+        token.into()
+    }
+}
+
+/// Internal helper standing in for stemming when the `stemming` feature is
+/// disabled.
+#[cfg(not(feature = "stemming"))]
+fn stem_token<'a>(
+    token: &'a str,
+    _options: &NormalizationOptions,
+) -> std::borrow::Cow<'a, str> {
+    token.into()
+}
+
+/// The letter `c` is built on, without its diacritics: `'e'` for `'é'` and
+/// `'ê'`. Characters without a canonical decomposition are their own base.
+pub(crate) fn base_letter(c: char) -> char {
+    let mut base = c;
+    decompose_canonical(c, |part| {
+        if base == c && !is_combining_mark(part) {
+            base = part;
+        }
+    });
+    base
+}
+
+/// Internal helper applying the normalization form and diacritic stripping
+/// steps of [`normalize_with`] to `input`.
+fn apply_form(input: &str, options: &NormalizationOptions) -> String {
+    let strip = options.strip_diacritics;
+    let latin = options.latin_diacritics_only;
+    match options.form {
+        Some(NormalizationForm::Nfc) => strip_marks(input.nfc(), strip, latin),
+        Some(NormalizationForm::Nfd) => strip_marks(input.nfd(), strip, latin),
+        Some(NormalizationForm::Nfkc) => {
+            strip_marks(input.nfkc(), strip, latin)
+        }
+        Some(NormalizationForm::Nfkd) => {
+            strip_marks(input.nfkd(), strip, latin)
+        }
+        None => strip_marks(input.chars(), strip, latin),
+    }
+}
+
+/// Internal helper emitting the full case folding of `c`. Under a form other
+/// than NFD or NFKD, marks that folding splits off a letter (other than a
+/// kept one) are composed back or stripped as `form` and `strip_diacritics`
+/// would have treated them in the input.
+fn fold_case(c: char, options: &NormalizationOptions, push: impl FnMut(char)) {
+    let folded = std::iter::once(c).default_case_fold();
+    if is_decomposed(options.form) || options.keep_letters.contains(&c) {
+        folded.for_each(push);
+        return;
+    }
+
+    let folded: String = folded.collect();
+    if !folded.chars().any(is_combining_mark) {
+        folded.chars().for_each(push);
+        return;
+    }
+    let (strip, latin) =
+        (options.strip_diacritics, options.latin_diacritics_only);
+    let refolded = match options.form {
+        Some(NormalizationForm::Nfc | NormalizationForm::Nfkc) => {
+            strip_marks(folded.nfc(), strip, latin)
+        }
+        _ => strip_marks(folded.chars(), strip, latin),
+    };
+    refolded.chars().for_each(push);
+}
+
+/// Internal helper checking whether `form` splits precomposed letters.
+fn is_decomposed(form: Option<NormalizationForm>) -> bool {
+    matches!(form, Some(NormalizationForm::Nfd | NormalizationForm::Nfkd))
+}
+
+/// Internal helper collecting normalized chars, dropping combining marks when
+/// `strip` is set (only those after a Latin letter with `latin_only`).
+fn strip_marks(
+    chars: impl Iterator<Item = char>,
+    strip: bool,
+    latin_only: bool,
+) -> String {
+    if strip {
+        let mut keep = keeps_unmarked(latin_only);
+        chars.filter(|&c| keep(c)).collect()
+    } else {
+        chars.collect()
+    }
+}
+
+/// Internal helper returning a filter over a stream of chars that drops
+/// combining marks or, with `latin_only`, only the marks attached to a Latin
+/// base letter.
+fn keeps_unmarked(latin_only: bool) -> impl FnMut(char) -> bool {
+    let mut after_latin = false;
+    move |c| {
+        if !is_combining_mark(c) {
+            after_latin = is_latin(c);
+            true
+        } else {
+            latin_only && !after_latin
+        }
+    }
+}
+
+/// Internal helper for detecting letters of the Latin script, including the
+/// extended and full-width blocks. Digits and punctuation are shared by every
+/// script and don't count.
+fn is_latin(c: char) -> bool {
+    matches!(
+        c,
+        'A'..='Z'
+            | 'a'..='z'
+            | 'ª'
+            | 'º'
+            | '\u{C0}'..='\u{D6}'
+            | '\u{D8}'..='\u{F6}'
+            | '\u{F8}'..='\u{2AF}'
+            | '\u{1D00}'..='\u{1D7F}'
+            | '\u{1E00}'..='\u{1EFF}'
+            | '\u{2C60}'..='\u{2C7F}'
+            | '\u{A720}'..='\u{A7FF}'
+            | '\u{AB30}'..='\u{AB6F}'
+            | '\u{FB00}'..='\u{FB06}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}'
+    )
+}
+
+/// Internal helper applying the canonical ordering step of NFD: each run of
+/// non-starters is stably sorted by canonical combining class.
+fn canonical_reorder(chars: &mut [(char, usize)]) {
+    let mut start = 0;
+    while start < chars.len() {
+        if canonical_combining_class(chars[start].0) == 0 {
+            start += 1;
+            continue;
+        }
+
+        let len = chars[start..]
+            .iter()
+            .take_while(|(c, _)| canonical_combining_class(*c) != 0)
+            .count();
+        chars[start..start + len]
+            .sort_by_key(|(c, _)| canonical_combining_class(*c));
+        start += len;
+    }
+}
+
+/// Internal helper applying the canonical composition step of NFC/NFKC to
+/// decomposed, reordered chars. A composed character keeps the offset of its
+/// starter.
+fn canonical_compose(chars: Vec<(char, usize)>) -> Vec<(char, usize)> {
+    let mut result: Vec<(char, usize)> = Vec::with_capacity(chars.len());
+    let mut starter: Option<usize> = None;
+    let mut last_class = 0;
+
+    for (c, offset) in chars {
+        let class = canonical_combining_class(c);
+
+        // A mark can join the last starter unless a mark of the same or
+        // higher class sits between them (or, for starters, anything does).
+        let unblocked = match starter {
+            Some(i) if result.len() == i + 1 => true,
+            Some(_) => last_class != 0 && last_class < class,
+            None => false,
+        };
+        if let Some(i) = starter.filter(|_| unblocked) {
+            if let Some(composed) = compose(result[i].0, c) {
+                result[i].0 = composed;
+                continue;
+            }
+        }
+
+        if class == 0 {
+            starter = Some(result.len());
+        }
+        last_class = class;
+        result.push((c, offset));
+    }
+
+    result
+}
+
+/// Internal helper mirroring [`normalize_whitespace`] while keeping offsets.
+fn collapse_whitespace_mapped(chars: Vec<(char, usize)>) -> Vec<(char, usize)> {
+    let mut result = Vec::with_capacity(chars.len());
+    let mut pending_space = None;
+
+    for (c, offset) in chars {
+        if c.is_whitespace() {
+            pending_space.get_or_insert(offset);
+            continue;
+        }
+
+        if let Some(space_offset) = pending_space.take() {
+            if !result.is_empty() {
+                result.push((' ', space_offset));
+            }
+        }
+        result.push((c, offset));
+    }
+
+    result
+}
+
+/// Internal helper detecting control characters other than whitespace and
+/// the zero-width characters (spaces, joiners, word joiner and the byte
+/// order mark), which render as nothing but still count when comparing.
+fn is_invisible(c: char) -> bool {
+    (c.is_control() && !c.is_whitespace())
+        || matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')
+}
+
+/// Internal helper detecting ASCII input without invisible characters, which
+/// `strip_control` leaves unchanged.
+fn is_visible_ascii(input: &str) -> bool {
+    input
+        .bytes()
+        .all(|b| b.is_ascii() && !is_invisible(b as char))
+}
+
+/// Internal helper emitting the confusable skeleton of a non-ASCII `c`, or
+/// `c` itself when it is ASCII.
+fn fold_confusable(c: char, mut push: impl FnMut(char)) {
+    if c.is_ascii() {
+        push(c);
+        return;
+    }
+
+    let mut buf = [0; 4];
+    for p in skeleton(c.encode_utf8(&mut buf)) {
+        push(p);
+    }
+}
+
+/// Internal helper emitting the letters of a ligature `c`, or `c` itself
+/// when it isn't one. `æ` and `œ` have no decomposition at all, so they are
+/// spelled out by hand; the compatibility ligatures are expanded with NFKD.
+fn expand_ligature(c: char, mut push: impl FnMut(char)) {
+    let letters = match c {
+        'æ' => "ae",
+        'Æ' => "AE",
+        'œ' => "oe",
+        'Œ' => "OE",
+        // ﬀ to ﬆ, Ĳ ĳ, and the Ǆ, Ǉ, Ǌ and Ǳ digraph families.
+        '\u{FB00}'..='\u{FB06}'
+        | '\u{0132}'..='\u{0133}'
+        | '\u{01C4}'..='\u{01CC}'
+        | '\u{01F1}'..='\u{01F3}' => {
+            return decompose_compatible(c, push);
+        }
+        _ => return push(c),
+    };
+    letters.chars().for_each(push);
+}
+
+/// Internal helper emitting the compatibility decomposition of a
+/// superscript, subscript or vulgar fraction `c`, or `c` itself when it is
+/// none of those. The fraction slash `'⁄'` that NFKD puts in fractions is
+/// spelled as an ASCII `'/'`.
+fn fold_superscript(c: char, mut push: impl FnMut(char)) {
+    let mut unslash = |d| push(if d == '\u{2044}' { '/' } else { d });
+    match c {
+        // ª ² ³ ¹ º, the modifier letters, the phonetic extensions, the
+        // Superscripts and Subscripts block, the Number Forms fractions and
+        // the Cyrillic and Latin modifier letters. NFKD leaves the other
+        // characters in these ranges as they are.
+        '\u{00AA}'
+        | '\u{00B2}'..='\u{00B3}'
+        | '\u{00B9}'..='\u{00BE}'
+        | '\u{02B0}'..='\u{02B8}'
+        | '\u{02E0}'..='\u{02E4}'
+        | '\u{1D2C}'..='\u{1D6A}'
+        | '\u{1D78}'
+        | '\u{1D9B}'..='\u{1DBF}'
+        | '\u{2044}'
+        | '\u{2070}'..='\u{209C}'
+        | '\u{2150}'..='\u{215F}'
+        | '\u{2189}'
+        | '\u{2C7C}'..='\u{2C7D}'
+        | '\u{A69C}'..='\u{A69D}'
+        | '\u{A770}'
+        | '\u{A7F2}'..='\u{A7F9}'
+        | '\u{AB5C}'..='\u{AB5F}'
+        | '\u{AB69}'
+        | '\u{10780}'..='\u{107BA}' => decompose_compatible(c, unslash),
+        _ => unslash(c),
+    }
+}
+
+/// Internal helper replacing an ASCII digit with the `'0'` placeholder.
+fn fold_digit(c: char) -> char {
+    if c.is_ascii_digit() {
+        '0'
+    } else {
+        c
+    }
+}
+
+/// Internal helper applying the Turkic case folding rules for the letter I.
+fn turkish_fold_i(c: char) -> char {
     match c {
-        '\u{0300}'..='\u{036F}' | // Combining Diacritical Marks
-        '\u{1AB0}'..='\u{1AFF}' | // Combining Diacritical Marks Extended
-        '\u{1DC0}'..='\u{1DFF}' | // Combining Diacritical Marks Supplement
-        '\u{20D0}'..='\u{20FF}' | // Combining Diacritical Marks for Symbols
-        '\u{FE20}'..='\u{FE2F}'   // Combining Half Marks
-        => true,
-        _ => false,
+        'I' => 'ı',
+        'İ' => 'i',
+        _ => c,
     }
 }
 
+/// Internal helper for detecting whether a character is a diacritic-like
+/// combining mark: General_Category `Mn` (nonspacing) or `Me` (enclosing).
+///
+/// This covers the Latin diacritic blocks as well as script-specific marks
+/// such as the Cyrillic titlo, Greek ypogegrammeni, Hebrew points and Arabic
+/// harakat. Spacing marks (`Mc`) are kept: in Indic and other Brahmic scripts
+/// they are vowel signs that change the word, not optional accents.
+fn is_combining_mark(c: char) -> bool {
+    // No ASCII character is a mark, so skip the table lookup for the
+    // common case.
+    if c.is_ascii() {
+        return false;
+    }
+
+    matches!(
+        get_general_category(c),
+        GeneralCategory::NonspacingMark | GeneralCategory::EnclosingMark
+    )
+}
+
 /// Internal helper for collapsing multiple whitespace characters into a single space.
 fn normalize_whitespace(input: &str) -> String {
-    input
-        .split_whitespace()
-        .collect::<Vec<&str>>()
-        .join(" ")
+    if is_collapsed_ascii(input.as_bytes()) {
+        return input.to_string();
+    }
+
+    let mut result = String::with_capacity(input.len());
+    for word in input.split_whitespace() {
+        if !result.is_empty() {
+            result.push(' ');
+        }
+        result.push_str(word);
+    }
+    result
+}
+
+/// Internal helper detecting ASCII input whose whitespace is already
+/// collapsed: no leading/trailing whitespace, no whitespace other than single
+/// spaces. Such input is returned unchanged by [`normalize_whitespace`], and
+/// the `memchr` searches below are much cheaper than splitting it.
+fn is_collapsed_ascii(bytes: &[u8]) -> bool {
+    match (bytes.first(), bytes.last()) {
+        (Some(b' '), _) | (_, Some(b' ')) => return false,
+        (None, _) => return true,
+        _ => {}
+    }
+
+    bytes.is_ascii()
+        && memchr3(b'\t', b'\n', b'\r', bytes).is_none()
+        && memchr2(0x0B, 0x0C, bytes).is_none()
+        && memmem::find(bytes, b"  ").is_none()
 }
 
 #[cfg(test)]
@@ -65,4 +799,588 @@ mod tests {
         assert_eq!(normalize_string("naïve", true), "naive");
         assert_eq!(normalize_string("résumé", true), "resume");
     }
+
+    #[test]
+    fn test_ascii_fast_path_matches_unicode_path() {
+        let inputs = [
+            "",
+            "Hello World",
+            "  Hello\t\tWORLD \n",
+            "a\x0bb\x0cc\rd",
+            "MiXeD   Case\r\nLines  ",
+            "ABC-123 xyz_!?",
+        ];
+        for input in inputs {
+            for case_fold in [true, false] {
+                let mut expected: String = input.nfd().collect();
+                if case_fold {
+                    expected = default_case_fold_str(&expected);
+                }
+                expected = expected
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let actual = normalize_string(input, case_fold);
+                assert_eq!(actual.as_bytes(), expected.as_bytes(), "{input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_letter_drops_diacritics() {
+        assert_eq!(base_letter('é'), 'e');
+        assert_eq!(base_letter('Ǖ'), 'U');
+        assert_eq!(base_letter('e'), 'e');
+        assert_eq!(base_letter('ß'), 'ß');
+    }
+
+    #[test]
+    fn test_accents_stripped_without_case_folding() {
+        assert_eq!(normalize_string("Café", false), "Cafe");
+        assert_eq!(normalize_string("ÉCOLE Naïve", false), "ECOLE Naive");
+    }
+
+    #[test]
+    fn test_case_folding() {
+        assert_eq!(normalize_string("STRASSE", true), "strasse");
+        assert_eq!(normalize_string("straße", true), "strasse");
+        assert_eq!(normalize_string("İstanbul", true), "istanbul");
+    }
+
+    #[test]
+    fn test_turkish_i() {
+        let fold_only = NormalizationOptions {
+            strip_diacritics: false,
+            collapse_whitespace: false,
+            ..Default::default()
+        };
+        let turkish = NormalizationOptions {
+            turkish_i: true,
+            ..fold_only.clone()
+        };
+
+        assert_ne!(normalize_with("İstanbul", &fold_only), "istanbul");
+        assert_eq!(normalize_with("İstanbul", &turkish), "istanbul");
+        assert_eq!(normalize_with("ISPARTA", &turkish), "ısparta");
+    }
+
+    #[test]
+    fn test_map_matches_normalize_with() {
+        let inputs = [
+            "Café  au\tlait ",
+            "  Straße\n",
+            "e\u{0323}\u{0302}",
+            "İstanbul",
+            "naïve résumé",
+            "ＡＢＣ１２３ ｶﾞｷﾞ ﬁ",
+            "e\u{0301}\u{0323}x\u{0308}\u{0301}",
+            "\u{0430}pple Ρаypаl",
+            "\u{0301}ἄλφα café שָׁלוֹם",
+            "NIÑO niño nin\u{0303}o",
+            "\u{FEFF}zero\u{200B}width\u{0000}",
+            "m² H₂O ½ ₍ⁱ₎ é²",
+        ];
+        let options = [
+            NormalizationOptions::default(),
+            NormalizationOptions {
+                turkish_i: true,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                latin_diacritics_only: true,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                keep_letters: ['ñ', 'Ñ', 'é'].into(),
+                ..Default::default()
+            },
+            NormalizationOptions {
+                strip_diacritics: false,
+                collapse_whitespace: false,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                collapse_whitespace: false,
+                trim: true,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                strip_control: true,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                alphanumeric_only: true,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                form: Some(NormalizationForm::Nfkd),
+                ..Default::default()
+            },
+            NormalizationOptions {
+                form: Some(NormalizationForm::Nfkc),
+                strip_diacritics: false,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                form: Some(NormalizationForm::Nfc),
+                ..Default::default()
+            },
+            NormalizationOptions {
+                form: None,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                ignore_chars: [' ', '-', 'e'].into(),
+                ..Default::default()
+            },
+            NormalizationOptions {
+                fold_confusables: true,
+                ..Default::default()
+            },
+            NormalizationOptions {
+                fold_superscripts: true,
+                form: Some(NormalizationForm::Nfc),
+                ..Default::default()
+            },
+        ];
+
+        for input in inputs {
+            for options in &options {
+                let (normalized, map) = normalize_with_map(input, options);
+                assert_eq!(normalized, normalize_with(input, options));
+                assert_eq!(map.len(), normalized.chars().count());
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_points_into_original() {
+        let input = "  Café   Olé";
+        let (normalized, map) =
+            normalize_with_map(input, &NormalizationOptions::default());
+
+        assert_eq!(normalized, "cafe ole");
+        assert_eq!(map, vec![2, 3, 4, 5, 7, 10, 11, 12]);
+        assert_eq!(&input[map[3]..map[3] + 'é'.len_utf8()], "é");
+        assert_eq!(&input[map[4]..map[4] + 1], " ");
+    }
+
+    #[test]
+    fn test_whitespace_fast_path_is_identical() {
+        let inputs = [
+            "",
+            " ",
+            "apple",
+            "apple pie",
+            "apple  pie",
+            " apple",
+            "apple ",
+            "apple\tpie",
+            "apple\u{0B}pie",
+            "apple\u{0C}pie",
+            "apple\r\npie",
+            "café au lait",
+            "café\u{00A0}au lait",
+        ];
+
+        for input in inputs {
+            let expected = input
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ");
+            assert_eq!(normalize_whitespace(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "stemming")]
+    fn test_stemming() {
+        let options = NormalizationOptions {
+            stem: true,
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_with("running", &options), "run");
+        assert_eq!(normalize_with("run", &options), "run");
+        assert_eq!(
+            normalize_with("Running  Shoes", &options),
+            normalize_with("run shoe", &options)
+        );
+    }
+
+    #[test]
+    fn test_stopwords() {
+        let options = NormalizationOptions {
+            stopwords: ["the", "of"].map(String::from).into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            normalize_with("The Lord of the Rings", &options),
+            "lord rings"
+        );
+
+        let (normalized, map) = normalize_with_map(" The  Lord", &options);
+        assert_eq!(normalized, "lord");
+        assert_eq!(map, vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_alphanumeric_only_matches_serial_numbers() {
+        let options = NormalizationOptions {
+            alphanumeric_only: true,
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_with("ABC-123", &options), "abc123");
+        assert_eq!(normalize_with("abc 123", &options), "abc123");
+        assert_eq!(normalize_with("SN: Ø-42/7é", &options), "snø427e");
+
+        let (normalized, map) = normalize_with_map("AB-12", &options);
+        assert_eq!(normalized, "ab12");
+        assert_eq!(map, vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_script_specific_marks_are_stripped() {
+        let options = NormalizationOptions::default();
+
+        // Serbian Cyrillic has no marks; Russian й decomposes to и + breve.
+        assert_eq!(normalize_with("Златибор", &options), "златибор");
+        assert_eq!(normalize_with("й", &options), "и");
+        // Cyrillic titlo (U+0483) is outside the Latin diacritic blocks.
+        assert_eq!(normalize_with("б\u{0483}", &options), "б");
+        // Greek: breathing, accent and iota subscript (ypogegrammeni) are all
+        // stripped; case folding then turns final sigma into σ.
+        assert_eq!(normalize_with("ᾅδης", &options), "αδησ");
+        // Arabic harakat (fatha, shadda) are stripped; letters are kept.
+        assert_eq!(normalize_with("الرياض", &options), "الرياض");
+        assert_eq!(normalize_with("مُحَمَّد", &options), "محمد");
+        // Enclosing marks (U+20DD combining enclosing circle) go too.
+        assert_eq!(normalize_with("a\u{20DD}", &options), "a");
+    }
+
+    #[test]
+    fn test_latin_diacritics_only_keeps_other_scripts() {
+        let latin_only = NormalizationOptions {
+            latin_diacritics_only: true,
+            ..Default::default()
+        };
+        let nfd = |s: &str| s.nfd().collect::<String>();
+
+        assert_eq!(normalize_with("Crème Brûlée", &latin_only), "creme brulee");
+        assert_eq!(normalize_with("Ḝ Ǖ", &latin_only), "e u");
+        // Hebrew points and Greek tonos survive, decomposed by NFD.
+        assert_eq!(normalize_with("שָׁלוֹם", &latin_only), nfd("שָׁלוֹם"));
+        assert_eq!(normalize_with("Ἀθῆναι", &latin_only), nfd("ἀθῆναι"));
+        assert_eq!(normalize_with("café ἄ", &latin_only), nfd("cafe ἄ"));
+
+        let everything = NormalizationOptions::default();
+        assert_eq!(normalize_with("שָׁלוֹם", &everything), "שלום");
+        assert_eq!(normalize_with("Ἀθῆναι", &everything), "αθηναι");
+    }
+
+    #[test]
+    fn test_keep_letters_are_not_decomposed() {
+        let spanish = NormalizationOptions {
+            keep_letters: ['ñ', 'Ñ'].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_with("niño", &spanish), "niño");
+        assert_eq!(normalize_with("NIÑO", &spanish), "niño");
+        assert_eq!(normalize_with("Señor Café", &spanish), "señor cafe");
+        assert_ne!(
+            normalize_with("niño", &spanish),
+            normalize_with("nino", &spanish)
+        );
+        assert_eq!(normalize_string("niño", true), "nino");
+    }
+
+    #[test]
+    fn test_spacing_marks_are_kept() {
+        // Devanagari vowel sign AA (U+093E) is a spacing mark.
+        let options = NormalizationOptions::default();
+        assert_eq!(normalize_with("का", &options), "का");
+    }
+
+    #[test]
+    fn test_compatibility_unifies_width_variants() {
+        let compatibility = NormalizationOptions {
+            form: Some(NormalizationForm::Nfkd),
+            ..Default::default()
+        };
+        let keep_accents = NormalizationOptions {
+            form: Some(NormalizationForm::Nfkc),
+            strip_diacritics: false,
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_with("ＡＢＣ１２３", &compatibility), "abc123");
+        assert_eq!(
+            normalize_with("ｶﾀｶﾅ", &compatibility),
+            normalize_with("カタカナ", &compatibility)
+        );
+        // NFD alone leaves both forms distinct.
+        let default = NormalizationOptions::default();
+        assert_eq!(normalize_with("ＡＢＣ１２３", &default), "ａｂｃ１２３");
+        assert_ne!(
+            normalize_with("ｶﾀｶﾅ", &default),
+            normalize_with("カタカナ", &default)
+        );
+
+        // Without stripping, NFKC keeps the voiced mark composed.
+        assert_eq!(normalize_with("ｶﾞ", &keep_accents), "ガ");
+        assert_eq!(normalize_with("Ｃａｆé", &keep_accents), "café");
+    }
+
+    #[test]
+    fn test_each_normalization_form() {
+        let form = |form, strip_diacritics| {
+            let options = NormalizationOptions {
+                form,
+                strip_diacritics,
+                case_fold: false,
+                ..Default::default()
+            };
+            normalize_with("Ｃafe\u{0301} ﬁ", &options)
+        };
+
+        assert_eq!(form(Some(NormalizationForm::Nfc), false), "Ｃafé ﬁ");
+        assert_eq!(
+            form(Some(NormalizationForm::Nfd), false),
+            "Ｃafe\u{0301} ﬁ"
+        );
+        assert_eq!(form(Some(NormalizationForm::Nfkc), false), "Café fi");
+        assert_eq!(
+            form(Some(NormalizationForm::Nfkd), false),
+            "Cafe\u{0301} fi"
+        );
+        assert_eq!(form(None, false), "Ｃafe\u{0301} ﬁ");
+
+        // Stripping only finds marks the chosen form leaves decomposed.
+        assert_eq!(form(Some(NormalizationForm::Nfd), true), "Ｃafe ﬁ");
+        assert_eq!(form(Some(NormalizationForm::Nfkd), true), "Cafe fi");
+        assert_eq!(form(Some(NormalizationForm::Nfc), true), "Ｃafé ﬁ");
+        assert_eq!(form(None, true), "Ｃafe ﬁ");
+    }
+
+    #[test]
+    fn test_ignore_chars_are_removed() {
+        let options = NormalizationOptions {
+            ignore_chars: ['-', '(', ')'].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_with("555-1234", &options), "5551234");
+        assert_eq!(normalize_with("(555) 12-34", &options), "555 1234");
+
+        let (normalized, map) = normalize_with_map("5-5", &options);
+        assert_eq!(normalized, "55");
+        assert_eq!(map, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_expand_ligatures_spells_out_letters() {
+        let options = NormalizationOptions {
+            expand_ligatures: true,
+            ..Default::default()
+        };
+        let expand = |input| normalize_with(input, &options);
+
+        assert_eq!(expand("encyclopædia"), "encyclopaedia");
+        assert_eq!(expand("ÆON"), "aeon");
+        assert_eq!(expand("œuvre"), "oeuvre");
+        assert_eq!(expand("Œdipus"), "oedipus");
+        assert_eq!(expand("\u{FB01}le"), "file");
+        assert_eq!(expand("\u{FB03}ne"), "ffine");
+        assert_eq!(expand("\u{0133}s"), "ijs");
+        // Other compatibility characters keep their form.
+        assert_eq!(expand("\u{FF21}\u{FB02}"), "\u{FF41}fl");
+        // Without the flag NFD leaves both alone; of the two only `ﬁ` is
+        // split, and only by case folding.
+        assert_eq!(normalize_string("æ\u{FB01}", false), "æ\u{FB01}");
+        assert_eq!(normalize_string("æ\u{FB01}", true), "æfi");
+
+        let (normalized, map) = normalize_with_map("cæ\u{FB01}", &options);
+        assert_eq!(normalized, "caefi");
+        assert_eq!(map, vec![0, 1, 1, 3, 3]);
+    }
+
+    #[test]
+    fn test_fold_superscripts_spells_out_numbers() {
+        let options = NormalizationOptions {
+            fold_superscripts: true,
+            ..Default::default()
+        };
+        let fold = |input| normalize_with(input, &options);
+
+        assert_eq!(fold("m²"), "m2");
+        assert_eq!(fold("½"), "1/2");
+        assert_eq!(fold("1\u{2044}3"), "1/3");
+        assert_eq!(fold("H₂O"), "h2o");
+        assert_eq!(fold("xⁿ⁺¹"), "xn+1");
+        assert_eq!(fold("1ª"), "1a");
+        // Other compatibility characters keep their form.
+        assert_eq!(fold("\u{FF21}\u{FB01}™"), "\u{FF41}fi™");
+        assert_eq!(normalize_string("m² ½", true), "m² ½");
+
+        let (normalized, map) = normalize_with_map("¾ m²", &options);
+        assert_eq!(normalized, "3/4 m2");
+        assert_eq!(map, vec![0, 0, 0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_fold_digits_replaces_every_digit() {
+        let options = NormalizationOptions {
+            fold_digits: true,
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_with("Room 101", &options), "room 000");
+        assert_eq!(
+            normalize_with("Room 101", &options),
+            normalize_with("room 202", &options)
+        );
+        // Only ASCII digits are folded.
+        assert_eq!(normalize_with("x\u{0663}", &options), "x\u{0663}");
+
+        let (normalized, map) = normalize_with_map("a1 2", &options);
+        assert_eq!(normalized, "a0 0");
+        assert_eq!(map, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_trim_keeps_inner_whitespace() {
+        let options = NormalizationOptions {
+            form: None,
+            strip_diacritics: false,
+            case_fold: false,
+            collapse_whitespace: false,
+            trim: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            normalize_with(" \tCafé  au lait\n", &options),
+            "Café  au lait"
+        );
+        assert_eq!(normalize_with("   ", &options), "");
+
+        let (normalized, offsets) = normalize_with_map("  ab c ", &options);
+        assert_eq!(normalized, "ab c");
+        assert_eq!(offsets, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_strip_control_removes_invisible_characters() {
+        let options = NormalizationOptions {
+            strip_control: true,
+            ..Default::default()
+        };
+        let pasted = "\u{FEFF}Zero\u{200B}width\u{0007}\tspace\u{2060}";
+        assert_eq!(normalize_with(pasted, &options), "zerowidth space");
+        assert_eq!(
+            normalize_with(pasted, &NormalizationOptions::default()),
+            "\u{FEFF}zero\u{200B}width\u{0007} space\u{2060}"
+        );
+
+        let (normalized, offsets) = normalize_with_map("a\u{200B}b", &options);
+        assert_eq!(normalized, "ab");
+        assert_eq!(offsets, vec![0, 4]);
+    }
+
+    #[test]
+    fn test_normalize_ascii_matches_normalize_string() {
+        let inputs = [
+            "",
+            "apple",
+            "  Apple  PIE ",
+            "\tTabs\tand\nnewlines\r\n",
+            "vertical\x0Btab\x0Cfeed",
+            "Hello, World! 123",
+            "   ",
+        ];
+        for input in inputs {
+            for to_lowercase in [true, false] {
+                assert_eq!(
+                    normalize_ascii(input, to_lowercase),
+                    normalize_string(input, to_lowercase),
+                    "{input:?}"
+                );
+            }
+        }
+
+        // Non-ASCII characters are left alone rather than folded.
+        assert_eq!(normalize_ascii("CAFÉ  au lait", true), "cafÉ au lait");
+    }
+
+    #[test]
+    fn test_greek_sigma_folds_to_one_letter() {
+        for word in ["ΟΔΟΣ", "οδος", "οδοσ", "ΌΔΟΣ", "όδος"]
+        {
+            assert_eq!(normalize_string(word, true), "οδοσ", "{word:?}");
+        }
+        for sigma in ["Σ", "ς", "σ"] {
+            assert_eq!(normalize_string(sigma, true), "σ");
+        }
+        // Without case folding final sigma stays distinct.
+        assert_eq!(normalize_string("οδος", false), "οδος");
+        assert_eq!(normalize_string("Straße", true), "strasse");
+    }
+
+    #[test]
+    fn test_case_folding_reintroduces_no_marks() {
+        let folds_to_marks = ['İ', 'ǰ', 'ΐ', 'ΰ', 'ẖ', 'ὐ', 'ᾷ', 'ῗ'];
+        for form in [
+            Some(NormalizationForm::Nfd),
+            Some(NormalizationForm::Nfkd),
+            Some(NormalizationForm::Nfc),
+            Some(NormalizationForm::Nfkc),
+            None,
+        ] {
+            let options = NormalizationOptions {
+                form,
+                ..Default::default()
+            };
+            for c in folds_to_marks {
+                let input = format!("{c}x");
+                let normalized = normalize_with(&input, &options);
+                assert!(
+                    !normalized
+                        .chars()
+                        .any(is_combining_mark),
+                    "{c:?} under {form:?}: {normalized:?}"
+                );
+                assert_eq!(normalize_with_map(&input, &options).0, normalized);
+            }
+        }
+
+        // Under NFC without stripping, folding still yields composed text.
+        let composed = NormalizationOptions {
+            form: Some(NormalizationForm::Nfc),
+            strip_diacritics: false,
+            ..Default::default()
+        };
+        assert_eq!(normalize_with("ΐ", &composed), "\u{0390}");
+        assert_eq!(normalize_with_map("ΐ", &composed).0, "\u{0390}");
+    }
+
+    #[test]
+    fn test_fold_confusables_unmasks_spoofs() {
+        let options = NormalizationOptions {
+            fold_confusables: true,
+            ..Default::default()
+        };
+
+        // Cyrillic а (U+0430) and Greek Ρ (U+03A1).
+        assert_eq!(normalize_with("\u{0430}pple", &options), "apple");
+        assert_eq!(normalize_with("\u{03A1}aypal", &options), "paypal");
+        // ASCII look-alikes such as "rn" and "m" stay distinct.
+        assert_eq!(normalize_with("modern", &options), "modern");
+        assert_ne!(
+            normalize_with("\u{0430}pple", &NormalizationOptions::default()),
+            "apple"
+        );
+    }
 }
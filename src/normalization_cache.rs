@@ -0,0 +1,223 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+
+use crate::NormalizationOptions;
+
+/// Default capacity of the shared normalization cache, in entries.
+pub const DEFAULT_NORMALIZATION_CACHE_SIZE: u32 = 10_000;
+
+/// Normalized strings shared by every search with `cacheNormalization`.
+///
+/// Searches run on the JS thread but score items on rayon workers, so the
+/// cache sits behind a mutex. The lock is only held to look up or store an
+/// entry, never while normalizing, and a poisoned lock is still used since
+/// every entry is written in one step.
+static CACHE: LazyLock<Mutex<NormalizationCache>> = LazyLock::new(|| {
+    Mutex::new(NormalizationCache::new(
+        DEFAULT_NORMALIZATION_CACHE_SIZE as usize,
+    ))
+});
+
+/// Returns the normalized form of `input` under the options identified by
+/// `options`, calling `normalize` and caching its result on a miss.
+pub(crate) fn cached(
+    options: u64,
+    input: &str,
+    normalize: impl FnOnce() -> String,
+) -> String {
+    if let Some(normalized) = lock().get(options, input) {
+        return normalized;
+    }
+
+    let normalized = normalize();
+    lock().insert(options, input, &normalized);
+    normalized
+}
+
+/// Empties the shared cache and bounds it to `capacity` entries; `0`
+/// disables it.
+pub(crate) fn resize(capacity: usize) {
+    *lock() = NormalizationCache::new(capacity);
+}
+
+/// Identifies `options` in cache keys. Equal options always get the same
+/// key, whatever order their sets were filled in.
+pub(crate) fn options_key(options: &NormalizationOptions) -> u64 {
+    // Destructured so that a new option can't be forgotten here.
+    let NormalizationOptions {
+        strip_control,
+        strip_diacritics,
+        latin_diacritics_only,
+        keep_letters,
+        fold_confusables,
+        expand_ligatures,
+        fold_superscripts,
+        form,
+        case_fold,
+        turkish_i,
+        alphanumeric_only,
+        ignore_chars,
+        fold_digits,
+        collapse_whitespace,
+        trim,
+        stem,
+        stopwords,
+    } = options;
+
+    let mut ignore_chars: Vec<&char> = ignore_chars.iter().collect();
+    ignore_chars.sort_unstable();
+    let mut keep_letters: Vec<&char> = keep_letters.iter().collect();
+    keep_letters.sort_unstable();
+    let mut stopwords: Vec<&String> = stopwords.iter().collect();
+    stopwords.sort_unstable();
+
+    let mut hasher = DefaultHasher::new();
+    // Tuples only implement `Hash` up to twelve fields, hence the nesting.
+    (
+        (strip_control, strip_diacritics, latin_diacritics_only),
+        keep_letters,
+        (fold_confusables, expand_ligatures, fold_superscripts),
+        form,
+        case_fold,
+        turkish_i,
+        alphanumeric_only,
+        (ignore_chars, fold_digits),
+        (collapse_whitespace, trim),
+        stem,
+        stopwords,
+    )
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+fn lock() -> MutexGuard<'static, NormalizationCache> {
+    CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+struct Entry {
+    options: u64,
+    input: Box<str>,
+    normalized: Box<str>,
+}
+
+/// A bounded map from `(options, input)` to normalized strings with
+/// approximately least-recently-used eviction in constant time.
+///
+/// Entries are keyed by a hash of both parts and store them for comparison,
+/// so a hash collision is a miss rather than a wrong result. New entries go
+/// into a young generation; hits in the old generation move back into the
+/// young one. Once the young generation holds half the capacity, the old one
+/// is dropped and the young one takes its place, evicting everything unused
+/// for a whole generation at once.
+struct NormalizationCache {
+    capacity: usize,
+    young: HashMap<u64, Entry>,
+    old: HashMap<u64, Entry>,
+}
+
+impl NormalizationCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            young: HashMap::new(),
+            old: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, options: u64, input: &str) -> Option<String> {
+        let key = key(options, input);
+        let matches =
+            |entry: &Entry| entry.options == options && &*entry.input == input;
+
+        if let Some(entry) = self
+            .young
+            .get(&key)
+            .filter(|e| matches(e))
+        {
+            return Some(entry.normalized.to_string());
+        }
+
+        let entry = self.old.remove(&key).filter(matches)?;
+        let normalized = entry.normalized.to_string();
+        self.promote(key, entry);
+        Some(normalized)
+    }
+
+    fn insert(&mut self, options: u64, input: &str, normalized: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let entry = Entry {
+            options,
+            input: input.into(),
+            normalized: normalized.into(),
+        };
+        self.promote(key(options, input), entry);
+    }
+
+    fn promote(&mut self, key: u64, entry: Entry) {
+        if self.young.len() >= (self.capacity / 2).max(1) {
+            self.old = mem::take(&mut self.young);
+        }
+        self.young.insert(key, entry);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.young.len() + self.old.len()
+    }
+}
+
+fn key(options: u64, input: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (options, input).hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_is_bounded_and_keeps_recent_entries() {
+        let mut cache = NormalizationCache::new(4);
+        for i in 0..100 {
+            let input = i.to_string();
+            cache.insert(1, &input, &input);
+            // Keep "0" in use so it survives every rotation.
+            assert_eq!(cache.get(1, "0"), Some("0".to_string()));
+            assert!(cache.len() <= 4);
+        }
+
+        assert_eq!(cache.get(1, "99"), Some("99".to_string()));
+        assert_eq!(cache.get(1, "1"), None);
+        assert_eq!(cache.get(2, "99"), None);
+    }
+
+    #[test]
+    fn test_options_key_ignores_set_order() {
+        let options = |chars: &str| NormalizationOptions {
+            ignore_chars: chars.chars().collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options_key(&options("-_./")),
+            options_key(&options("/._-"))
+        );
+        assert_ne!(options_key(&options("-")), options_key(&options("_")));
+        assert_ne!(
+            options_key(&NormalizationOptions::default()),
+            options_key(&NormalizationOptions {
+                case_fold: false,
+                ..Default::default()
+            })
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+/// A Unicode normalization form, see
+/// [UAX #15](https://unicode.org/reports/tr15/).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NormalizationForm {
+    /// Canonical composition: precomposed characters where possible.
+    Nfc,
+    /// Canonical decomposition: base letters followed by combining marks.
+    Nfd,
+    /// Compatibility composition: like NFC, also unifying width variants,
+    /// ligatures and similar presentation forms.
+    Nfkc,
+    /// Compatibility decomposition: like NFD, also unifying presentation
+    /// forms.
+    Nfkd,
+}
+
+/// Settings for [`crate::normalize_with`].
+///
+/// [`Default`] matches `normalize_string(input, true)`: the input is
+/// decomposed with NFD, diacritics are stripped, case is folded and
+/// whitespace is collapsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizationOptions {
+    /// Remove control characters and zero-width characters (U+200B–U+200D,
+    /// U+2060 and the U+FEFF byte order mark) before any other step, so text
+    /// pasted with invisible characters still matches. Tabs, newlines and
+    /// other whitespace are kept for `collapse_whitespace`.
+    pub strip_control: bool,
+
+    /// Remove combining marks after applying `form`. Only decomposed forms
+    /// (NFD, NFKD) separate accents from their base letters, so with NFC or
+    /// NFKC only marks without a precomposed character are removed.
+    pub strip_diacritics: bool,
+
+    /// Only strip the combining marks that follow a Latin letter, leaving
+    /// other scripts' marks intact, so `"café"` becomes `"cafe"` but Hebrew
+    /// points and Greek tonos are kept. Has no effect without
+    /// `strip_diacritics`.
+    pub latin_diacritics_only: bool,
+
+    /// Characters passed through the normalization form and diacritic
+    /// stripping untouched, so with `'ñ'` in the set `"niño"` keeps its tilde
+    /// while `"café"` still becomes `"cafe"`. Case folding still applies.
+    pub keep_letters: HashSet<char>,
+
+    /// Replace non-ASCII characters that look like others with their UTS #39
+    /// confusable prototype, so a Cyrillic-spoofed `"аpple"` becomes
+    /// `"apple"`. ASCII is left alone, so `'m'` doesn't turn into `"rn"`.
+    pub fold_confusables: bool,
+
+    /// Spell out ligatures as their letters, so `"encyclopædia"` becomes
+    /// `"encyclopaedia"` and `"ﬁle"` becomes `"file"`. Covers `æ` and `œ`,
+    /// which no normalization form splits, plus the compatibility ligatures
+    /// (`ﬁ`, `ĳ`, `ǆ`, …) without applying NFKD to anything else.
+    pub expand_ligatures: bool,
+
+    /// Fold superscripts, subscripts and vulgar fractions into plain
+    /// characters with their compatibility decomposition, so `"m²"` becomes
+    /// `"m2"`, `"H₂O"` becomes `"h2o"` and `'½'` becomes `"1/2"`, without
+    /// applying NFKD to anything else. `™` and `℠` are left alone.
+    pub fold_superscripts: bool,
+
+    /// Unicode normalization form applied before the other steps, or `None`
+    /// to leave the input's form alone. The compatibility forms turn
+    /// full-width `"ＡＢＣ１２３"` into `"ABC123"` and half-width katakana
+    /// into their full-width forms.
+    pub form: Option<NormalizationForm>,
+
+    /// Apply Unicode case folding, e.g. `"STRASSE"` and `"straße"` both
+    /// become `"strasse"`, and Greek `'Σ'`, `'ς'` and `'σ'` all become `'σ'`.
+    /// Runs after diacritic stripping; see [`crate::normalize_with`] for the
+    /// full order.
+    pub case_fold: bool,
+
+    /// Use Turkish/Azeri rules for the letter I when case folding: `'I'`
+    /// folds to dotless `'ı'` and `'İ'` folds to `'i'`.
+    pub turkish_i: bool,
+
+    /// Drop every character that isn't alphanumeric, including whitespace, so
+    /// `"ABC-123"` and `"abc 123"` both become `"abc123"`.
+    pub alphanumeric_only: bool,
+
+    /// Characters removed after case folding, e.g. `'-'` so `"555-1234"`
+    /// becomes `"5551234"`. Like `stopwords`, entries are compared against
+    /// already folded text.
+    pub ignore_chars: HashSet<char>,
+
+    /// Replace every ASCII digit with `'0'`, so numbers only compare by
+    /// their shape: `"room 101"` and `"room 202"` become the same string.
+    pub fold_digits: bool,
+
+    /// Collapse runs of whitespace into single spaces and trim the ends.
+    pub collapse_whitespace: bool,
+
+    /// Trim whitespace from both ends without collapsing the runs in
+    /// between, so `"  red  apple "` becomes `"red  apple"`. Collapsing
+    /// already trims, so this only matters without `collapse_whitespace`.
+    pub trim: bool,
+
+    /// Reduce each whitespace-separated token to its English stem, so
+    /// `"running shoes"` becomes `"run shoe"`. Requires the `stemming`
+    /// feature; without it this flag is ignored.
+    pub stem: bool,
+
+    /// Tokens removed before stemming. Entries are compared against tokens
+    /// after the other steps, so they should already be normalized the same
+    /// way (e.g. case folded).
+    pub stopwords: HashSet<String>,
+}
+
+impl Default for NormalizationOptions {
+    fn default() -> Self {
+        Self {
+            strip_control: false,
+            strip_diacritics: true,
+            latin_diacritics_only: false,
+            keep_letters: HashSet::new(),
+            fold_confusables: false,
+            expand_ligatures: false,
+            fold_superscripts: false,
+            form: Some(NormalizationForm::Nfd),
+            case_fold: true,
+            turkish_i: false,
+            alphanumeric_only: false,
+            ignore_chars: HashSet::new(),
+            fold_digits: false,
+            collapse_whitespace: true,
+            trim: false,
+            stem: false,
+            stopwords: HashSet::new(),
+        }
+    }
+}
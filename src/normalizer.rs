@@ -0,0 +1,101 @@
+use napi::bindgen_prelude::*;
+use napi::{JsFunction, JsString, NapiValue};
+
+/// A JS `normalizer(s: string): string` function, from
+/// [`crate::SearchOptions::normalizer`].
+///
+/// It holds the function's handle, which is only valid on the JS thread
+/// during the call that received it, so it is only ever called there, before
+/// scoring starts; the worker threads scoring the normalized strings never
+/// touch it.
+#[derive(Clone, Copy)]
+pub struct Normalizer {
+    env: sys::napi_env,
+    function: sys::napi_value,
+    /// [`call_js`], set when the function arrives from JS. Calling it through
+    /// this pointer keeps the N-API functions it needs out of the Rust-only
+    /// paths, so Rust callers still link without Node.js.
+    call: unsafe fn(sys::napi_env, sys::napi_value, &str) -> Result<String>,
+}
+
+// SAFETY: see above; the handle is passed along with the options but only
+// dereferenced by `call`, on the JS thread that created it.
+unsafe impl Send for Normalizer {}
+unsafe impl Sync for Normalizer {}
+
+impl Normalizer {
+    /// Runs the function over `input`. A thrown exception propagates, and a
+    /// result that isn't a string is rejected.
+    pub(crate) fn call(&self, input: &str) -> Result<String> {
+        // SAFETY: both handles are still current on the JS thread.
+        unsafe { (self.call)(self.env, self.function, input) }
+    }
+
+    /// Runs the function over every item, in order.
+    pub(crate) fn call_all(&self, items: &[String]) -> Result<Vec<String>> {
+        items
+            .iter()
+            .map(|item| self.call(item))
+            .collect()
+    }
+}
+
+/// Calls the JS `function` with `input`.
+///
+/// # Safety
+///
+/// `env` and `function` must be current handles on the JS thread.
+unsafe fn call_js(
+    env: sys::napi_env,
+    function: sys::napi_value,
+    input: &str,
+) -> Result<String> {
+    let function = unsafe { JsFunction::from_raw_unchecked(env, function) };
+    let input = unsafe { Env::from_raw(env) }.create_string(input)?;
+    let output = function.call(None, &[input])?;
+    if output.get_type()? != ValueType::String {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "normalizer must return a string".to_string(),
+        ));
+    }
+    // SAFETY: just checked to be a string.
+    unsafe { output.cast::<JsString>() }
+        .into_utf8()?
+        .into_owned()
+}
+
+impl TypeName for Normalizer {
+    fn type_name() -> &'static str {
+        "Function"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Function
+    }
+}
+
+impl ValidateNapiValue for Normalizer {}
+
+impl FromNapiValue for Normalizer {
+    unsafe fn from_napi_value(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+    ) -> Result<Self> {
+        unsafe { JsFunction::validate(env, napi_val)? };
+        Ok(Self {
+            env,
+            function: napi_val,
+            call: call_js,
+        })
+    }
+}
+
+impl ToNapiValue for Normalizer {
+    unsafe fn to_napi_value(
+        _env: sys::napi_env,
+        val: Self,
+    ) -> Result<sys::napi_value> {
+        Ok(val.function)
+    }
+}
@@ -0,0 +1,126 @@
+//! Phonetic codes for matching names that sound alike but are spelled
+//! differently, such as `"Smith"` and `"Smyth"`.
+
+/// Computes the American Soundex code of `input`: its first letter followed
+/// by three digits describing the consonants after it, e.g. `"R163"`.
+///
+/// Letters that sound alike share a digit, vowels only separate repeated
+/// digits, and `h`/`w` are skipped entirely. Characters other than ASCII
+/// letters are ignored, so accented input should be normalized first.
+/// Returns an empty string when `input` has no ASCII letters.
+///
+/// # Examples
+///
+/// ```
+/// use fast_fuzzy_rs::soundex;
+///
+/// assert_eq!(soundex("Robert"), "R163");
+/// assert_eq!(soundex("Rupert"), "R163");
+/// assert_eq!(soundex("Smith"), soundex("Smyth"));
+/// assert_eq!(soundex("Lee"), "L000");
+/// assert_eq!(soundex("42"), "");
+/// ```
+pub fn soundex(input: &str) -> String {
+    soundex_code(input)
+        .map(|code| {
+            code.iter()
+                .map(|&b| b as char)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// [`soundex`] as fixed-size bytes, so comparing codes doesn't allocate.
+pub(crate) fn soundex_code(input: &str) -> Option<[u8; 4]> {
+    let mut letters = input
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_lowercase());
+
+    let first = letters.next()?;
+    let mut code = [first.to_ascii_uppercase(), b'0', b'0', b'0'];
+    let mut len = 1;
+    let mut last = digit(first);
+
+    for letter in letters {
+        if len == code.len() {
+            break;
+        }
+        match letter {
+            b'h' | b'w' => {}
+            _ => {
+                let current = digit(letter);
+                if current != b'0' && current != last {
+                    code[len] = current;
+                    len += 1;
+                }
+                last = current;
+            }
+        }
+    }
+
+    Some(code)
+}
+
+/// The Soundex digit of a lowercase ASCII letter, `b'0'` for vowels and `y`.
+fn digit(letter: u8) -> u8 {
+    match letter {
+        b'b' | b'f' | b'p' | b'v' => b'1',
+        b'c' | b'g' | b'j' | b'k' | b'q' | b's' | b'x' | b'z' => b'2',
+        b'd' | b't' => b'3',
+        b'l' => b'4',
+        b'm' | b'n' => b'5',
+        b'r' => b'6',
+        _ => b'0',
+    }
+}
+
+/// Whether `a` and `b` have the same number of words and every pair of
+/// words shares a Soundex code, so `"jon smyth"` matches `"john smith"`.
+pub(crate) fn sounds_alike(a: &str, b: &str) -> bool {
+    let (mut a, mut b) = (a.split_whitespace(), b.split_whitespace());
+    let mut compared = false;
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return compared,
+            (Some(a), Some(b)) => match (soundex_code(a), soundex_code(b)) {
+                (Some(a), Some(b)) if a == b => compared = true,
+                _ => return false,
+            },
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_soundex_codes() {
+        let cases = [
+            ("Robert", "R163"),
+            ("Rupert", "R163"),
+            ("Rubin", "R150"),
+            ("Ashcraft", "A261"),
+            ("Ashcroft", "A261"),
+            ("Tymczak", "T522"),
+            ("Pfister", "P236"),
+            ("Honeyman", "H555"),
+            ("Tsai", "T200"),
+        ];
+        for (name, code) in cases {
+            assert_eq!(soundex(name), code, "{name}");
+        }
+        assert_eq!(soundex(""), "");
+    }
+
+    #[test]
+    fn test_sounds_alike_compares_each_word() {
+        assert!(sounds_alike("jon smyth", "john smith"));
+        assert!(!sounds_alike("john smith", "john"));
+        assert!(!sounds_alike("john smith", "john jones"));
+        assert!(!sounds_alike("", ""));
+        assert!(!sounds_alike("42", "42"));
+    }
+}
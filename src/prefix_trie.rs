@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+/// A trie over normalized items, used by [`crate::Searcher::prefix_search`]
+/// to find every item starting with a prefix without scanning the corpus.
+///
+/// Finding the node for a prefix takes one step per `char` of the prefix;
+/// collecting the matches then visits only the subtree below it. Removed ids
+/// are dropped from their node, but empty nodes are kept until
+/// [`PrefixTrie::clear`].
+#[derive(Default)]
+pub(crate) struct PrefixTrie {
+    /// Node `0` is the root, the node for the empty prefix.
+    nodes: Vec<Node>,
+}
+
+#[derive(Default)]
+struct Node {
+    children: HashMap<char, usize>,
+    /// Ids of the items ending exactly at this node.
+    ids: Vec<u32>,
+}
+
+impl PrefixTrie {
+    /// Indexes `text` under `id`.
+    pub(crate) fn insert(&mut self, id: u32, text: &str) {
+        if self.nodes.is_empty() {
+            self.nodes.push(Node::default());
+        }
+
+        let mut node = 0;
+        for c in text.chars() {
+            node = match self.nodes[node].children.get(&c) {
+                Some(&child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node]
+                        .children
+                        .insert(c, child);
+                    child
+                }
+            };
+        }
+        self.nodes[node].ids.push(id);
+    }
+
+    /// Removes the entry `text` was indexed under for `id`.
+    pub(crate) fn remove(&mut self, id: u32, text: &str) {
+        if let Some(node) = self.find(text) {
+            self.nodes[node]
+                .ids
+                .retain(|&other| other != id);
+        }
+    }
+
+    /// Removes every entry from the trie.
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Returns the ids of items starting with `prefix`, in no particular
+    /// order.
+    pub(crate) fn starting_with(&self, prefix: &str) -> Vec<u32> {
+        let mut ids = Vec::new();
+        let mut stack: Vec<usize> = self.find(prefix).into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            ids.extend_from_slice(&node.ids);
+            stack.extend(node.children.values());
+        }
+        ids
+    }
+
+    /// The node reached by following `text` from the root, if any.
+    fn find(&self, text: &str) -> Option<usize> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        text.chars().try_fold(0, |node, c| {
+            self.nodes[node]
+                .children
+                .get(&c)
+                .copied()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starting_with_collects_the_subtree() {
+        let mut trie = PrefixTrie::default();
+        for (id, word) in ["car", "cart", "care", "cat", "dog"]
+            .iter()
+            .enumerate()
+        {
+            trie.insert(id as u32, word);
+        }
+
+        let mut ids = trie.starting_with("car");
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 1, 2]);
+        assert_eq!(trie.starting_with("").len(), 5);
+        assert!(trie.starting_with("cow").is_empty());
+
+        trie.remove(1, "cart");
+        let mut ids = trie.starting_with("car");
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 2]);
+
+        trie.clear();
+        assert!(trie.starting_with("").is_empty());
+    }
+}
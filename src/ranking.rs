@@ -0,0 +1,170 @@
+use std::cmp::Ordering;
+
+use crate::SearchOptions;
+
+/// How results are ordered, resolved from [`SearchOptions`] once per search:
+/// by descending score, then by descending `sort_keys`, then with
+/// `natural_order` by [`natural_cmp`] of the items, then by corpus order.
+#[derive(Clone, Copy)]
+pub(crate) struct Ranking<'a> {
+    sort_keys: Option<&'a [f64]>,
+    /// The items compared on ties, if `natural_order` is set.
+    natural: Option<Corpus<'a>>,
+}
+
+/// The item strings of a search, owned by the napi layer or borrowed by
+/// [`crate::search_ref`].
+#[derive(Clone, Copy)]
+pub(crate) enum Corpus<'a> {
+    Owned(&'a [String]),
+    Borrowed(&'a [&'a str]),
+}
+
+impl<'a> Corpus<'a> {
+    fn get(self, index: u32) -> &'a str {
+        match self {
+            Self::Owned(items) => &items[index as usize],
+            Self::Borrowed(items) => items[index as usize],
+        }
+    }
+}
+
+impl<'a> From<&'a [String]> for Corpus<'a> {
+    fn from(items: &'a [String]) -> Self {
+        Self::Owned(items)
+    }
+}
+
+impl<'a> From<&'a [&'a str]> for Corpus<'a> {
+    fn from(items: &'a [&'a str]) -> Self {
+        Self::Borrowed(items)
+    }
+}
+
+impl<'a> Ranking<'a> {
+    /// `items` are the strings `natural_order` compares; searches over
+    /// records pass `None` and ignore it.
+    pub(crate) fn new(
+        opts: &'a SearchOptions,
+        items: Option<Corpus<'a>>,
+    ) -> Self {
+        Self {
+            sort_keys: opts.sort_keys.as_deref(),
+            natural: items.filter(|_| opts.natural_order.unwrap_or(false)),
+        }
+    }
+
+    /// Orders two `(score, index)` results, the better one first.
+    pub(crate) fn compare(
+        &self,
+        (a_score, a_index): (f64, u32),
+        (b_score, b_index): (f64, u32),
+    ) -> Ordering {
+        b_score
+            .partial_cmp(&a_score)
+            .unwrap()
+            .then_with(|| {
+                self.sort_key(b_index)
+                    .partial_cmp(&self.sort_key(a_index))
+                    .unwrap()
+            })
+            .then_with(|| match self.natural {
+                Some(items) => {
+                    natural_cmp(items.get(a_index), items.get(b_index))
+                }
+                None => Ordering::Equal,
+            })
+            .then(a_index.cmp(&b_index))
+    }
+
+    /// The sort key of the item at `index`, `0.0` for every item when no sort
+    /// keys were given.
+    fn sort_key(&self, index: u32) -> f64 {
+        self.sort_keys
+            .map_or(0.0, |keys| keys[index as usize])
+    }
+}
+
+/// Compares strings the way people sort numbered names: runs of ASCII digits
+/// compare by value, so `"item2" < "item10"`, and everything else compares
+/// `char` by `char`. Runs that differ only in leading zeros compare equal.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (x, y) = match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => (x, y),
+        };
+
+        if x.is_ascii_digit() && y.is_ascii_digit() {
+            let (x_run, x_rest) = split_digits(a);
+            let (y_run, y_rest) = split_digits(b);
+            let (x_run, y_run) =
+                (x_run.trim_start_matches('0'), y_run.trim_start_matches('0'));
+            let ordering = x_run
+                .len()
+                .cmp(&y_run.len())
+                .then_with(|| x_run.cmp(y_run));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (x_rest, y_rest);
+        } else if x != y {
+            return x.cmp(&y);
+        } else {
+            (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+        }
+    }
+}
+
+/// Splits `s` after its leading run of ASCII digits.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp_compares_numbers_by_value() {
+        let mut items =
+            vec!["item10", "item2", "item1b", "item", "file3", "item1"];
+        items.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            items,
+            ["file3", "item", "item1", "item1b", "item2", "item10"]
+        );
+
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+        assert_eq!(natural_cmp("a007", "a7"), Ordering::Equal);
+        assert_eq!(natural_cmp("a0", "a"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_tie_breakers_apply_in_order() {
+        let items: Vec<String> = ["item10", "item2", "item3"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let opts = SearchOptions {
+            natural_order: Some(true),
+            sort_keys: Some(vec![0.0, 0.0, 1.0]),
+            ..Default::default()
+        };
+        let ranking = Ranking::new(&opts, Some(items.as_slice().into()));
+
+        let mut indices = vec![0, 1, 2];
+        indices.sort_by(|&a, &b| ranking.compare((0.5, a), (0.5, b)));
+        assert_eq!(indices, [2, 1, 0]);
+        assert_eq!(ranking.compare((0.9, 0), (0.5, 2)), Ordering::Less);
+
+        let records = Ranking::new(&opts, None);
+        assert_eq!(records.compare((0.5, 0), (0.5, 1)), Ordering::Less);
+    }
+}
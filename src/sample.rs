@@ -0,0 +1,109 @@
+use crate::SearchOptions;
+
+/// The items a search scores when `sampleLimit` caps it: all of them by
+/// default, otherwise a uniform random subset of `sampleLimit` items.
+///
+/// The subset is drawn by reservoir sampling from a generator seeded with
+/// `sampleSeed`, so the same corpus size, limit and seed always pick the
+/// same items. Drawing costs one random number per item, far less than
+/// scoring it.
+pub(crate) struct Sample {
+    /// Whether each item was picked, or `None` when every item is scored.
+    picked: Option<Vec<bool>>,
+}
+
+impl Sample {
+    pub(crate) fn new(opts: &SearchOptions, item_count: usize) -> Self {
+        let limit = match opts.sample_limit {
+            Some(limit) if is_sampled(opts, item_count) => limit as usize,
+            _ => return Self { picked: None },
+        };
+
+        let mut random = SplitMix64(u64::from(opts.sample_seed.unwrap_or(0)));
+        let mut reservoir: Vec<usize> = (0..limit).collect();
+        for index in limit..item_count {
+            let slot = random.below(index as u64 + 1) as usize;
+            if slot < limit {
+                reservoir[slot] = index;
+            }
+        }
+
+        let mut picked = vec![false; item_count];
+        for index in reservoir {
+            picked[index] = true;
+        }
+        Self {
+            picked: Some(picked),
+        }
+    }
+
+    /// Whether the item at `index` is scored.
+    pub(crate) fn contains(&self, index: usize) -> bool {
+        self.picked
+            .as_ref()
+            .is_none_or(|picked| picked[index])
+    }
+}
+
+/// Whether `sampleLimit` leaves some of `item_count` items unscored, which
+/// makes a search's results approximate.
+pub(crate) fn is_sampled(opts: &SearchOptions, item_count: usize) -> bool {
+    opts.sample_limit
+        .is_some_and(|limit| (limit as usize) < item_count)
+}
+
+/// The SplitMix64 generator: tiny, fast and good enough to pick samples.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, by scaling rather than `%` to avoid bias
+    /// towards small values.
+    fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next()) * u128::from(bound)) >> 64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picked(
+        limit: Option<u32>,
+        seed: Option<u32>,
+        count: usize,
+    ) -> Vec<usize> {
+        let opts = SearchOptions {
+            sample_limit: limit,
+            sample_seed: seed,
+            ..Default::default()
+        };
+        let sample = Sample::new(&opts, count);
+        (0..count)
+            .filter(|&index| sample.contains(index))
+            .collect()
+    }
+
+    #[test]
+    fn test_sample_is_reproducible_per_seed() {
+        let first = picked(Some(50), Some(7), 1_000);
+        assert_eq!(first.len(), 50);
+        assert_eq!(picked(Some(50), Some(7), 1_000), first);
+        assert_ne!(picked(Some(50), Some(8), 1_000), first);
+        // Picks are spread over the corpus, not bunched at its start.
+        assert!(first.iter().any(|&index| index >= 500));
+
+        // A limit covering the corpus scores everything.
+        assert_eq!(picked(Some(10), None, 10), (0..10).collect::<Vec<_>>());
+        assert_eq!(picked(None, None, 3), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,22 @@
+use napi_derive::napi;
+
+/// The distance and similarity of one pair, returned by
+/// [`crate::score_detailed`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface ScoreDetails {
+///   distance: number;   // edit distance between the normalized strings
+///   similarity: number; // the score `fuzzy` gives the same pair
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreDetails {
+    /// Edit distance between the normalized strings, as
+    /// [`crate::ScoreExplanation::distance`] reports it.
+    pub distance: u32,
+
+    /// The similarity score of the pair, including boosts and penalties.
+    pub similarity: f64,
+}
@@ -0,0 +1,62 @@
+use napi_derive::napi;
+
+/// Breakdown of how a [`crate::SearchResult`]'s score was computed.
+///
+/// Only attached when the `explain` search option is set.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface ScoreExplanation {
+///   distance: number;     // raw edit distance between normalized strings
+///   maxLength: number;    // length the distance was divided by
+///   algorithm: string;    // name of the scoring algorithm
+///   boosts: ScoreBoost[]; // bonuses applied on top of the base score
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ScoreExplanation {
+    /// Raw edit distance between the normalized query and item.
+    pub distance: u32,
+
+    /// Length used to turn the distance into a similarity score: the one
+    /// `scoreDenominator` picks, the longer input's by default, or the sum
+    /// of both with the `"ratio"` formula.
+    pub max_length: u32,
+
+    /// Name of the algorithm that produced the base score.
+    pub algorithm: String,
+
+    /// Bonuses applied on top of the base score, in the order applied.
+    pub boosts: Vec<ScoreBoost>,
+}
+
+/// A single bonus applied on top of a base similarity score. Penalties are
+/// reported the same way, with a negative `value`.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface ScoreBoost {
+///   name: string;   // which boost was applied
+///   value: number;  // amount added to the score (negative for penalties)
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ScoreBoost {
+    /// Name of the boost.
+    pub name: String,
+
+    /// Amount added to the score.
+    pub value: f64,
+}
@@ -0,0 +1,147 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::SearchResult;
+
+/// Search results whose scores fall in one bucket of
+/// [`crate::search_grouped`].
+///
+/// A bucket includes its lower edge and excludes its upper edge, so with
+/// edges `[0.9, 0.7]` a score of exactly `0.9` lands in `">=0.9"` and `0.7`
+/// lands in `"0.7-0.9"`.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface ScoreGroup {
+///   label: string;           // e.g. ">=0.9", "0.7-0.9", "<0.7"
+///   min?: number;            // inclusive lower edge, unset for the lowest
+///   max?: number;            // exclusive upper edge, unset for the highest
+///   results: SearchResult[]; // sorted by descending score
+/// }
+/// ```
+#[napi(object)]
+pub struct ScoreGroup {
+    /// Human-readable range of the bucket.
+    pub label: String,
+
+    /// Inclusive lower edge; `None` for the lowest bucket.
+    pub min: Option<f64>,
+
+    /// Exclusive upper edge; `None` for the highest bucket.
+    pub max: Option<f64>,
+
+    /// The results in this bucket, in ranking order.
+    pub results: Vec<SearchResult>,
+}
+
+/// Splits ranked `results` into one group per bucket delimited by `edges`,
+/// ordered from the highest bucket to the lowest.
+///
+/// Edges may be given in any order and duplicates are ignored. Every bucket
+/// is returned, even when empty, so facet counts stay stable. Without edges
+/// all results land in a single `"all"` group.
+pub(crate) fn group_by_score(
+    results: Vec<SearchResult>,
+    edges: &[f64],
+) -> Result<Vec<ScoreGroup>> {
+    if edges.iter().any(|edge| edge.is_nan()) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "bucket edges must be numbers".to_string(),
+        ));
+    }
+
+    let mut edges = edges.to_vec();
+    edges.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    edges.dedup();
+
+    if edges.is_empty() {
+        return Ok(vec![ScoreGroup {
+            label: "all".to_string(),
+            min: None,
+            max: None,
+            results,
+        }]);
+    }
+
+    let mut groups: Vec<ScoreGroup> = (0..=edges.len())
+        .map(|i| {
+            let min = edges.get(i).copied();
+            let max = i.checked_sub(1).map(|j| edges[j]);
+            let label = match (min, max) {
+                (Some(min), None) => format!(">={min}"),
+                (Some(min), Some(max)) => format!("{min}-{max}"),
+                (None, Some(max)) => format!("<{max}"),
+                (None, None) => unreachable!("edges is not empty"),
+            };
+            ScoreGroup {
+                label,
+                min,
+                max,
+                results: Vec::new(),
+            }
+        })
+        .collect();
+
+    for result in results {
+        let bucket = edges
+            .iter()
+            .position(|&edge| result.score >= edge)
+            .unwrap_or(edges.len());
+        groups[bucket].results.push(result);
+    }
+
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(score: f64) -> SearchResult {
+        SearchResult {
+            item: String::new(),
+            score,
+            index: 0,
+            explanation: None,
+            query_index: None,
+            raw_score: None,
+            normalized_item: None,
+            alias_index: None,
+            token_scores: None,
+        }
+    }
+
+    #[test]
+    fn test_boundaries_belong_to_the_higher_bucket() {
+        let results = Vec::from([1.0, 0.9, 0.8, 0.7, 0.2].map(result));
+        let groups = group_by_score(results, &[0.7, 0.9, 0.7]).unwrap();
+
+        let labels: Vec<&str> = groups
+            .iter()
+            .map(|g| g.label.as_str())
+            .collect();
+        assert_eq!(labels, vec![">=0.9", "0.7-0.9", "<0.7"]);
+
+        let scores: Vec<Vec<f64>> = groups
+            .iter()
+            .map(|g| {
+                g.results
+                    .iter()
+                    .map(|r| r.score)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(scores, vec![vec![1.0, 0.9], vec![0.8, 0.7], vec![0.2]]);
+        assert_eq!(groups[1].min, Some(0.7));
+        assert_eq!(groups[1].max, Some(0.9));
+    }
+
+    #[test]
+    fn test_no_edges_is_one_group() {
+        let groups = group_by_score(vec![result(0.5)], &[]).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].label, "all");
+        assert!(group_by_score(Vec::new(), &[f64::NAN]).is_err());
+    }
+}
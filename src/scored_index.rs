@@ -0,0 +1,20 @@
+use napi_derive::napi;
+
+/// A search result without the matched item, returned by
+/// [`crate::search_indices`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface ScoredIndex {
+///   index: number;  // index of the item in the original array
+///   score: number;  // similarity score (higher is better)
+/// }
+/// ```
+#[napi(object)]
+pub struct ScoredIndex {
+    /// Index of the item in the original input collection.
+    pub index: u32,
+
+    /// The similarity score assigned to this item.
+    pub score: f64,
+}
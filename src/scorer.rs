@@ -0,0 +1,1788 @@
+use std::cell::RefCell;
+use std::str::SplitWhitespace;
+
+use napi::bindgen_prelude::*;
+
+use unicode_segmentation::{UnicodeSegmentation, UnicodeWords};
+
+use crate::algo::{
+    affine_gap_distance, grapheme_osa_distance_with,
+    levenshtein_distance_prepared, unicode_word_distance_with,
+    weighted_levenshtein_distance, wildcard_distance, PreparedQuery,
+};
+use crate::jaro_winkler_similarity;
+use crate::normalization::base_letter;
+use crate::normalization_cache;
+use crate::phonetic::sounds_alike;
+use crate::trigram_index::shares_trigrams;
+use crate::{
+    apply_winkler_boost, check_length, exceeds_length, grapheme_distance_with,
+    levenshtein_distance_bounded_with, levenshtein_distance_with,
+    normalize_with, osa_distance_with, resolve_normalization,
+    word_distance_with, AlgorithmWeight, LevenshteinBuffer,
+    NormalizationOptions, ScoreBoost, ScoreExplanation, SearchOptions,
+    DEFAULT_MAX_INPUT_LENGTH,
+};
+
+thread_local! {
+    /// Scratch space for the distance DP, one per thread so a [`Scorer`] can
+    /// be shared across a parallel search.
+    static BUFFER: RefCell<LevenshteinBuffer> = RefCell::default();
+
+    /// The last query [`Scorer::distance`] decoded, one per thread, so a
+    /// search decodes its query once per thread rather than once per item.
+    static QUERY: RefCell<PreparedQuery> = RefCell::default();
+}
+
+/// How an edit distance is turned into a similarity score, selected by
+/// [`SearchOptions::score_formula`] and [`SearchOptions::score_denominator`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ScoreFormula {
+    /// `1 - distance / denominator`, clamped to `0.0`.
+    Distance(Denominator),
+    /// `(len_a + len_b - distance) / (len_a + len_b)`.
+    Ratio,
+}
+
+/// Which length [`ScoreFormula::Distance`] divides by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Denominator {
+    Max,
+    Min,
+    Query,
+    Item,
+}
+
+impl ScoreFormula {
+    /// The length a distance between a query of length `a` and an item of
+    /// length `b` is divided by.
+    fn length(self, a: usize, b: usize) -> usize {
+        match self {
+            Self::Distance(Denominator::Max) => a.max(b),
+            Self::Distance(Denominator::Min) => a.min(b),
+            Self::Distance(Denominator::Query) => a,
+            Self::Distance(Denominator::Item) => b,
+            Self::Ratio => a + b,
+        }
+    }
+
+    fn parse(name: Option<&str>, denominator: Option<&str>) -> Result<Self> {
+        let denominator = match denominator.unwrap_or("max") {
+            "max" => Denominator::Max,
+            "min" => Denominator::Min,
+            "query" => Denominator::Query,
+            "item" => Denominator::Item,
+            other => {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("unknown score denominator {other:?}"),
+                ))
+            }
+        };
+
+        match (name.unwrap_or("distance"), denominator) {
+            ("distance", denominator) => Ok(Self::Distance(denominator)),
+            ("ratio", Denominator::Max) => Ok(Self::Ratio),
+            ("ratio", _) => Err(Error::new(
+                Status::InvalidArg,
+                "the ratio formula always divides by the sum of both lengths"
+                    .to_string(),
+            )),
+            (other, _) => Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown score formula {other:?}"),
+            )),
+        }
+    }
+}
+
+/// Which edit distance is computed between two strings, selected by
+/// [`SearchOptions::algorithm`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Algorithm {
+    /// Insertions, deletions and substitutions.
+    Levenshtein,
+    /// Levenshtein plus adjacent transpositions, see [`crate::osa_distance`].
+    Osa,
+    /// Levenshtein over whole words, see [`crate::word_distance`].
+    WordLevel,
+}
+
+impl Algorithm {
+    fn parse(name: Option<&str>) -> Result<Self> {
+        match name.unwrap_or("levenshtein") {
+            "levenshtein" => Ok(Self::Levenshtein),
+            "osa" => Ok(Self::Osa),
+            "word-level" => Ok(Self::WordLevel),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown algorithm {other:?}"),
+            )),
+        }
+    }
+
+    /// The function computing this distance, over grapheme clusters with
+    /// `graphemes` and, for word-level distances, over Unicode words with
+    /// `unicode_words`.
+    fn distance_fn(self, graphemes: bool, unicode_words: bool) -> DistanceFn {
+        match (self, graphemes) {
+            (Self::Levenshtein, false) => levenshtein_distance_with,
+            (Self::Levenshtein, true) => grapheme_distance_with,
+            (Self::Osa, false) => osa_distance_with,
+            (Self::Osa, true) => grapheme_osa_distance_with,
+            (Self::WordLevel, _) if unicode_words => unicode_word_distance_with,
+            (Self::WordLevel, _) => word_distance_with,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Levenshtein => "levenshtein",
+            Self::Osa => "osa",
+            Self::WordLevel => "word-level",
+        }
+    }
+}
+
+/// An edit distance between two strings, computed with a reusable buffer.
+type DistanceFn = fn(&str, &str, &mut LevenshteinBuffer) -> usize;
+
+/// The tokens of a prepared string, see [`Scorer::tokens`].
+enum Tokens<'a> {
+    Whitespace(SplitWhitespace<'a>),
+    Words(UnicodeWords<'a>),
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        match self {
+            Self::Whitespace(tokens) => tokens.next(),
+            Self::Words(tokens) => tokens.next(),
+        }
+    }
+}
+
+/// A similarity that can be blended with others through a list of
+/// [`AlgorithmWeight`]s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Component {
+    /// `1 - distance / length` for an edit distance, using `formula`.
+    Distance(Algorithm),
+    /// [`jaro_winkler_similarity`].
+    JaroWinkler,
+    /// `1 -` the [`affine_gap_distance`] over its worst case.
+    Alignment,
+}
+
+impl Component {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "jaro-winkler" => Ok(Self::JaroWinkler),
+            "alignment" => Ok(Self::Alignment),
+            other => Algorithm::parse(Some(other)).map(Self::Distance),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Distance(algorithm) => algorithm.name(),
+            Self::JaroWinkler => "jaro-winkler",
+            Self::Alignment => "alignment",
+        }
+    }
+}
+
+/// Resolves [`SearchOptions::algorithm`] into the edit distance used for
+/// distances and, for a list, the blend components with weights scaled to
+/// sum to `1.0`.
+fn parse_algorithm(
+    algorithm: Option<&Either<String, Vec<AlgorithmWeight>>>,
+) -> Result<(Algorithm, Vec<(Component, f64)>)> {
+    let entries = match algorithm {
+        None => return Ok((Algorithm::Levenshtein, Vec::new())),
+        // Alignment isn't an edit distance, so alone it is scored as a blend
+        // of one, leaving Levenshtein for `maxDistance`.
+        Some(Either::A(name)) if name == "alignment" => {
+            return Ok((
+                Algorithm::Levenshtein,
+                vec![(Component::Alignment, 1.0)],
+            ))
+        }
+        Some(Either::A(name)) => {
+            return Ok((Algorithm::parse(Some(name))?, Vec::new()))
+        }
+        Some(Either::B(entries)) => entries,
+    };
+
+    let mut blend = entries
+        .iter()
+        .map(|entry| {
+            let weight = entry.weight.unwrap_or(1.0);
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("invalid weight {weight} for {:?}", entry.name),
+                ));
+            }
+            Ok((Component::parse(&entry.name)?, weight))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let total: f64 = blend
+        .iter()
+        .map(|(_, weight)| weight)
+        .sum();
+    if total <= 0.0 {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "an algorithm blend needs at least one positive weight".to_string(),
+        ));
+    }
+    for (_, weight) in &mut blend {
+        *weight /= total;
+    }
+
+    let algorithm = blend
+        .iter()
+        .find_map(|(component, _)| match component {
+            Component::Distance(algorithm) => Some(*algorithm),
+            Component::JaroWinkler | Component::Alignment => None,
+        })
+        .unwrap_or(Algorithm::Levenshtein);
+    Ok((algorithm, blend))
+}
+
+/// How many chars of a `wildcard` query are literals, not `*` or `?`.
+fn wildcard_literals(query: &str) -> usize {
+    query
+        .chars()
+        .filter(|c| !matches!(c, '*' | '?'))
+        .count()
+}
+
+/// Similarity of `item` to a `wildcard` query: one minus the
+/// [`wildcard_distance`] per literal char, or `0.0` if `item` doesn't fit the
+/// pattern. A query of wildcards alone scores `1.0` whenever it fits.
+fn wildcard_similarity(query: &str, item: &str) -> f64 {
+    let literals = wildcard_literals(query);
+    match wildcard_distance(query, item) {
+        None => 0.0,
+        Some(_) if literals == 0 => 1.0,
+        Some(distance) => (1.0 - distance as f64 / literals as f64).max(0.0),
+    }
+}
+
+/// Scoring settings resolved once from [`SearchOptions`] before iterating
+/// over the items of a search.
+pub(crate) struct Scorer {
+    normalization: NormalizationOptions,
+    /// Key of `normalization` in the shared cache, if it is used.
+    cache_key: Option<u64>,
+    algorithm: Algorithm,
+    /// `algorithm` resolved once, so scoring an item doesn't dispatch on the
+    /// options again.
+    distance_fn: DistanceFn,
+    /// Components of a blended score, empty for a single algorithm.
+    blend: Vec<(Component, f64)>,
+    graphemes: bool,
+    /// Split tokens at Unicode word boundaries instead of whitespace.
+    unicode_words: bool,
+    /// Cost of swapping a letter for an accented form of it, if accents are
+    /// kept.
+    accent_cost: Option<f64>,
+    /// `gapOpen` and `gapExtend`, for the alignment similarity.
+    gap_costs: (f64, f64),
+    formula: ScoreFormula,
+    require_all_tokens: bool,
+    exact_substring: bool,
+    wildcard: bool,
+    phonetic: bool,
+    empty_query_returns_all: bool,
+    boost_suffix: f64,
+    prefix_boost_weight: f64,
+    prefix_boost_length: usize,
+    length_penalty: f64,
+    /// Weight of matching word counts, in token-based scoring only.
+    word_count_bonus: f64,
+    /// `scoreFloor` and `scoreCeil`, the band scores are clamped into.
+    score_bounds: (f64, f64),
+    max_distance: Option<usize>,
+    /// Distinct trigrams an item must share with the query to be scored.
+    min_trigram_overlap: usize,
+    token_threshold: f64,
+    /// Weight of each query token relative to the previous one.
+    position_decay: f64,
+    max_input_length: usize,
+    min_query_length: usize,
+}
+
+impl Scorer {
+    pub(crate) fn new(opts: &SearchOptions) -> Result<Self> {
+        if opts.normalizer.is_some() {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "normalizer is only supported by search, searchWithCount, \
+                 searchIndices, searchArrays, bestMatch and score"
+                    .to_string(),
+            ));
+        }
+        Self::build(opts)
+    }
+
+    /// A scorer for strings already run through `opts.normalizer`, which
+    /// leaves them as they are instead of normalizing them again.
+    pub(crate) fn prenormalized(opts: &SearchOptions) -> Result<Self> {
+        Ok(Self {
+            normalization: NormalizationOptions {
+                form: None,
+                strip_diacritics: false,
+                case_fold: false,
+                collapse_whitespace: false,
+                ..Default::default()
+            },
+            cache_key: None,
+            ..Self::build(opts)?
+        })
+    }
+
+    fn build(opts: &SearchOptions) -> Result<Self> {
+        let (algorithm, blend) = parse_algorithm(opts.algorithm.as_ref())?;
+        let graphemes = opts.graphemes.unwrap_or(false);
+        let unicode_words = opts.unicode_words.unwrap_or(false);
+        let accent_cost = check_accent_cost(opts.accent_cost)?;
+        if accent_cost.is_some()
+            && (algorithm != Algorithm::Levenshtein
+                || graphemes
+                || !blend.is_empty())
+        {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "accentCost requires the levenshtein algorithm alone, \
+                 without graphemes"
+                    .to_string(),
+            ));
+        }
+
+        let normalization =
+            resolve_normalization(opts.into(), accent_cost.is_some())?;
+
+        Ok(Self {
+            cache_key: opts
+                .cache_normalization
+                .unwrap_or(false)
+                .then(|| normalization_cache::options_key(&normalization)),
+            normalization,
+            algorithm,
+            distance_fn: algorithm.distance_fn(graphemes, unicode_words),
+            blend,
+            graphemes,
+            unicode_words,
+            accent_cost,
+            gap_costs: (
+                check_gap_cost("gapOpen", opts.gap_open, 1.0)?,
+                check_gap_cost("gapExtend", opts.gap_extend, 0.5)?,
+            ),
+            formula: ScoreFormula::parse(
+                opts.score_formula.as_deref(),
+                opts.score_denominator.as_deref(),
+            )?,
+            require_all_tokens: opts.require_all_tokens.unwrap_or(false),
+            exact_substring: opts.exact_substring.unwrap_or(false),
+            wildcard: opts.wildcard.unwrap_or(false),
+            phonetic: opts.phonetic.unwrap_or(false),
+            empty_query_returns_all: opts
+                .empty_query_returns_all
+                .unwrap_or(false),
+            boost_suffix: check_boost_suffix(opts.boost_suffix)?,
+            prefix_boost_weight: check_prefix_boost_weight(
+                opts.prefix_boost_weight,
+            )?,
+            prefix_boost_length: opts.prefix_boost_length.unwrap_or(4) as usize,
+            length_penalty: check_length_penalty(opts.length_penalty)?,
+            word_count_bonus: check_word_count_bonus(opts.word_count_bonus)?,
+            score_bounds: check_score_bounds(
+                opts.score_floor,
+                opts.score_ceil,
+            )?,
+            max_distance: opts
+                .max_distance
+                .map(|max| max as usize),
+            min_trigram_overlap: opts.min_trigram_overlap.unwrap_or(0) as usize,
+            token_threshold: opts.token_threshold.unwrap_or(0.6),
+            position_decay: check_position_decay(opts.position_decay)?,
+            max_input_length: opts
+                .max_input_length
+                .unwrap_or(DEFAULT_MAX_INPUT_LENGTH)
+                as usize,
+            min_query_length: opts.min_query_length.unwrap_or(0) as usize,
+        })
+    }
+
+    /// Rejects a query longer than `max_input_length`.
+    pub(crate) fn check_query(&self, query: &str) -> Result<()> {
+        check_length(query, self.max_input_length)
+    }
+
+    /// Whether a prepared query is shorter than `min_query_length` and should
+    /// match nothing.
+    pub(crate) fn too_short(&self, query: &str) -> bool {
+        is_shorter_than(query, self.min_query_length)
+    }
+
+    /// Whether an item is too long to be compared and should score `0.0`.
+    pub(crate) fn too_long(&self, item: &str) -> bool {
+        exceeds_length(item, self.max_input_length)
+    }
+
+    /// Normalizes a query or item according to the search options.
+    pub(crate) fn prepare(&self, input: &str) -> String {
+        match self.cache_key {
+            Some(key) => normalization_cache::cached(key, input, || {
+                normalize_with(input, &self.normalization)
+            }),
+            None => normalize_with(input, &self.normalization),
+        }
+    }
+
+    /// Normalizes and scores `item` against a prepared query, or returns
+    /// `None` if it is more than `max_distance` edits away.
+    pub(crate) fn score_raw(&self, query: &str, item: &str) -> Option<f64> {
+        if self.too_long(item) {
+            self.unmeasured_score()
+        } else {
+            self.score_within(query, &self.prepare(item))
+        }
+    }
+
+    /// The score of an item too long to compare: `0.0`, or `None` under
+    /// `max_distance` since the item can't be shown to be close enough.
+    pub(crate) fn unmeasured_score(&self) -> Option<f64> {
+        self.max_distance
+            .is_none()
+            .then_some(0.0)
+    }
+
+    /// Same as [`Scorer::score`], but returns `None` if the item is more than
+    /// `max_distance` edits away.
+    pub(crate) fn score_within(&self, query: &str, item: &str) -> Option<f64> {
+        if !self.overlaps(query, item) {
+            return None;
+        }
+        if let Some(max) = self.max_distance {
+            self.bounded_distance(query, item, max)?;
+        }
+        Some(self.score(query, item))
+    }
+
+    /// Scores a prepared query against a prepared item, including boosts.
+    pub(crate) fn score(&self, query: &str, item: &str) -> f64 {
+        let base = self.base_score(query, item);
+        self.adjust(query, item, base, |_, _| {})
+    }
+
+    /// Whether [`Scorer::score`] gives the pair at least `threshold`.
+    ///
+    /// When no boost can raise the score above the plain edit-distance
+    /// similarity, a score of `threshold` allows at most `(1 - threshold)`
+    /// times the length in edits, so the distance computation stops as soon
+    /// as it passes that bound. Otherwise the pair is scored in full.
+    pub(crate) fn reaches(
+        &self,
+        query: &str,
+        item: &str,
+        threshold: f64,
+    ) -> bool {
+        if !self.overlaps(query, item) {
+            return false;
+        }
+        let bounded = threshold > 0.0
+            && self.blend.is_empty()
+            && !self.require_all_tokens
+            && !self.exact_substring
+            && !self.wildcard
+            && !self.phonetic
+            && self.boost_suffix == 0.0
+            && self.prefix_boost_weight == 0.0
+            && self.word_count_bonus == 0.0
+            && self.score_bounds.0 == 0.0
+            && !self.matches_everything(query);
+        if bounded {
+            // The slack keeps `1 - d / len == threshold` from being rounded
+            // out of the bound.
+            let allowed = (1.0 - threshold) * self.length(query, item) as f64;
+            let max = (allowed + 1e-9).floor() as usize;
+            if query != item
+                && self
+                    .bounded_distance(query, item, max)
+                    .is_none()
+            {
+                return false;
+            }
+        }
+        self.score(query, item) >= threshold
+    }
+
+    /// Applies the penalties and boosts on top of a base score, reporting
+    /// each one that changed the score to `record` with the amount it added.
+    fn adjust(
+        &self,
+        query: &str,
+        item: &str,
+        base: f64,
+        mut record: impl FnMut(&'static str, f64),
+    ) -> f64 {
+        let mut score = base;
+        let mut apply = |score: &mut f64, name, adjusted: f64| {
+            if adjusted != *score {
+                record(name, adjusted - *score);
+                *score = adjusted;
+            }
+        };
+
+        let penalized = score * self.length_factor(query, item);
+        apply(&mut score, "length-penalty", penalized);
+        if self.prefix_boost_weight > 0.0 && !self.matches_everything(query) {
+            let boosted = apply_winkler_boost(
+                score,
+                query,
+                item,
+                self.prefix_boost_weight,
+                self.prefix_boost_length,
+            );
+            apply(&mut score, "prefix", boosted);
+        }
+        let boosted = (score + self.suffix_bonus(query, item)).min(1.0);
+        apply(&mut score, "suffix", boosted);
+        if self.word_count_bonus > 0.0 && self.is_token_based() {
+            let same = self.tokens(query).count() == self.tokens(item).count();
+            let weight = self.word_count_bonus;
+            let blended =
+                (1.0 - weight) * score + if same { weight } else { 0.0 };
+            apply(&mut score, "word-count", blended);
+        }
+        let (floor, ceil) = self.score_bounds;
+        if score > 0.0 && score < floor {
+            apply(&mut score, "floor", floor);
+        }
+        if score > ceil {
+            apply(&mut score, "ceil", ceil);
+        }
+
+        score
+    }
+
+    /// Scores a prepared query against a prepared item, before boosts.
+    fn base_score(&self, query: &str, item: &str) -> f64 {
+        if self.matches_everything(query) {
+            return 1.0;
+        }
+
+        if self.exact_substring {
+            return if item.contains(query) { 1.0 } else { 0.0 };
+        }
+
+        if self.wildcard {
+            return wildcard_similarity(query, item);
+        }
+
+        if self.phonetic && sounds_alike(query, item) {
+            return 1.0;
+        }
+
+        BUFFER.with_borrow_mut(|buffer| {
+            if self.require_all_tokens {
+                self.all_tokens_similarity(query, item, buffer)
+            } else {
+                self.similarity(query, item, buffer)
+            }
+        })
+    }
+
+    /// Explains the score [`Scorer::score`] gives the same pair.
+    pub(crate) fn explain(&self, query: &str, item: &str) -> ScoreExplanation {
+        let mut explanation = self.explain_base(query, item);
+
+        let base = self.base_score(query, item);
+        self.adjust(query, item, base, |name, value| {
+            explanation.boosts.push(ScoreBoost {
+                name: name.to_string(),
+                value,
+            });
+        });
+
+        explanation
+    }
+
+    /// The distance [`Scorer::explain`] reports for the same pair.
+    pub(crate) fn distance_between(&self, query: &str, item: &str) -> u32 {
+        self.explain_base(query, item).distance
+    }
+
+    /// Explains the score [`Scorer::base_score`] gives the same pair.
+    fn explain_base(&self, query: &str, item: &str) -> ScoreExplanation {
+        if self.matches_everything(query) {
+            return ScoreExplanation {
+                distance: 0,
+                max_length: item.len() as u32,
+                algorithm: "empty-query".to_string(),
+                boosts: Vec::new(),
+            };
+        }
+
+        if self.exact_substring {
+            let max_length = query.len().max(item.len());
+            return ScoreExplanation {
+                distance: if item.contains(query) { 0 } else { max_length }
+                    as u32,
+                max_length: max_length as u32,
+                algorithm: "exact-substring".to_string(),
+                boosts: Vec::new(),
+            };
+        }
+
+        if self.wildcard {
+            let literals = wildcard_literals(query);
+            return ScoreExplanation {
+                distance: wildcard_distance(query, item)
+                    .map_or(literals, |distance| distance.min(literals))
+                    as u32,
+                max_length: literals as u32,
+                algorithm: "wildcard".to_string(),
+                boosts: Vec::new(),
+            };
+        }
+
+        if self.phonetic && sounds_alike(query, item) {
+            return ScoreExplanation {
+                distance: 0,
+                max_length: query.len().max(item.len()) as u32,
+                algorithm: "soundex".to_string(),
+                boosts: Vec::new(),
+            };
+        }
+
+        if !self.require_all_tokens {
+            return ScoreExplanation {
+                distance: BUFFER.with_borrow_mut(|buffer| {
+                    self.distance(query, item, buffer)
+                }) as u32,
+                max_length: self.length(query, item) as u32,
+                algorithm: self.algorithm_name(),
+                boosts: Vec::new(),
+            };
+        }
+
+        let (distance, max_length) = self
+            .tokens(query)
+            .map(|token| {
+                BUFFER.with_borrow_mut(|buffer| {
+                    self.best_token_match(token, item, buffer)
+                })
+            })
+            .fold((0, 0), |(distance, max_length), (_, token)| {
+                let token_distance = BUFFER.with_borrow_mut(|buffer| {
+                    self.distance(token.0, token.1, buffer)
+                });
+                (
+                    distance + token_distance,
+                    max_length + self.length(token.0, token.1),
+                )
+            });
+
+        ScoreExplanation {
+            distance: distance as u32,
+            max_length: max_length as u32,
+            algorithm: format!("{}-all-tokens", self.algorithm_name()),
+            boosts: Vec::new(),
+        }
+    }
+
+    /// Edit-distance similarity of a single pair, using `algorithm` and
+    /// `formula`.
+    fn similarity(
+        &self,
+        a: &str,
+        b: &str,
+        buffer: &mut LevenshteinBuffer,
+    ) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+
+        if !self.blend.is_empty() {
+            return self
+                .blend
+                .iter()
+                .map(|&(component, weight)| {
+                    weight * self.component_similarity(component, a, b, buffer)
+                })
+                .sum();
+        }
+
+        let distance = match self.accent_cost {
+            Some(cost) => self.accent_weighted_distance(a, b, cost),
+            None => self.distance(a, b, buffer) as f64,
+        };
+        (1.0 - distance / self.length(a, b) as f64).max(0.0)
+    }
+
+    /// Similarity of a non-empty pair under one blend component.
+    fn component_similarity(
+        &self,
+        component: Component,
+        a: &str,
+        b: &str,
+        buffer: &mut LevenshteinBuffer,
+    ) -> f64 {
+        match component {
+            Component::Distance(algorithm) => {
+                let distance_fn =
+                    algorithm.distance_fn(self.graphemes, self.unicode_words);
+                let distance = distance_fn(a, b, buffer);
+                let length = self.length_by(algorithm, a, b);
+                (1.0 - distance as f64 / length as f64).max(0.0)
+            }
+            Component::JaroWinkler => jaro_winkler_similarity(a, b),
+            Component::Alignment => {
+                let (open, extend) = self.gap_costs;
+                let (n, m) = (a.chars().count(), b.chars().count());
+                // Substituting the shorter string and one gap for the rest is
+                // always an alignment, so this bounds the cost. Strings of
+                // equal length need no gap at all.
+                let worst = if n == m {
+                    n as f64
+                } else {
+                    n.min(m) as f64 + open + (n.abs_diff(m) - 1) as f64 * extend
+                };
+                if worst == 0.0 {
+                    return 1.0;
+                }
+                let distance = affine_gap_distance(a, b, open, extend);
+                (1.0 - distance / worst).max(0.0)
+            }
+        }
+    }
+
+    /// The algorithm name reported by [`Scorer::explain`], e.g.
+    /// `"levenshtein-ratio"` or `"0.5*osa+0.5*jaro-winkler"` for a blend.
+    fn algorithm_name(&self) -> String {
+        let suffixed = |name: &str| {
+            let mut name = name.to_string();
+            if self.accent_cost.is_some() {
+                name.push_str("-accent-weighted");
+            }
+            if self.formula == ScoreFormula::Ratio {
+                name.push_str("-ratio");
+            }
+            name
+        };
+
+        if self.blend.is_empty() {
+            return suffixed(self.algorithm.name());
+        }
+        if let [(Component::Alignment, _)] = self.blend[..] {
+            return Component::Alignment.name().to_string();
+        }
+        self.blend
+            .iter()
+            .map(|&(component, weight)| match component {
+                Component::Distance(_) => {
+                    format!("{weight}*{}", suffixed(component.name()))
+                }
+                Component::JaroWinkler | Component::Alignment => {
+                    format!("{weight}*{}", component.name())
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+
+    /// Levenshtein distance where substituting a letter for an accented form
+    /// of the same letter costs `cost` instead of a full edit.
+    fn accent_weighted_distance(&self, a: &str, b: &str, cost: f64) -> f64 {
+        weighted_levenshtein_distance(a, b, |x, y| {
+            if base_letter(x) == base_letter(y) {
+                cost
+            } else {
+                1.0
+            }
+        })
+    }
+
+    /// Edit distance of a single pair, using `algorithm` over `char`s or,
+    /// with `graphemes`, over grapheme clusters.
+    fn distance(
+        &self,
+        a: &str,
+        b: &str,
+        buffer: &mut LevenshteinBuffer,
+    ) -> usize {
+        if self.algorithm == Algorithm::Levenshtein && !self.graphemes {
+            return QUERY.with_borrow_mut(|query| {
+                query.prepare(a);
+                levenshtein_distance_prepared(query, b, buffer)
+            });
+        }
+        (self.distance_fn)(a, b, buffer)
+    }
+
+    /// Same as [`Scorer::distance`], or `None` once it is known to exceed
+    /// `max`. Only plain Levenshtein can stop early; the others are computed
+    /// in full and then compared. With `accent_cost`, the weighted distance
+    /// is compared instead.
+    fn bounded_distance(&self, a: &str, b: &str, max: usize) -> Option<usize> {
+        if self.wildcard {
+            return wildcard_distance(a, b).filter(|&distance| distance <= max);
+        }
+        if let Some(cost) = self.accent_cost {
+            let distance = self.accent_weighted_distance(a, b, cost);
+            return (distance <= max as f64).then(|| distance.ceil() as usize);
+        }
+
+        BUFFER.with_borrow_mut(|buffer| {
+            if self.algorithm == Algorithm::Levenshtein && !self.graphemes {
+                return levenshtein_distance_bounded_with(a, b, max, buffer);
+            }
+            Some(self.distance(a, b, buffer))
+                .filter(|&distance| distance <= max)
+        })
+    }
+
+    /// The length a distance between `a` and `b` is divided by, counting
+    /// bytes or, with `graphemes`, grapheme clusters.
+    fn length(&self, a: &str, b: &str) -> usize {
+        self.length_by(self.algorithm, a, b)
+    }
+
+    /// Same as [`Scorer::length`] for a distance under `algorithm`; word-level
+    /// distances are divided by a number of words, at least one.
+    fn length_by(&self, algorithm: Algorithm, a: &str, b: &str) -> usize {
+        let length = |s: &str| match algorithm {
+            Algorithm::WordLevel => self.tokens(s).count(),
+            _ if self.graphemes => s.graphemes(true).count(),
+            _ => s.len(),
+        };
+        let length = self
+            .formula
+            .length(length(a), length(b));
+        if algorithm == Algorithm::WordLevel {
+            length.max(1)
+        } else {
+            length
+        }
+    }
+
+    /// The `length_penalty` factor: the ratio of the shorter to the longer
+    /// length, raised to `length_penalty`.
+    fn length_factor(&self, query: &str, item: &str) -> f64 {
+        if self.length_penalty == 0.0 || self.matches_everything(query) {
+            return 1.0;
+        }
+
+        let (a, b) = (query.chars().count(), item.chars().count());
+        if a.max(b) == 0 {
+            return 1.0;
+        }
+        (a.min(b) as f64 / a.max(b) as f64).powf(self.length_penalty)
+    }
+
+    /// Splits a prepared string into the tokens that token-based scoring
+    /// compares: whitespace-separated, or the words between Unicode word
+    /// boundaries with `unicode_words`.
+    fn tokens<'a>(&self, s: &'a str) -> Tokens<'a> {
+        if self.unicode_words {
+            Tokens::Words(s.unicode_words())
+        } else {
+            Tokens::Whitespace(s.split_whitespace())
+        }
+    }
+
+    /// Whether scores compare whole words, so `word_count_bonus` applies.
+    fn is_token_based(&self) -> bool {
+        self.require_all_tokens || self.algorithm == Algorithm::WordLevel
+    }
+
+    /// The `boost_suffix` bonus, if `item` ends with a non-empty `query`.
+    fn suffix_bonus(&self, query: &str, item: &str) -> f64 {
+        if self.boost_suffix > 0.0 && !query.is_empty() && item.ends_with(query)
+        {
+            self.boost_suffix
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether `item` shares `min_trigram_overlap` trigrams with `query`.
+    /// Without trigrams to compare, an empty query or a `wildcard` or
+    /// `exactSubstring` one lets every item through.
+    fn overlaps(&self, query: &str, item: &str) -> bool {
+        query.is_empty()
+            || self.wildcard
+            || self.exact_substring
+            || shares_trigrams(query, item, self.min_trigram_overlap)
+    }
+
+    /// Whether `query` is empty and `empty_query_returns_all` passes every
+    /// item through.
+    fn matches_everything(&self, query: &str) -> bool {
+        self.empty_query_returns_all && query.is_empty()
+    }
+
+    /// Requires every query token to fuzzily match some item token with at
+    /// least `token_threshold`, scoring the average of the best matches,
+    /// weighted by `position_decay`. Returns `0.0` as soon as one query token
+    /// has no good enough match.
+    fn all_tokens_similarity(
+        &self,
+        query: &str,
+        item: &str,
+        buffer: &mut LevenshteinBuffer,
+    ) -> f64 {
+        let mut total = 0.0;
+        let mut total_weight = 0.0;
+        let mut weight = 1.0;
+
+        for token in self.tokens(query) {
+            let (score, _) = self.best_token_match(token, item, buffer);
+            if score < self.token_threshold {
+                return 0.0;
+            }
+            total += weight * score;
+            total_weight += weight;
+            weight *= self.position_decay;
+        }
+
+        if total_weight == 0.0 {
+            return self.similarity(query, item, buffer);
+        }
+
+        total / total_weight
+    }
+
+    /// How well each token of `query` matches its most similar token of
+    /// `item`, in query order, as [`Scorer::all_tokens_similarity`] scores
+    /// them before averaging.
+    pub(crate) fn token_scores(&self, query: &str, item: &str) -> Vec<f64> {
+        BUFFER.with_borrow_mut(|buffer| {
+            self.tokens(query)
+                .map(|token| {
+                    self.best_token_match(token, item, buffer)
+                        .0
+                })
+                .collect()
+        })
+    }
+
+    /// Finds the item token most similar to `token`, returning its score
+    /// along with the matched pair.
+    fn best_token_match<'a>(
+        &self,
+        token: &'a str,
+        item: &'a str,
+        buffer: &mut LevenshteinBuffer,
+    ) -> (f64, (&'a str, &'a str)) {
+        self.tokens(item)
+            .map(|candidate| {
+                (
+                    self.similarity(token, candidate, buffer),
+                    (token, candidate),
+                )
+            })
+            .fold((0.0, (token, "")), |best, candidate| {
+                if candidate.0 > best.0 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+/// Rejects an `accent_cost` outside `0.0..=1.0`.
+fn check_accent_cost(cost: Option<f64>) -> Result<Option<f64>> {
+    match cost {
+        Some(cost) if !(0.0..=1.0).contains(&cost) => Err(Error::new(
+            Status::InvalidArg,
+            format!("accentCost must be between 0 and 1, got {cost}"),
+        )),
+        _ => Ok(cost),
+    }
+}
+
+/// Rejects a negative or non-finite gap cost, defaulting to `default`.
+fn check_gap_cost(name: &str, cost: Option<f64>, default: f64) -> Result<f64> {
+    match cost.unwrap_or(default) {
+        cost if cost.is_finite() && cost >= 0.0 => Ok(cost),
+        cost => Err(Error::new(
+            Status::InvalidArg,
+            format!("{name} must be a non-negative number, got {cost}"),
+        )),
+    }
+}
+
+/// Rejects a `position_decay` outside `(0.0, 1.0]`, defaulting to `1.0`.
+fn check_position_decay(decay: Option<f64>) -> Result<f64> {
+    match decay.unwrap_or(1.0) {
+        decay if decay > 0.0 && decay <= 1.0 => Ok(decay),
+        decay => Err(Error::new(
+            Status::InvalidArg,
+            format!("positionDecay must be in (0, 1], got {decay}"),
+        )),
+    }
+}
+
+/// Validates the `length_penalty` option, defaulting to `0.0`.
+fn check_length_penalty(exponent: Option<f64>) -> Result<f64> {
+    match exponent.unwrap_or(0.0) {
+        exponent if exponent >= 0.0 && exponent.is_finite() => Ok(exponent),
+        exponent => Err(Error::new(
+            Status::InvalidArg,
+            format!("lengthPenalty must not be negative, got {exponent}"),
+        )),
+    }
+}
+
+/// Validates the `boost_suffix` option, defaulting to `0.0`.
+fn check_boost_suffix(bonus: Option<f64>) -> Result<f64> {
+    match bonus.unwrap_or(0.0) {
+        bonus if bonus >= 0.0 && bonus.is_finite() => Ok(bonus),
+        bonus => Err(Error::new(
+            Status::InvalidArg,
+            format!("boostSuffix must not be negative, got {bonus}"),
+        )),
+    }
+}
+
+/// Validates the `prefix_boost_weight` option, defaulting to `0.0`.
+fn check_prefix_boost_weight(weight: Option<f64>) -> Result<f64> {
+    match weight.unwrap_or(0.0) {
+        weight if weight >= 0.0 && weight.is_finite() => Ok(weight),
+        weight => Err(Error::new(
+            Status::InvalidArg,
+            format!("prefixBoostWeight must not be negative, got {weight}"),
+        )),
+    }
+}
+
+/// Validates the `word_count_bonus` option, defaulting to `0.0`.
+fn check_word_count_bonus(weight: Option<f64>) -> Result<f64> {
+    match weight.unwrap_or(0.0) {
+        weight if (0.0..=1.0).contains(&weight) => Ok(weight),
+        weight => Err(Error::new(
+            Status::InvalidArg,
+            format!("wordCountBonus must be in [0, 1], got {weight}"),
+        )),
+    }
+}
+
+/// Validates the `score_floor` and `score_ceil` options, defaulting to `0.0`
+/// and `1.0`.
+fn check_score_bounds(
+    floor: Option<f64>,
+    ceil: Option<f64>,
+) -> Result<(f64, f64)> {
+    let floor = floor.unwrap_or(0.0);
+    let ceil = ceil.unwrap_or(1.0);
+    for (name, bound) in [("scoreFloor", floor), ("scoreCeil", ceil)] {
+        if !(0.0..=1.0).contains(&bound) {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("{name} must be in [0, 1], got {bound}"),
+            ));
+        }
+    }
+    if floor > ceil {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+                "scoreFloor must not exceed scoreCeil, got {floor} > {ceil}"
+            ),
+        ));
+    }
+    Ok((floor, ceil))
+}
+
+/// Returns `true` if `input` has fewer than `min_length` characters.
+pub(crate) fn is_shorter_than(input: &str, min_length: usize) -> bool {
+    // Every char takes at least one byte, so only count when needed.
+    input.len() < min_length
+        || input.chars().take(min_length).count() < min_length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_tokens() -> Scorer {
+        Scorer::new(&SearchOptions {
+            require_all_tokens: Some(true),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exact_substring_skips_edit_distance() {
+        let scorer = Scorer::new(&SearchOptions {
+            exact_substring: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(scorer.score("app", "pineapple"), 1.0);
+        assert_eq!(scorer.score("apple", "appel"), 0.0);
+    }
+
+    #[test]
+    fn test_alignment_prefers_one_long_gap() {
+        let scorer = |algorithm: &str| {
+            Scorer::new(&SearchOptions {
+                algorithm: Some(Either::A(algorithm.to_string())),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let (alignment, levenshtein) =
+            (scorer("alignment"), scorer("levenshtein"));
+
+        let one_gap = alignment.score("abcdefgh", "abcdxxxxefgh");
+        let scattered = alignment.score("abcdefgh", "axbcxdexfgxh");
+        assert!(one_gap > scattered, "{one_gap} <= {scattered}");
+        assert!((one_gap - (1.0 - 2.5 / 10.5)).abs() < 1e-9);
+        assert_eq!(
+            levenshtein.score("abcdefgh", "abcdxxxxefgh"),
+            levenshtein.score("abcdefgh", "axbcxdexfgxh")
+        );
+        assert_eq!(
+            alignment
+                .explain("abc", "abd")
+                .algorithm,
+            "alignment"
+        );
+
+        // Equal lengths need no gap, so nothing in common scores zero.
+        assert_eq!(alignment.score("abc", "xyz"), 0.0);
+        assert_eq!(alignment.score("a", "b"), 0.0);
+        assert!((alignment.score("abc", "abd") - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(alignment.score("", ""), 1.0);
+
+        assert!(Scorer::new(&SearchOptions {
+            gap_extend: Some(-1.0),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_wildcard_scores_literal_parts() {
+        let scorer = Scorer::new(&SearchOptions {
+            wildcard: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(scorer.score("a*e", "apple"), 1.0);
+        assert_eq!(scorer.score("a?e", "ace"), 1.0);
+        assert_eq!(scorer.score("a?e", "apple"), 0.0);
+        assert!((scorer.score("app*", "aple pie") - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(scorer.score("??", "a"), 0.0);
+        assert_eq!(scorer.explain("a*e", "apple").algorithm, "wildcard");
+    }
+
+    #[test]
+    fn test_word_count_bonus_breaks_near_ties() {
+        let scorer = |word_count_bonus| {
+            Scorer::new(&SearchOptions {
+                require_all_tokens: Some(true),
+                word_count_bonus: Some(word_count_bonus),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let plain = scorer(0.0);
+        let bonus = scorer(0.05);
+
+        // Every query word matches in both, so only the bonus tells them
+        // apart.
+        assert_eq!(plain.score("john smith", "john smith jr"), 1.0);
+        assert_eq!(plain.score("john smith", "john smith"), 1.0);
+        assert!(
+            bonus.score("john smith", "john smith")
+                > bonus.score("john smith", "john smith jr")
+        );
+
+        // A small bonus doesn't outweigh a worse textual match.
+        assert!(
+            bonus.score("john smith", "john smith jr")
+                > bonus.score("john smith", "jon smth")
+        );
+
+        let explanation = bonus.explain("john smith", "john smith jr");
+        assert_eq!(explanation.boosts[0].name, "word-count");
+        assert!(explanation.boosts[0].value < 0.0);
+
+        // Character-level scoring ignores the bonus.
+        let chars = Scorer::new(&SearchOptions {
+            word_count_bonus: Some(0.5),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(chars.score("john", "john smith"), 0.4);
+        assert!(Scorer::new(&SearchOptions {
+            word_count_bonus: Some(1.5),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_score_bounds_clamp_scores() {
+        let scorer = |score_floor, score_ceil| {
+            Scorer::new(&SearchOptions {
+                score_floor,
+                score_ceil,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let plain = scorer(None, None);
+        let clamped = scorer(Some(0.7), Some(0.9));
+
+        assert_eq!(plain.score("apple", "apple"), 1.0);
+        assert_eq!(clamped.score("apple", "apple"), 0.9);
+        assert_eq!(plain.score("apple", "appla"), 0.8);
+        assert_eq!(clamped.score("apple", "appla"), 0.8);
+        assert_eq!(plain.score("apple", "appxx"), 0.6);
+        assert_eq!(clamped.score("apple", "appxx"), 0.7);
+        // Nothing in common stays at zero.
+        assert_eq!(plain.score("apple", "zzzzz"), 0.0);
+        assert_eq!(clamped.score("apple", "zzzzz"), 0.0);
+
+        // The raised score passes a threshold the plain one misses, even
+        // where the distance alone would rule the pair out.
+        assert!(!plain.reaches("apple", "appxx", 0.7));
+        assert!(clamped.reaches("apple", "appxx", 0.7));
+        assert!(!clamped.reaches("apple", "apple", 0.95));
+
+        let explanation = clamped.explain("apple", "appxx");
+        assert_eq!(explanation.boosts[0].name, "floor");
+        assert!((explanation.boosts[0].value - 0.1).abs() < 1e-9);
+        assert_eq!(clamped.explain("apple", "apple").boosts[0].name, "ceil");
+
+        for (floor, ceil) in [(-0.1, 1.0), (0.0, 1.5), (0.6, 0.5)] {
+            assert!(Scorer::new(&SearchOptions {
+                score_floor: Some(floor),
+                score_ceil: Some(ceil),
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_unicode_words_split_punctuation_and_cjk() {
+        let scorer = |algorithm: &str, unicode_words| {
+            Scorer::new(&SearchOptions {
+                algorithm: Some(Either::A(algorithm.to_string())),
+                require_all_tokens: Some(algorithm != "word-level"),
+                unicode_words: Some(unicode_words),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let whitespace = scorer("levenshtein", false);
+        let words = scorer("levenshtein", true);
+
+        // Whitespace keeps "new-york" as one token that neither query word
+        // matches well enough.
+        assert_eq!(whitespace.score("new york", "new-york city"), 0.0);
+        assert_eq!(words.score("new york", "new-york city"), 1.0);
+
+        // Without spaces the whole run is one token; word boundaries split
+        // off each ideograph and keep the katakana together.
+        let mixed = "東京タワー tokyo";
+        assert_eq!(
+            whitespace
+                .token_scores("東京", mixed)
+                .len(),
+            1
+        );
+        assert!(whitespace.token_scores("東京", mixed)[0] < 1.0);
+        assert_eq!(words.token_scores("東京", mixed), vec![1.0, 1.0]);
+        assert_eq!(words.token_scores("タワー", mixed), vec![1.0]);
+
+        // Punctuation isn't part of any word, and quoted words lose their
+        // quotes.
+        assert_eq!(
+            scorer("word-level", false).score("hello, world", "hello world"),
+            0.5
+        );
+        assert_eq!(
+            scorer("word-level", true).score("hello, world", "\"hello\" world"),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_too_short_counts_chars() {
+        let scorer = Scorer::new(&SearchOptions {
+            min_query_length: Some(2),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(scorer.too_short(""));
+        assert!(scorer.too_short("é"));
+        assert!(!scorer.too_short("ab"));
+    }
+
+    #[test]
+    fn test_empty_query_is_configurable() {
+        let scorer = |empty_query_returns_all| {
+            Scorer::new(&SearchOptions {
+                empty_query_returns_all,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        assert_eq!(scorer(None).score("", "apple"), 0.0);
+        assert_eq!(scorer(Some(true)).score("", "apple"), 1.0);
+        assert_eq!(scorer(Some(true)).score("x", "apple"), 0.0);
+    }
+
+    #[test]
+    fn test_ratio_is_gentler_on_short_strings() {
+        let scorer = |score_formula: &str| {
+            Scorer::new(&SearchOptions {
+                score_formula: Some(score_formula.to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        // One substitution in four characters.
+        assert_eq!(scorer("distance").score("abcd", "abce"), 0.75);
+        assert_eq!(scorer("ratio").score("abcd", "abce"), 0.875);
+        // Both formulas agree on identical and completely different pairs.
+        assert_eq!(scorer("ratio").score("same", "same"), 1.0);
+        assert_eq!(scorer("ratio").score("abc", ""), 0.0);
+
+        let explanation = scorer("ratio").explain("abcd", "abce");
+        assert_eq!(explanation.distance, 1);
+        assert_eq!(explanation.max_length, 8);
+        assert_eq!(explanation.algorithm, "levenshtein-ratio");
+    }
+
+    #[test]
+    fn test_unknown_score_formula_is_rejected() {
+        let options = SearchOptions {
+            score_formula: Some("cosine".to_string()),
+            ..Default::default()
+        };
+        assert!(Scorer::new(&options).is_err());
+    }
+
+    #[test]
+    fn test_suffix_boost_is_capped() {
+        let scorer = Scorer::new(&SearchOptions {
+            boost_suffix: Some(0.5),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(scorer.score("pdf", "report.pdf"), 0.8);
+        assert_eq!(scorer.score("pdf", "pdfs"), 0.75);
+        assert_eq!(scorer.score("apple", "pineapple"), 1.0);
+
+        let explanation = scorer.explain("pdf", "report.pdf");
+        assert_eq!(explanation.boosts[0].name, "suffix");
+        assert_eq!(explanation.boosts[0].value, 0.5);
+
+        for boost_suffix in [-0.1, f64::NAN, f64::INFINITY] {
+            assert!(Scorer::new(&SearchOptions {
+                boost_suffix: Some(boost_suffix),
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_prefix_boost_is_small_and_bounded() {
+        let scorer = |prefix_boost_weight, prefix_boost_length| {
+            Scorer::new(&SearchOptions {
+                prefix_boost_weight,
+                prefix_boost_length,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let plain = scorer(None, None);
+        let boosted = scorer(Some(0.1), None);
+
+        // "martha"/"marhta" share "mar": 3 * 0.1 of the remaining gap.
+        let base = plain.score("martha", "marhta");
+        let score = boosted.score("martha", "marhta");
+        assert!((score - (base + 0.3 * (1.0 - base))).abs() < 1e-12);
+        assert!(score - base < 0.15);
+        // No shared prefix, no boost.
+        assert_eq!(
+            boosted.score("amrtha", "martha"),
+            plain.score("amrtha", "martha")
+        );
+
+        // The prefix counts at most `prefix_boost_length` characters, and the
+        // boosted score never exceeds `1.0`.
+        let long = ("abcdefgh", "abcdefgx");
+        let short = scorer(Some(0.1), Some(2)).score(long.0, long.1);
+        assert!(short < boosted.score(long.0, long.1));
+        assert_eq!(scorer(Some(1.0), None).score(long.0, long.1), 1.0);
+
+        let explanation = boosted.explain("martha", "marhta");
+        assert_eq!(explanation.boosts[0].name, "prefix");
+        assert!(Scorer::new(&SearchOptions {
+            prefix_boost_weight: Some(-0.1),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_length_penalty_suppresses_length_mismatch() {
+        let scorer = |length_penalty| {
+            Scorer::new(&SearchOptions {
+                length_penalty,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        let plain = scorer(None).score("cat", "cataclysmic");
+        let penalized = scorer(Some(1.0)).score("cat", "cataclysmic");
+        assert_eq!(penalized, plain * 3.0 / 11.0);
+        assert_eq!(
+            scorer(Some(1.0)).score("cat", "car"),
+            scorer(None).score("cat", "car")
+        );
+
+        let explanation = scorer(Some(1.0)).explain("cat", "cataclysmic");
+        assert_eq!(explanation.boosts[0].name, "length-penalty");
+        assert!(explanation.boosts[0].value < 0.0);
+    }
+
+    #[test]
+    fn test_invalid_length_penalty_is_rejected() {
+        for length_penalty in [-1.0, f64::NAN, f64::INFINITY] {
+            assert!(Scorer::new(&SearchOptions {
+                length_penalty: Some(length_penalty),
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_max_distance_filters_far_items() {
+        let scorer = Scorer::new(&SearchOptions {
+            max_distance: Some(2),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(scorer.score_raw("kitten", "Sitten"), Some(5.0 / 6.0));
+        assert_eq!(scorer.score_raw("kitten", "sitting"), None);
+        assert_eq!(scorer.score_raw("kitten", &"k".repeat(20_000)), None);
+    }
+
+    #[test]
+    fn test_osa_counts_transpositions_as_one_edit() {
+        let scorer = |algorithm: &str| {
+            Scorer::new(&SearchOptions {
+                algorithm: Some(Either::A(algorithm.to_string())),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        assert_eq!(scorer("osa").score("form", "from"), 0.75);
+        assert_eq!(scorer("levenshtein").score("form", "from"), 0.5);
+
+        let explanation = scorer("osa").explain("form", "from");
+        assert_eq!(explanation.distance, 1);
+        assert_eq!(explanation.algorithm, "osa");
+        assert!(Scorer::new(&SearchOptions {
+            algorithm: Some(Either::A("damerau".to_string())),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_word_level_counts_whole_words() {
+        let scorer = |options: SearchOptions| {
+            Scorer::new(&SearchOptions {
+                algorithm: Some(Either::A("word-level".to_string())),
+                ..options
+            })
+            .unwrap()
+        };
+        let words = scorer(SearchOptions::default());
+
+        // One deleted word out of three, however long the word.
+        assert_eq!(words.score("the quick fox", "the fox"), 1.0 - 1.0 / 3.0);
+        assert_eq!(
+            words.score("the extraordinarily quick fox", "the quick fox"),
+            0.75
+        );
+        assert_eq!(words.score("the fox", "the box"), 0.5);
+        assert_eq!(words.score("fox the", "the fox"), 0.0);
+
+        let explanation = words.explain("the quick fox", "the fox");
+        assert_eq!(explanation.distance, 1);
+        assert_eq!(explanation.max_length, 3);
+        assert_eq!(explanation.algorithm, "word-level");
+
+        let bounded = scorer(SearchOptions {
+            max_distance: Some(1),
+            ..Default::default()
+        });
+        assert!(bounded
+            .score_within("the quick brown fox", "the fox")
+            .is_none());
+        assert!(bounded
+            .score_within("the quick fox", "the fox")
+            .is_some());
+    }
+
+    #[test]
+    fn test_phonetic_matches_score_full_marks() {
+        let scorer = Scorer::new(&SearchOptions {
+            phonetic: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(scorer.score("robert", "rupert"), 1.0);
+        assert_eq!(
+            scorer
+                .explain("robert", "rupert")
+                .algorithm,
+            "soundex"
+        );
+        assert_eq!(scorer.score("robert", "rogers"), 1.0 - 2.0 / 6.0);
+        assert!(
+            Scorer::new(&SearchOptions::default())
+                .unwrap()
+                .score("robert", "rupert")
+                < 1.0
+        );
+    }
+
+    #[test]
+    fn test_graphemes_count_emoji_as_one_character() {
+        let scorer = |graphemes| {
+            Scorer::new(&SearchOptions {
+                graphemes,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let (query, item) = ("flag 🇺🇸", "flag 🇫🇷");
+
+        assert_eq!(scorer(Some(true)).score(query, item), 1.0 - 1.0 / 6.0);
+        let explanation = scorer(Some(true)).explain(query, item);
+        assert_eq!((explanation.distance, explanation.max_length), (1, 6));
+        assert_eq!(
+            scorer(None)
+                .explain(query, item)
+                .distance,
+            2
+        );
+    }
+
+    #[test]
+    fn test_accent_cost_gives_partial_credit() {
+        let score = |accent_cost| {
+            let scorer = Scorer::new(&SearchOptions {
+                accent_cost,
+                ..Default::default()
+            })
+            .unwrap();
+            scorer.score(&scorer.prepare("resume"), &scorer.prepare("Résumé"))
+        };
+
+        let stripped = score(None);
+        let partial = score(Some(0.25));
+        let naive = score(Some(1.0));
+        assert_eq!(stripped, 1.0);
+        assert!(naive < partial && partial < stripped);
+        assert_eq!(score(Some(0.0)), 1.0);
+
+        assert!(Scorer::new(&SearchOptions {
+            accent_cost: Some(1.5),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(Scorer::new(&SearchOptions {
+            accent_cost: Some(0.5),
+            algorithm: Some(Either::A("osa".to_string())),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_score_denominator_changes_length_used() {
+        let score = |query: &str, item: &str, denominator: &str| {
+            let scorer = Scorer::new(&SearchOptions {
+                score_denominator: Some(denominator.to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+            scorer.score(query, item)
+        };
+
+        // Two insertions turn "app" into "apple".
+        assert_eq!(score("app", "apple", "max"), 1.0 - 2.0 / 5.0);
+        assert_eq!(score("app", "apple", "min"), 1.0 - 2.0 / 3.0);
+        assert_eq!(score("app", "apple", "query"), 1.0 - 2.0 / 3.0);
+        assert_eq!(score("app", "apple", "item"), 1.0 - 2.0 / 5.0);
+        assert_eq!(score("apple", "app", "query"), 1.0 - 2.0 / 5.0);
+        assert_eq!(score("apple", "app", "item"), 1.0 - 2.0 / 3.0);
+        assert_eq!(score("a", "xyz", "min"), 0.0);
+
+        for (formula, denominator) in [("distance", "sum"), ("ratio", "min")] {
+            assert!(Scorer::new(&SearchOptions {
+                score_formula: Some(formula.to_string()),
+                score_denominator: Some(denominator.to_string()),
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_blend_is_weighted_mean_of_components() {
+        let blend = |entries: &[(&str, f64)]| {
+            let algorithm = entries
+                .iter()
+                .map(|&(name, weight)| AlgorithmWeight {
+                    name: name.to_string(),
+                    weight: Some(weight),
+                })
+                .collect();
+            Scorer::new(&SearchOptions {
+                algorithm: Some(Either::B(algorithm)),
+                ..Default::default()
+            })
+        };
+        let (a, b) = ("martha", "marhta");
+        let levenshtein = 1.0 - 2.0 / 6.0;
+        let osa = 1.0 - 1.0 / 6.0;
+        let jaro_winkler = jaro_winkler_similarity(a, b);
+
+        let scorer =
+            blend(&[("levenshtein", 1.0), ("jaro-winkler", 3.0)]).unwrap();
+        let expected = 0.25 * levenshtein + 0.75 * jaro_winkler;
+        assert!((scorer.score(a, b) - expected).abs() < 1e-12);
+        assert_eq!(
+            scorer.explain(a, b).algorithm,
+            "0.25*levenshtein+0.75*jaro-winkler"
+        );
+
+        let scorer = blend(&[("osa", 0.5), ("levenshtein", 0.5)]).unwrap();
+        assert!((scorer.score(a, b) - (osa + levenshtein) / 2.0).abs() < 1e-12);
+        // The first edit distance in the blend is the one reported.
+        assert_eq!(scorer.explain(a, b).distance, 1);
+
+        assert!(blend(&[]).is_err());
+        assert!(blend(&[("levenshtein", 0.0)]).is_err());
+        assert!(blend(&[("levenshtein", -1.0), ("osa", 2.0)]).is_err());
+        assert!(blend(&[("soundex", 1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_position_decay_favors_early_tokens() {
+        let decayed = |decay: f64| {
+            Scorer::new(&SearchOptions {
+                require_all_tokens: Some(true),
+                position_decay: Some(decay),
+                ..Default::default()
+            })
+        };
+
+        let flat = decayed(1.0).unwrap();
+        assert_eq!(flat.score("red car", "red cart"), 0.875);
+        assert_eq!(flat.score("car red", "red cart"), 0.875);
+
+        let halved = decayed(0.5).unwrap();
+        let in_order = halved.score("red car", "red cart");
+        let reordered = halved.score("car red", "red cart");
+        assert!((in_order - 2.75 / 3.0).abs() < 1e-12);
+        assert!((reordered - 2.5 / 3.0).abs() < 1e-12);
+
+        assert!(decayed(0.0).is_err());
+        assert!(decayed(1.5).is_err());
+        assert!(decayed(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_reaches_agrees_with_score() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("apple", "aple"),
+            ("abcde", "abcdx"),
+            ("", "abc"),
+            ("same", "same"),
+            ("xyz", "abc"),
+        ];
+        let variants = [
+            SearchOptions::default(),
+            SearchOptions {
+                score_formula: Some("ratio".to_string()),
+                ..Default::default()
+            },
+            SearchOptions {
+                boost_suffix: Some(0.2),
+                length_penalty: Some(1.0),
+                ..Default::default()
+            },
+            SearchOptions {
+                algorithm: Some(Either::A("osa".to_string())),
+                ..Default::default()
+            },
+        ];
+
+        for opts in &variants {
+            let scorer = Scorer::new(opts).unwrap();
+            for (a, b) in pairs {
+                let score = scorer.score(a, b);
+                for threshold in [0.0, 0.5, 0.8, score, score + 1e-9, 1.0] {
+                    assert_eq!(
+                        scorer.reaches(a, b, threshold),
+                        score >= threshold,
+                        "{a:?} vs {b:?} at {threshold}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_tokens_rejects_missing_token() {
+        assert_eq!(all_tokens().score("red car", "red bicycle"), 0.0);
+    }
+
+    #[test]
+    fn test_all_tokens_accepts_fuzzy_tokens() {
+        let score = all_tokens().score("red car", "reed cart");
+        assert_eq!(score, 0.75);
+    }
+}
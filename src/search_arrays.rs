@@ -0,0 +1,25 @@
+use napi::bindgen_prelude::{Float64Array, Uint32Array};
+use napi_derive::napi;
+
+/// The results of [`crate::search_arrays`] as two parallel typed arrays.
+///
+/// Result `i` is the item at `indices[i]` of the searched array, scoring
+/// `scores[i]`; both arrays have one entry per result, best first, in the
+/// order [`crate::search`] returns them. Each array crosses into JS as one
+/// buffer instead of one object per result.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface SearchArrays {
+///   indices: Uint32Array; // index of each result's item in `items`
+///   scores: Float64Array; // score of each result, same order
+/// }
+/// ```
+#[napi(object)]
+pub struct SearchArrays {
+    /// Index of each result's item in the original input collection.
+    pub indices: Uint32Array,
+
+    /// The similarity score of each result, in the same order as `indices`.
+    pub scores: Float64Array,
+}
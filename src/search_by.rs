@@ -0,0 +1,192 @@
+use napi::bindgen_prelude::*;
+
+use crate::ranking::Ranking;
+use crate::scorer::Scorer;
+use crate::{
+    check_item_arrays, check_limit, filter_map_items, item_weight, rank,
+    round_score, SearchByResult, SearchKey, SearchOptions,
+};
+
+/// How [`crate::search_by`] turns per-field scores into one record score,
+/// selected by [`SearchOptions::combine_fields`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum FieldCombine {
+    /// The highest weighted field score.
+    Max,
+    /// The sum of all weighted field scores.
+    Sum,
+}
+
+impl FieldCombine {
+    fn parse(name: Option<&str>) -> Result<Self> {
+        match name.unwrap_or("max") {
+            "max" => Ok(Self::Max),
+            "sum" => Ok(Self::Sum),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown field combination {other:?}"),
+            )),
+        }
+    }
+}
+
+/// Scores records given as one optional text per key, parallel to `keys`.
+pub(crate) fn search_fields(
+    query: &str,
+    records: &[Vec<Option<String>>],
+    keys: &[SearchKey],
+    opts: &SearchOptions,
+) -> Result<Vec<SearchByResult>> {
+    let combine = FieldCombine::parse(opts.combine_fields.as_deref())?;
+    let weights = key_weights(keys)?;
+    let scorer = Scorer::new(opts)?;
+    scorer.check_query(query)?;
+    check_item_arrays(opts, records.len())?;
+    check_limit(opts)?;
+
+    let normalized_query = scorer.prepare(query);
+    if scorer.too_short(&normalized_query) {
+        return Ok(Vec::new());
+    }
+
+    let threshold = opts.threshold.unwrap_or(0.0);
+    let results = filter_map_items(records, opts, |index, fields| {
+        let field_scores = fields
+            .iter()
+            .zip(&weights)
+            .map(|(text, weight)| {
+                let score = text
+                    .as_ref()
+                    .and_then(|text| scorer.score_raw(&normalized_query, text));
+                score.unwrap_or(0.0) * weight
+            });
+
+        let mut best = (0, f64::NEG_INFINITY);
+        let mut total = 0.0;
+        for (field, score) in field_scores.enumerate() {
+            if score > best.1 {
+                best = (field, score);
+            }
+            total += score;
+        }
+
+        let score = match combine {
+            FieldCombine::Max => best.1,
+            FieldCombine::Sum => total,
+        } * item_weight(opts, index);
+
+        (score >= threshold).then(|| SearchByResult {
+            index: index as u32,
+            score,
+            field: keys[best.0].key.clone(),
+        })
+    });
+
+    let ranking = Ranking::new(opts, None);
+    let mut results = rank(results, opts, &ranking, |r| (r.score, r.index));
+    for result in &mut results {
+        result.score = round_score(opts, result.score);
+    }
+    Ok(results)
+}
+
+/// Resolves each key's weight, rejecting an empty key list and weights that
+/// are negative or not finite.
+fn key_weights(keys: &[SearchKey]) -> Result<Vec<f64>> {
+    if keys.is_empty() {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "expected at least one search key".to_string(),
+        ));
+    }
+
+    keys.iter()
+        .map(|key| match key.weight.unwrap_or(1.0) {
+            weight if weight.is_finite() && weight >= 0.0 => Ok(weight),
+            weight => Err(Error::new(
+                Status::InvalidArg,
+                format!("invalid weight {weight} for key {:?}", key.key),
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> Vec<SearchKey> {
+        vec![
+            SearchKey {
+                key: "name".to_string(),
+                weight: Some(1.0),
+            },
+            SearchKey {
+                key: "description".to_string(),
+                weight: Some(0.5),
+            },
+        ]
+    }
+
+    fn records() -> Vec<Vec<Option<String>>> {
+        [
+            ("Lamp", "A bright apple-shaped light"),
+            ("Apple", "A fruit"),
+            ("Chair", "Something to sit on"),
+        ]
+        .iter()
+        .map(|(name, description)| {
+            vec![Some(name.to_string()), Some(description.to_string())]
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_name_outweighs_description() {
+        let opts = SearchOptions {
+            exact_substring: Some(true),
+            threshold: Some(0.1),
+            ..Default::default()
+        };
+        let results =
+            search_fields("apple", &records(), &keys(), &opts).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!((results[0].index, results[0].score), (1, 1.0));
+        assert_eq!(results[0].field, "name");
+        assert_eq!((results[1].index, results[1].score), (0, 0.5));
+        assert_eq!(results[1].field, "description");
+    }
+
+    #[test]
+    fn test_sum_adds_field_scores() {
+        let opts = SearchOptions {
+            exact_substring: Some(true),
+            combine_fields: Some("sum".to_string()),
+            ..Default::default()
+        };
+        let mut records = records();
+        records[1][1] = Some("An apple a day".to_string());
+        let results = search_fields("apple", &records, &keys(), &opts).unwrap();
+
+        assert_eq!(results[0].index, 1);
+        assert_eq!(results[0].score, 1.5);
+        assert_eq!(results[0].field, "name");
+    }
+
+    #[test]
+    fn test_invalid_keys_are_rejected() {
+        let opts = SearchOptions::default();
+        assert!(search_fields("a", &records(), &[], &opts).is_err());
+
+        let mut keys = keys();
+        keys[0].weight = Some(-1.0);
+        assert!(search_fields("a", &records(), &keys, &opts).is_err());
+
+        let opts = SearchOptions {
+            combine_fields: Some("avg".to_string()),
+            ..Default::default()
+        };
+        assert!(search_fields("a", &records(), &self::keys(), &opts).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use napi_derive::napi;
+
+/// A single record matched by [`crate::search_by`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface SearchByResult {
+///   index: number;  // index of the record in the original array
+///   score: number;  // combined, weighted score (higher is better)
+///   field: string;  // key of the field with the highest weighted score
+/// }
+/// ```
+#[napi(object)]
+pub struct SearchByResult {
+    /// Index of the record in the original input collection.
+    pub index: u32,
+
+    /// The fields' weighted scores, combined as set by `combineFields`.
+    pub score: f64,
+
+    /// The key of the field that contributed the highest weighted score.
+    pub field: String,
+}
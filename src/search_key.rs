@@ -0,0 +1,23 @@
+use napi_derive::napi;
+
+/// A record field searched by [`crate::search_by`], with its importance.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface SearchKey {
+///   key: string;     // name of the record property to search
+///   weight?: number; // default: 1
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone)]
+pub struct SearchKey {
+    /// Name of the property holding the text to search. Records where it is
+    /// missing or not a string score `0.0` for this field.
+    pub key: String,
+
+    /// Multiplier applied to this field's score before the fields are
+    /// combined. Must be finite and non-negative.
+    /// Defaults to `1.0`.
+    pub weight: Option<f64>,
+}
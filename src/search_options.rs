@@ -1,5 +1,8 @@
+use napi::Either;
 use napi_derive::napi;
 
+use crate::{AlgorithmWeight, Normalizer};
+
 /// Search options exposed to Node.js.
 /// All fields are optional when provided from JavaScript/TypeScript; if omitted,
 /// defaults will be filled in from [`Default`].
@@ -7,18 +10,102 @@ use napi_derive::napi;
 /// # TypeScript definition (generated by `napi`):
 /// ```ts
 /// interface SearchOptions {
-///   limit?: number;        // default: 10
+///   limit?: number | null; // default: 10; null for no limit
+///   offset?: number;       // default: 0
 ///   threshold?: number;    // default: 0.0
 ///   normalize?: boolean;   // default: true
 ///   ignoreCase?: boolean;  // default: true
+///   explain?: boolean;     // default: false
+///   requireAllTokens?: boolean; // default: false
+///   tokenThreshold?: number;    // default: 0.6
+///   maxInputLength?: number;    // default: 10000
+///   turkishI?: boolean;         // default: false
+///   stem?: boolean;             // default: false
+///   stopwords?: string[];       // default: []
+///   exactSubstring?: boolean;   // default: false
+///   weights?: number[];         // default: []
+///   includeRawScore?: boolean;  // default: false
+///   minQueryLength?: number;    // default: 0
+///   emptyQueryReturnsAll?: boolean; // default: false
+///   alphanumericOnly?: boolean; // default: false
+///   scoreFormula?: "distance" | "ratio"; // default: "distance"
+///   compatibility?: boolean;    // default: false
+///   parallelThreshold?: number; // default: 1000
+///   ignoreChars?: string;       // default: ""
+///   boostSuffix?: number;       // default: 0
+///   foldConfusables?: boolean;  // default: false
+///   lengthPenalty?: number;     // default: 0
+///   combineFields?: "max" | "sum"; // default: "max"
+///   maxDistance?: number;       // default: unset
+///   algorithm?: "levenshtein" | "osa" | "word-level" | "alignment"
+///     | AlgorithmWeight[];
+///                               // default: "levenshtein"
+///   phonetic?: boolean;         // default: false
+///   includeNormalized?: boolean; // default: false
+///   graphemes?: boolean;        // default: false
+///   cacheNormalization?: boolean; // default: false
+///   sortKeys?: number[];        // default: []
+///   accentCost?: number;        // default: unset
+///   naturalOrder?: boolean;     // default: false
+///   scoreDenominator?: "max" | "min" | "query" | "item";
+///                               // default: "max"
+///   preserveWhitespace?: boolean; // default: false
+///   precision?: number;         // default: unset
+///   locale?: string;            // default: unset
+///   positionDecay?: number;     // default: 1
+///   latinDiacriticsOnly?: boolean; // default: false
+///   keepLetters?: string;       // default: ""
+///   prefixBoostWeight?: number; // default: 0
+///   prefixBoostLength?: number; // default: 4
+///   includeTies?: boolean;      // default: false
+///   expandLigatures?: boolean;  // default: false
+///   streamOrder?: "corpus" | "score"; // default: "corpus"
+///   foldDigits?: boolean;       // default: false
+///   sampleLimit?: number;       // default: unset
+///   sampleSeed?: number;        // default: 0
+///   normalizer?: (s: string) => string; // default: unset
+///   wildcard?: boolean;         // default: false
+///   minTrigramOverlap?: number; // default: 0
+///   gapOpen?: number;           // default: 1
+///   gapExtend?: number;         // default: 0.5
+///   trim?: boolean;             // default: false
+///   wordCountBonus?: number;    // default: 0
+///   stripControl?: boolean;     // default: false
+///   includeTokenScores?: boolean; // default: false
+///   foldSuperscripts?: boolean; // default: false
+///   scoreFloor?: number;        // default: 0
+///   scoreCeil?: number;         // default: 1
+///   unicodeWords?: boolean;     // default: false
 /// }
 /// ```
 #[napi(object)]
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SearchOptions {
-    /// Maximum number of results to return.
-    /// Defaults to `10` if not specified.
-    pub limit: Option<u32>,
+    /// Maximum number of results to return. Leaving it out (`None`) keeps
+    /// the default of `10`, while an explicit `null` (`Some(None)`) returns
+    /// every match and `0` returns none. Fractional limits are rounded down
+    /// and an infinite one means no limit; negative or `NaN` limits are
+    /// rejected with an error.
+    /// Defaults to [`crate::DEFAULT_LIMIT`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            deserialize_with = "limit_serde::deserialize",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub limit: Option<Option<f64>>,
+
+    /// Number of sorted results to skip before `limit` is applied.
+    /// Offsets beyond the number of results produce an empty list.
+    /// Defaults to `0`.
+    pub offset: Option<u32>,
 
     /// Minimum score required for a result to be included.
     /// Defaults to `0.0`.
@@ -31,15 +118,526 @@ pub struct SearchOptions {
     /// Whether to compare case-insensitively.
     /// Defaults to `true`.
     pub ignore_case: Option<bool>,
+
+    /// Whether to attach a [`crate::ScoreExplanation`] to each result.
+    /// Defaults to `false`.
+    pub explain: Option<bool>,
+
+    /// Whether every query token must fuzzily match some item token.
+    /// Items missing a token score `0.0`; the rest score the average of their
+    /// per-token best matches.
+    /// Defaults to `false`.
+    pub require_all_tokens: Option<bool>,
+
+    /// Minimum per-token score used by `require_all_tokens`.
+    /// Defaults to `0.6`.
+    pub token_threshold: Option<f64>,
+
+    /// Maximum length, in characters, of the query and of each item.
+    /// A longer query is rejected with an error; longer items score `0.0`
+    /// without being compared.
+    /// Defaults to [`crate::DEFAULT_MAX_INPUT_LENGTH`].
+    pub max_input_length: Option<u32>,
+
+    /// Whether case folding uses the Turkish rules for the letter I
+    /// (`'I'` ↔ `'ı'`, `'İ'` ↔ `'i'`).
+    /// Defaults to `false`.
+    pub turkish_i: Option<bool>,
+
+    /// Whether to reduce words to their English stems before scoring, so
+    /// `"running"` matches `"run"`. Requires the `stemming` feature.
+    /// Defaults to `false`.
+    pub stem: Option<bool>,
+
+    /// Words to drop before scoring, e.g. `["the", "a"]`.
+    /// Defaults to none.
+    pub stopwords: Option<Vec<String>>,
+
+    /// Whether to score by substring containment instead of edit distance:
+    /// items whose normalized form contains the normalized query score `1.0`,
+    /// all others `0.0`. Much faster when only filtering is needed.
+    /// Defaults to `false`.
+    pub exact_substring: Option<bool>,
+
+    /// Per-item weights, parallel to `items`, that multiply each item's score
+    /// before `threshold` and sorting, e.g. to rank popular items higher at
+    /// equal similarity. Must have exactly one finite weight per item.
+    /// Defaults to none (every weight is `1.0`).
+    pub weights: Option<Vec<f64>>,
+
+    /// Whether to also report the unweighted score in
+    /// [`crate::SearchResult::raw_score`].
+    /// Defaults to `false`.
+    pub include_raw_score: Option<bool>,
+
+    /// Minimum length, in characters, of the normalized query. Shorter queries
+    /// return no results instead of matching almost everything weakly.
+    /// Defaults to `0` (no minimum).
+    pub min_query_length: Option<u32>,
+
+    /// Whether an empty normalized query matches every item with score `1.0`,
+    /// so an empty search box can show the full list in its original order.
+    /// When `false`, an empty query scores `0.0` against every non-empty item.
+    /// Defaults to `false`.
+    pub empty_query_returns_all: Option<bool>,
+
+    /// Whether to keep only alphanumeric characters after the other
+    /// normalization steps, so `"ABC-123"` matches `"abc123"`. Whitespace is
+    /// dropped too, which turns multi-word strings into a single token.
+    /// Defaults to `false`.
+    pub alphanumeric_only: Option<bool>,
+
+    /// How an edit distance is turned into a score: `"distance"` computes
+    /// `1 - distance / max(lenA, lenB)`, `"ratio"` computes
+    /// `(lenA + lenB - distance) / (lenA + lenB)` like RapidFuzz's `ratio`,
+    /// which is gentler on short strings.
+    /// Defaults to `"distance"`.
+    pub score_formula: Option<String>,
+
+    /// Whether to use Unicode compatibility normalization (NFKD/NFKC) so
+    /// full-width and half-width forms match, e.g. `"ＡＢＣ"` and `"ABC"`.
+    /// Defaults to `false`.
+    pub compatibility: Option<bool>,
+
+    /// Minimum number of items before scoring is spread across threads. Smaller
+    /// searches run on the calling thread, where they finish faster than the
+    /// thread pool can be woken up. `0` always goes parallel.
+    /// Defaults to [`crate::DEFAULT_PARALLEL_THRESHOLD`].
+    pub parallel_threshold: Option<u32>,
+
+    /// Characters to remove from the query and items before scoring, e.g.
+    /// `"-"` so `"555-1234"` matches `"5551234"`, or `" "` to ignore spaces.
+    /// Defaults to none.
+    pub ignore_chars: Option<String>,
+
+    /// Bonus added to an item's score when it ends with the normalized query,
+    /// e.g. so `"pdf"` ranks `"report.pdf"` above `"pdfs"`. The boosted score
+    /// is capped at `1.0`. Must be finite and not negative.
+    /// Defaults to `0.0` (no boost).
+    pub boost_suffix: Option<f64>,
+
+    /// Whether to fold visually confusable characters to the ones they imitate,
+    /// so a Cyrillic-spoofed `"аpple"` matches `"apple"`.
+    /// Defaults to `false`.
+    pub fold_confusables: Option<bool>,
+
+    /// Exponent of a length-ratio penalty: the base score is multiplied by
+    /// `(shorter / longer) ^ lengthPenalty`, measured in characters of the
+    /// normalized query and item. This pushes down matches between strings of
+    /// very different lengths, such as `"cat"` and `"cataclysmic"`. Must be
+    /// finite and not negative, since a negative exponent would boost those
+    /// matches instead.
+    /// Defaults to `0.0` (no penalty).
+    pub length_penalty: Option<f64>,
+
+    /// How [`crate::search_by`] combines the weighted scores of a record's
+    /// fields: `"max"` keeps the best field, `"sum"` adds them up so records
+    /// matching in several fields rank higher. Ignored by other searches.
+    /// Defaults to `"max"`.
+    pub combine_fields: Option<String>,
+
+    /// Maximum Levenshtein distance between the normalized query and an item.
+    /// Items needing more edits are dropped, independently of `threshold`;
+    /// when both are set an item must pass both. Items too long to compare are
+    /// dropped as well.
+    /// Defaults to unset (no limit).
+    pub max_distance: Option<u32>,
+
+    /// Which edit distance scores are computed from: `"levenshtein"` counts
+    /// insertions, deletions and substitutions; `"osa"` (Optimal String
+    /// Alignment) also counts swapping two adjacent characters as one edit, so
+    /// `"form"` is closer to `"from"`; `"word-level"` counts whole-word edits
+    /// and divides by the number of words, for matching sentences. Also used
+    /// by `maxDistance`. `"alignment"` scores with affine gap costs instead,
+    /// see `gapOpen` and `gapExtend`, leaving Levenshtein for `maxDistance`.
+    ///
+    /// A list of [`AlgorithmWeight`]s blends several similarities into their
+    /// weighted mean instead, e.g. Levenshtein with `"jaro-winkler"` to reward
+    /// shared prefixes; weights are scaled to sum to `1.0`. The first edit
+    /// distance in the list, or Levenshtein, serves `maxDistance`.
+    /// Defaults to `"levenshtein"`.
+    #[cfg_attr(feature = "serde", serde(default, with = "algorithm_serde"))]
+    pub algorithm: Option<Either<String, Vec<AlgorithmWeight>>>,
+
+    /// Whether items whose words have the same Soundex codes as the query's
+    /// score `1.0`, so `"Smith"` finds `"Smyth"`. Other items fall back to the
+    /// usual edit-distance score. Meant for searching names.
+    /// Defaults to `false`.
+    pub phonetic: Option<bool>,
+
+    /// Whether to attach each item's normalized form to its result as
+    /// [`crate::SearchResult::normalized_item`], e.g. to show why it matched.
+    /// Defaults to `false`.
+    pub include_normalized: Option<bool>,
+
+    /// Whether edit distances count extended grapheme clusters instead of
+    /// `char`s, so a flag or a family emoji is one character to insert,
+    /// delete or replace instead of several. Lengths are counted in graphemes
+    /// too.
+    /// Defaults to `false`.
+    pub graphemes: Option<bool>,
+
+    /// Whether to look up normalized items in a cache shared by all searches,
+    /// which saves work when the same items are searched again and again
+    /// without a [`crate::Searcher`]. The cache holds the most recently used
+    /// entries, up to [`crate::set_normalization_cache_size`], and never
+    /// changes results.
+    /// Defaults to `false`.
+    pub cache_normalization: Option<bool>,
+
+    /// Per-item sort keys, parallel to `items`, that order results with equal
+    /// scores: the higher key comes first, e.g. a timestamp to show the most
+    /// recent of several equally good matches first. Must have exactly one
+    /// number per item, none of them `NaN`.
+    /// Defaults to none (ties keep their corpus order).
+    pub sort_keys: Option<Vec<f64>>,
+
+    /// Cost of substituting a letter for an accented form of the same letter,
+    /// such as `'e'` for `'é'`, between `0.0` and `1.0`. When set, diacritics
+    /// are kept instead of stripped, so `"résumé"` matches `"resume"` closely
+    /// but not perfectly, and better than a plain substitution would allow.
+    /// Requires the `"levenshtein"` algorithm over `char`s.
+    /// Defaults to unset (diacritics are stripped when `normalize` is on).
+    pub accent_cost: Option<f64>,
+
+    /// Whether results with equal scores (and equal `sortKeys`) are ordered by
+    /// natural comparison of the item strings, where digit runs compare by
+    /// value, so `"item2"` comes before `"item10"`. Ignored by
+    /// [`crate::search_by`].
+    /// Defaults to `false` (ties keep their corpus order).
+    pub natural_order: Option<bool>,
+
+    /// Which length the edit distance is divided by with the `"distance"`
+    /// formula: `"max"` uses the longer of the query and item, `"min"` the
+    /// shorter, `"query"` and `"item"` that string's own length. Scores that
+    /// would drop below `0.0` are clamped to it. Must stay `"max"` with the
+    /// `"ratio"` formula.
+    /// Defaults to `"max"`.
+    pub score_denominator: Option<String>,
+
+    /// Whether to keep whitespace exactly as given instead of collapsing runs
+    /// into single spaces and trimming the ends, e.g. when matching code or
+    /// formatted text. Tabs and newlines then count as characters like any
+    /// other.
+    /// Defaults to `false`.
+    pub preserve_whitespace: Option<bool>,
+
+    /// Number of decimal places scores are rounded to before they are
+    /// returned, e.g. `2` to report `0.83` instead of `0.8333333333333334`.
+    /// Rounding happens after `threshold`, sorting and `limit`, so it never
+    /// changes which results are returned or their order. Values above `15`
+    /// keep full precision.
+    /// Defaults to unset (no rounding).
+    pub precision: Option<u32>,
+
+    /// BCP 47 language tag whose case mapping rules apply when folding case.
+    /// `"tr"` and `"az"`, and regional variants such as `"tr-TR"`, fold the
+    /// letter I as `turkish_i` does; other locales use the default Unicode
+    /// rules. Defaults to unset.
+    pub locale: Option<String>,
+
+    /// How much less each query token counts than the one before it with
+    /// `require_all_tokens`: the first token weighs `1`, the second
+    /// `position_decay`, the third `position_decay²` and so on, so earlier
+    /// query words matter more. Must be in `(0, 1]`; `1` weighs every token
+    /// the same. Defaults to `1.0`.
+    pub position_decay: Option<f64>,
+
+    /// Whether `normalize` only strips the accents of Latin letters, keeping
+    /// the combining marks of other scripts, such as Hebrew points or Greek
+    /// tonos, where they can change the word.
+    /// Defaults to `false`.
+    pub latin_diacritics_only: Option<bool>,
+
+    /// Precomposed letters that `normalize` keeps whole instead of stripping
+    /// their accents, for languages where they are letters of their own, e.g.
+    /// `"ñå"` so `"niño"` no longer matches `"nino"`. Each letter also keeps
+    /// its other case. Only precomposed input is protected: an `'n'` followed
+    /// by a combining tilde still loses the tilde.
+    /// Defaults to none.
+    pub keep_letters: Option<String>,
+
+    /// Scale of a Winkler prefix bonus added on top of any algorithm's score:
+    /// with a common prefix of `l` characters, up to `prefix_boost_length`, the
+    /// score `s` becomes `s + l * prefix_boost_weight * (1 - s)`, capped at
+    /// `1.0`. Jaro-Winkler uses `0.1`. Must not be negative.
+    /// Defaults to `0.0` (no boost).
+    pub prefix_boost_weight: Option<f64>,
+
+    /// Longest common prefix, in characters, that `prefix_boost_weight`
+    /// rewards. Defaults to `4`, as in Jaro-Winkler.
+    pub prefix_boost_length: Option<u32>,
+
+    /// Whether `limit` keeps every result tied with the last one it would
+    /// keep, so a page never ends in the middle of a group of equal scores.
+    /// More than `limit` results can then come back.
+    /// Defaults to `false`.
+    pub include_ties: Option<bool>,
+
+    /// Whether to spell out ligatures as their letters, so `"encyclopædia"`
+    /// matches `"encyclopaedia"` and `"ﬁle"` matches `"file"`. Covers `æ`,
+    /// `œ` and the compatibility ligatures such as `ﬁ` and `ĳ`.
+    /// Defaults to `false`.
+    pub expand_ligatures: Option<bool>,
+
+    /// The order [`crate::search_stream`] delivers results in: `"corpus"` as
+    /// they are found, `"score"` sorted like [`crate::search`] once every item
+    /// has been scored. Ignored by other searches.
+    /// Defaults to `"corpus"`.
+    pub stream_order: Option<String>,
+
+    /// Whether to replace every ASCII digit with the same placeholder before
+    /// scoring, so numbers only match by their shape: `"room 101"` matches
+    /// `"room 202"` exactly, and `"room 1010"` closely. Meant for template
+    /// matching.
+    /// Defaults to `false`.
+    pub fold_digits: Option<bool>,
+
+    /// Upper bound on the number of items scored. Larger corpora are searched
+    /// through a random sample of this many items, picked with `sample_seed`,
+    /// so results are approximate: a better match outside the sample is
+    /// missed. Trades recall for latency on huge corpora.
+    /// Defaults to unset (score every item).
+    pub sample_limit: Option<u32>,
+
+    /// Seed for the random sample `sample_limit` draws. The same seed, corpus
+    /// size and limit always pick the same items, so approximate results are
+    /// reproducible; a different seed picks a different sample.
+    /// Defaults to `0`.
+    pub sample_seed: Option<u32>,
+
+    /// A JS function replacing the built-in normalization: the query and each
+    /// item go through it instead, and the built-in steps, such as `normalize`,
+    /// `ignoreCase`, `stopwords` and the normalization cache, are skipped.
+    ///
+    /// It runs on the JS thread before scoring starts, once for the query and
+    /// once per item on every search, so each item crosses the FFI boundary
+    /// twice per search; over a large corpus that can cost more than the
+    /// scoring itself. When the same items are searched repeatedly, normalize
+    /// them once up front and search those with a [`crate::Searcher`]
+    /// instead. Only [`crate::search`], [`crate::search_with_count`],
+    /// [`crate::search_indices`], [`crate::search_arrays`],
+    /// [`crate::best_match`] and [`crate::score`] accept it; other functions
+    /// reject it. It can't be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub normalizer: Option<Normalizer>,
+
+    /// Treats the query as a glob-style pattern: `*` matches any run of chars
+    /// and `?` any single char, while the rest of the query is matched fuzzily.
+    /// An item scores one minus its edits per literal char of the pattern, so
+    /// `"a*e"` matches `"apple"` exactly, and an item with fewer chars than the
+    /// pattern has `?`s scores `0.0`. `*` and `?` survive the other
+    /// normalization steps unless `alphanumericOnly` or `ignoreChars` remove
+    /// them.
+    /// Defaults to `false`.
+    pub wildcard: Option<bool>,
+
+    /// Distinct character trigrams an item must share with the query to be
+    /// scored at all; items sharing fewer are left out of the results without
+    /// running the edit-distance DP. Counting shared trigrams is far cheaper
+    /// than the DP, so a cutoff of `1` or `2` speeds up searches over corpora
+    /// of mostly dissimilar items. Higher values lose recall: a short query
+    /// has few trigrams, and a typo changes up to three of them, so close
+    /// matches start to be dropped. Ignored for an empty query and with
+    /// `wildcard` or `exactSubstring`.
+    /// Defaults to `0`, scoring every item.
+    pub min_trigram_overlap: Option<u32>,
+
+    /// Cost of opening a gap, a run of inserted or deleted chars, under the
+    /// `"alignment"` algorithm. Must not be negative.
+    /// Defaults to `1`, the cost of a substitution.
+    pub gap_open: Option<f64>,
+
+    /// Cost of each further char of a gap under the `"alignment"` algorithm,
+    /// so a gap of `k` chars costs `gapOpen + (k - 1) * gapExtend`. Keeping
+    /// it below `gapOpen` makes one long gap cheaper than several short ones.
+    /// Must not be negative.
+    /// Defaults to `0.5`.
+    pub gap_extend: Option<f64>,
+
+    /// Whether to trim whitespace from both ends of the query and each item
+    /// even when whitespace isn't collapsed, e.g. with `normalize: false` or
+    /// `preserveWhitespace`. Runs of whitespace in between are kept, and
+    /// diacritics and case are left to the other options.
+    /// Defaults to `false`; collapsing whitespace always trims.
+    pub trim: Option<bool>,
+
+    /// Weight of matching word counts in token-based scoring
+    /// (`requireAllTokens` or the `"word-level"` algorithm): the score becomes
+    /// `(1 - w) * score + w` when the query and item have the same number of
+    /// words, and `(1 - w) * score` otherwise, so `"john smith"` ranks
+    /// `"John Smith"` above `"John Smith Jr"`. Keep it small so it only breaks
+    /// near-ties; must be in `[0, 1]`.
+    /// Defaults to `0.0` (word counts are ignored).
+    pub word_count_bonus: Option<f64>,
+
+    /// Whether to remove control characters and zero-width characters, such
+    /// as U+200B (zero-width space) and U+FEFF (byte order mark), from the
+    /// query and items before any other normalization, so text pasted from
+    /// the web matches its visible form. Tabs and newlines are kept as
+    /// whitespace.
+    /// Defaults to `false`.
+    pub strip_control: Option<bool>,
+
+    /// Whether to attach `tokenScores` to each result: for every whitespace-
+    /// separated query token, in order, how well it matched its most similar
+    /// item token, as `requireAllTokens` scores tokens. Lets UIs highlight each
+    /// query word by how confidently it matched.
+    /// Defaults to `false`.
+    pub include_token_scores: Option<bool>,
+
+    /// Fold superscripts, subscripts and vulgar fractions into plain
+    /// characters, so `"m²"` matches `"m2"` and `"½"` matches `"1/2"`.
+    /// Unlike a compatibility `form` this leaves every other character
+    /// alone.
+    pub fold_superscripts: Option<bool>,
+
+    /// Lowest score an item sharing anything with the query can get: scores
+    /// above `0.0` are raised to it after boosts, before `weights` and
+    /// `threshold`, so a `threshold` at or below the floor keeps every item
+    /// with any similarity while unrelated items still score `0.0`. Must be in
+    /// `[0, 1]` and at most `scoreCeil`.
+    /// Defaults to `0.0` (no floor).
+    pub score_floor: Option<f64>,
+
+    /// Highest score any item can get: scores above it are lowered to it after
+    /// boosts, before `weights` and `threshold`, so exact and near matches tie
+    /// and a `threshold` above the cap filters out everything. Must be in
+    /// `[0, 1]`.
+    /// Defaults to `1.0` (no cap).
+    pub score_ceil: Option<f64>,
+
+    /// Whether token-based scoring (`requireAllTokens`, the `"word-level"`
+    /// algorithm, `wordCountBonus` and `includeTokenScores`) splits text at
+    /// Unicode word boundaries (UAX #29) instead of whitespace, so
+    /// `"fast-fuzzy"` is two tokens, punctuation is dropped and Chinese or
+    /// Japanese text without spaces splits into one token per ideograph.
+    /// Defaults to `false`.
+    pub unicode_words: Option<bool>,
 }
 
 impl Default for SearchOptions {
     fn default() -> Self {
         Self {
-            limit: Some(10),
+            limit: Some(Some(f64::from(crate::DEFAULT_LIMIT))),
+            offset: Some(0),
             threshold: Some(0.0),
             normalize: true.into(),
             ignore_case: true.into(),
+            explain: false.into(),
+            require_all_tokens: false.into(),
+            token_threshold: Some(0.6),
+            max_input_length: Some(crate::DEFAULT_MAX_INPUT_LENGTH),
+            turkish_i: false.into(),
+            stem: false.into(),
+            stopwords: None,
+            exact_substring: false.into(),
+            weights: None,
+            include_raw_score: false.into(),
+            min_query_length: Some(0),
+            empty_query_returns_all: false.into(),
+            alphanumeric_only: false.into(),
+            score_formula: Some("distance".to_string()),
+            compatibility: false.into(),
+            parallel_threshold: Some(crate::DEFAULT_PARALLEL_THRESHOLD),
+            ignore_chars: None,
+            boost_suffix: Some(0.0),
+            fold_confusables: false.into(),
+            length_penalty: Some(0.0),
+            combine_fields: Some("max".to_string()),
+            max_distance: None,
+            algorithm: Some(Either::A("levenshtein".to_string())),
+            phonetic: false.into(),
+            include_normalized: false.into(),
+            graphemes: false.into(),
+            cache_normalization: false.into(),
+            sort_keys: None,
+            accent_cost: None,
+            natural_order: false.into(),
+            score_denominator: Some("max".to_string()),
+            preserve_whitespace: false.into(),
+            precision: None,
+            locale: None,
+            position_decay: Some(1.0),
+            latin_diacritics_only: false.into(),
+            keep_letters: None,
+            prefix_boost_weight: Some(0.0),
+            prefix_boost_length: Some(4),
+            include_ties: Some(false),
+            expand_ligatures: false.into(),
+            stream_order: Some("corpus".to_string()),
+            fold_digits: false.into(),
+            sample_limit: None,
+            sample_seed: Some(0),
+            normalizer: None,
+            wildcard: false.into(),
+            min_trigram_overlap: Some(0),
+            gap_open: Some(1.0),
+            gap_extend: Some(0.5),
+            trim: false.into(),
+            word_count_bonus: Some(0.0),
+            strip_control: false.into(),
+            include_token_scores: false.into(),
+            fold_superscripts: false.into(),
+            score_floor: Some(0.0),
+            score_ceil: Some(1.0),
+            unicode_words: false.into(),
         }
     }
 }
+
+/// Serializes `algorithm` as JavaScript passes it: a name or a list of
+/// weights.
+#[cfg(feature = "serde")]
+mod algorithm_serde {
+    use napi::Either;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::AlgorithmWeight;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Algorithm {
+        Name(String),
+        Blend(Vec<AlgorithmWeight>),
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        algorithm: &Option<Either<String, Vec<AlgorithmWeight>>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        algorithm
+            .as_ref()
+            .map(|algorithm| match algorithm {
+                Either::A(name) => Algorithm::Name(name.clone()),
+                Either::B(blend) => Algorithm::Blend(blend.clone()),
+            })
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Either<String, Vec<AlgorithmWeight>>>, D::Error> {
+        Ok(
+            Option::<Algorithm>::deserialize(deserializer)?.map(|algorithm| {
+                match algorithm {
+                    Algorithm::Name(name) => Either::A(name),
+                    Algorithm::Blend(blend) => Either::B(blend),
+                }
+            }),
+        )
+    }
+}
+
+/// Reads `limit` so that an explicit `null` is kept apart from a missing
+/// field, as from JavaScript.
+#[cfg(feature = "serde")]
+mod limit_serde {
+    use serde::{Deserialize, Deserializer};
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Option<f64>>, D::Error> {
+        Option::<f64>::deserialize(deserializer).map(Some)
+    }
+}
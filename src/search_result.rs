@@ -1,5 +1,7 @@
 use napi_derive::napi;
 
+use crate::ScoreExplanation;
+
 /// A single search result returned to Node.js.
 ///
 /// # TypeScript definition (generated by `napi`):
@@ -8,9 +10,21 @@ use napi_derive::napi;
 ///   item: string;   // the matched item
 ///   score: number;  // similarity score (higher is better)
 ///   index: number;  // index of the item in the original array
+///   explanation?: ScoreExplanation; // only set when `explain` is enabled
+///   queryIndex?: number; // best-matching query, set by `searchAny`
+///   rawScore?: number;   // score before `weights`, with `includeRawScore`
+///   normalizedItem?: string; // what `item` was compared as, with
+///                            // `includeNormalized`
+///   aliasIndex?: number; // best-matching alias, set by `searchAliases`
+///   tokenScores?: number[]; // per query token, with `includeTokenScores`
 /// }
 /// ```
 #[napi(object)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SearchResult {
     /// The original item/string that matched.
     pub item: String,
@@ -22,4 +36,28 @@ pub struct SearchResult {
 
     /// Index of the item in the original input collection.
     pub index: u32,
+
+    /// How the score was computed; `None` unless `explain` was requested.
+    pub explanation: Option<ScoreExplanation>,
+
+    /// Index of the query that produced `score`, for multi-query searches.
+    pub query_index: Option<u32>,
+
+    /// The score before the item's weight was applied; `None` unless
+    /// `include_raw_score` was requested.
+    pub raw_score: Option<f64>,
+
+    /// The item after normalization, as it was compared to the query; `None`
+    /// unless `include_normalized` was requested or the item was too long to
+    /// be compared.
+    pub normalized_item: Option<String>,
+
+    /// Index, within its group, of the alias that produced `score`, for
+    /// [`crate::search_aliases`]. `item` is still the group's first alias.
+    pub alias_index: Option<u32>,
+
+    /// How well each query token matched its most similar item token, in
+    /// query token order; `None` unless `include_token_scores` was
+    /// requested.
+    pub token_scores: Option<Vec<f64>>,
 }
@@ -0,0 +1,34 @@
+use napi_derive::napi;
+
+use crate::SearchResult;
+
+/// Search results together with the number of items that matched.
+///
+/// An empty corpus and a search where nothing matched both have a `total` of
+/// `0`; `item_count` tells them apart.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface SearchResults {
+///   results: SearchResult[]; // sorted results, truncated to `limit`
+///   total: number;           // results passing `threshold`, before `limit`
+///   itemCount: number;       // items searched, `0` for an empty corpus
+///   approximate: boolean;    // whether `sampleLimit` skipped some items
+/// }
+/// ```
+#[napi(object)]
+pub struct SearchResults {
+    /// The sorted results, truncated to `limit`.
+    pub results: Vec<SearchResult>,
+
+    /// How many items passed `threshold` before `limit` was applied.
+    pub total: u32,
+
+    /// How many items were searched, matching or not.
+    pub item_count: u32,
+
+    /// Whether `sampleLimit` only scored some of the items, so that `results`
+    /// are the best of a sample and `total` counts matches among the sampled
+    /// items only.
+    pub approximate: bool,
+}
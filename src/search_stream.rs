@@ -0,0 +1,240 @@
+use std::sync::mpsc;
+
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction,
+    ThreadsafeFunctionCallMode,
+};
+
+use crate::sample::Sample;
+use crate::scorer::Scorer;
+use crate::{
+    check_item_arrays, check_limit, result_limit, score_item, score_ranked,
+    search_result, SearchOptions, SearchResult,
+};
+
+/// How many results may wait for the JS callback before the search pauses.
+pub(crate) const STREAM_QUEUE_SIZE: usize = 64;
+
+/// The callback [`crate::search_stream`] hands each result to.
+pub(crate) type ResultCallback =
+    ThreadsafeFunction<Streamed, ErrorStrategy::Fatal>;
+
+/// The order [`crate::search_stream`] delivers results in, selected by
+/// [`SearchOptions::stream_order`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StreamOrder {
+    /// As items are scored, in corpus order.
+    Corpus,
+    /// Sorted like [`crate::search`], once every item has been scored.
+    Score,
+}
+
+impl StreamOrder {
+    fn parse(name: Option<&str>) -> Result<Self> {
+        match name.unwrap_or("corpus") {
+            "corpus" => Ok(Self::Corpus),
+            "score" => Ok(Self::Score),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown stream order {other:?}"),
+            )),
+        }
+    }
+}
+
+/// A search run off the JS thread, passing each result to a callback.
+///
+/// Created by [`crate::search_stream`]; the options are validated before the
+/// task is queued, so argument errors throw instead of rejecting.
+pub struct SearchStream {
+    query: String,
+    items: Vec<String>,
+    opts: SearchOptions,
+    order: StreamOrder,
+    on_result: ResultCallback,
+}
+
+impl SearchStream {
+    pub(crate) fn new(
+        query: String,
+        items: Vec<String>,
+        opts: SearchOptions,
+        on_result: ResultCallback,
+    ) -> Result<Self> {
+        let order = StreamOrder::parse(opts.stream_order.as_deref())?;
+        Scorer::new(&opts)?.check_query(&query)?;
+        check_item_arrays(&opts, items.len())?;
+        check_limit(&opts)?;
+
+        Ok(Self {
+            query,
+            items,
+            opts,
+            order,
+            on_result,
+        })
+    }
+
+    /// Scores the items in corpus order, delivering each match as soon as it
+    /// is found. `offset` skips the first matches and `limit` stops the scan.
+    /// Returns how many results were delivered.
+    fn stream_corpus(&mut self, scorer: &Scorer, query: &str) -> u32 {
+        let offset = self.opts.offset.unwrap_or(0) as usize;
+        let limit = result_limit(&self.opts).unwrap_or(usize::MAX);
+        let sample = Sample::new(&self.opts, self.items.len());
+        let mut delivery = Delivery::new(&self.on_result);
+
+        let mut matches = 0;
+        for index in 0..self.items.len() {
+            if delivery.count >= limit {
+                break;
+            }
+            if !sample.contains(index) {
+                continue;
+            }
+            let item = &self.items[index];
+            let Some(scored) =
+                score_item(scorer, query, index, item, &self.opts)
+            else {
+                continue;
+            };
+            matches += 1;
+            if matches <= offset {
+                continue;
+            }
+            let item = std::mem::take(&mut self.items[index]);
+            delivery
+                .send(search_result(scorer, &self.opts, query, scored, item));
+        }
+
+        delivery.finish()
+    }
+
+    /// Ranks every item first, then delivers the page [`crate::search`]
+    /// would return, best first. Returns how many results were delivered.
+    fn stream_sorted(&mut self, scorer: &Scorer, query: &str) -> u32 {
+        let (scored, _) = score_ranked(scorer, query, &self.items, &self.opts);
+        let mut delivery = Delivery::new(&self.on_result);
+
+        for s in scored {
+            let item = std::mem::take(&mut self.items[s.index as usize]);
+            delivery.send(search_result(scorer, &self.opts, query, s, item));
+        }
+
+        delivery.finish()
+    }
+}
+
+impl Task for SearchStream {
+    type Output = u32;
+    type JsValue = u32;
+
+    fn compute(&mut self) -> Result<u32> {
+        let scorer = Scorer::new(&self.opts)?;
+        let query = scorer.prepare(&self.query);
+        if scorer.too_short(&query) {
+            return Ok(0);
+        }
+
+        Ok(match self.order {
+            StreamOrder::Corpus => self.stream_corpus(&scorer, &query),
+            StreamOrder::Score => self.stream_sorted(&scorer, &query),
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, count: u32) -> Result<u32> {
+        Ok(count)
+    }
+}
+
+/// A result on its way to the JS callback.
+pub(crate) struct Streamed {
+    result: SearchResult,
+    /// Signalled just before the callback is called with `result`.
+    calling: Option<mpsc::Sender<()>>,
+}
+
+/// Turns a [`Streamed`] result into the callback's arguments, signalling
+/// `calling` on the way.
+pub(crate) fn callback_args(
+    ctx: ThreadSafeCallContext<Streamed>,
+) -> Result<Vec<SearchResult>> {
+    if let Some(calling) = ctx.value.calling {
+        let _ = calling.send(());
+    }
+    Ok(vec![ctx.value.result])
+}
+
+/// Hands results to the JS callback, one behind, so the last one can be
+/// sent with a completion signal.
+///
+/// Calls block while [`STREAM_QUEUE_SIZE`] results are already waiting,
+/// which keeps a slow callback from piling up the whole result set. The
+/// queue is first in, first out, so once the last result is being handed
+/// over every earlier callback has returned. The JS thread then runs the
+/// last callback before it can settle the promise, so the promise always
+/// resolves after every callback ran.
+struct Delivery<'a> {
+    on_result: &'a ResultCallback,
+    pending: Option<SearchResult>,
+    count: usize,
+}
+
+impl<'a> Delivery<'a> {
+    fn new(on_result: &'a ResultCallback) -> Self {
+        Self {
+            on_result,
+            pending: None,
+            count: 0,
+        }
+    }
+
+    fn send(&mut self, result: SearchResult) {
+        if let Some(previous) = self.pending.replace(result) {
+            self.on_result.call(
+                Streamed {
+                    result: previous,
+                    calling: None,
+                },
+                ThreadsafeFunctionCallMode::Blocking,
+            );
+        }
+        self.count += 1;
+    }
+
+    /// Sends the last result and waits until it is being handed to the
+    /// callback. Returns how many results were delivered.
+    fn finish(self) -> u32 {
+        if let Some(last) = self.pending {
+            let (calling, called) = mpsc::channel();
+            let status = self.on_result.call(
+                Streamed {
+                    result: last,
+                    calling: Some(calling),
+                },
+                ThreadsafeFunctionCallMode::Blocking,
+            );
+            // A closing callback never gets the result, so don't wait for it.
+            if status == Status::Ok {
+                let _ = called.recv();
+            }
+        }
+        self.count as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_order_defaults_to_corpus() {
+        assert_eq!(StreamOrder::parse(None).unwrap(), StreamOrder::Corpus);
+        assert_eq!(
+            StreamOrder::parse(Some("score")).unwrap(),
+            StreamOrder::Score
+        );
+        assert!(StreamOrder::parse(Some("random")).is_err());
+    }
+}
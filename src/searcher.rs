@@ -0,0 +1,760 @@
+use std::rc::Rc;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::algo::{similarity_prepared, PreparedQuery};
+use crate::cache::LruCache;
+use crate::prefix_trie::PrefixTrie;
+use crate::scorer::is_shorter_than;
+use crate::trigram_index::TrigramIndex;
+use crate::{
+    check_length, check_limit, exceeds_length, explain_similarity,
+    normalize_with, page_length, resolve_normalization, result_limit,
+    round_score, LevenshteinBuffer, NormalizationOptions, SearchOptions,
+    SearchResult, SearcherOptions, DEFAULT_MAX_INPUT_LENGTH,
+};
+
+/// A reusable searcher over a fixed corpus.
+///
+/// Items are normalized once when the searcher is created, and the scores for
+/// recently seen normalized queries are kept in an LRU cache. This suits
+/// autocomplete, where the same prefixes are queried repeatedly as users type
+/// and backspace.
+///
+/// Each cache entry stores a score for every item, so the cache costs roughly
+/// `cacheSize * items.length * 16` bytes. The cache is cleared whenever the
+/// corpus changes.
+///
+/// Items longer than `maxInputLength` are kept but never compared; they
+/// always score `0.0`.
+///
+/// Removed items are tombstoned rather than compacted: every other item keeps
+/// its index, so indices held by the caller stay valid, and new items added
+/// later are still numbered after the removed ones. Only `clear` and
+/// `setItems` restart numbering from `0`.
+///
+/// When `minSharedGrams` is set, the searcher also keeps an inverted index from
+/// character trigrams to items and only scores items sharing at least that
+/// many distinct trigrams with the query; other items are left out of the
+/// results. This makes searches over large corpora much cheaper, at the cost
+/// of missing matches that only share a few characters with the query (e.g.
+/// very short queries, or typos in every trigram). Keep it low (`1` or `2`)
+/// to preserve recall for typical typo-tolerant searches.
+///
+/// `prefixSearch` answers strict autocomplete without any fuzziness: it
+/// returns the items whose normalized form starts with the normalized query.
+/// With `prefixIndex` set, the searcher keeps a trie over the normalized
+/// items so this costs one step per query character plus one per match;
+/// otherwise every item is checked.
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// class Searcher {
+///   constructor(items: string[], options?: SearcherOptions);
+///   query(query: string, options?: SearchOptions): SearchResult[];
+///   prefixSearch(query: string, options?: SearchOptions): SearchResult[];
+///   setItems(items: string[]): void;
+///   readonly cacheHits: number;
+/// }
+/// ```
+#[napi]
+pub struct Searcher {
+    items: Vec<String>,
+    normalized: Vec<Option<String>>,
+    removed: Vec<bool>,
+    normalization: NormalizationOptions,
+    max_input_length: usize,
+    min_shared_grams: Option<usize>,
+    index: TrigramIndex,
+    prefix: Option<PrefixTrie>,
+    cache: LruCache<String, Rc<Vec<(u32, f64)>>>,
+    cache_hits: u32,
+    buffer: LevenshteinBuffer,
+}
+
+#[napi]
+impl Searcher {
+    #[napi(constructor)]
+    pub fn new(
+        items: Vec<String>,
+        options: Option<SearcherOptions>,
+    ) -> Result<Self> {
+        let opts = options.unwrap_or_default();
+        let normalization = resolve_normalization((&opts).into(), false)?;
+        let cache_size = opts.cache_size.unwrap_or(32) as usize;
+        let max_input_length =
+            opts.max_input_length
+                .unwrap_or(DEFAULT_MAX_INPUT_LENGTH) as usize;
+
+        let mut searcher = Self {
+            items: Vec::new(),
+            normalized: Vec::new(),
+            removed: Vec::new(),
+            normalization,
+            max_input_length,
+            min_shared_grams: opts
+                .min_shared_grams
+                .filter(|&min| min > 0)
+                .map(|min| min as usize),
+            index: TrigramIndex::default(),
+            prefix: opts
+                .prefix_index
+                .unwrap_or(false)
+                .then(PrefixTrie::default),
+            cache: LruCache::new(cache_size),
+            cache_hits: 0,
+            buffer: LevenshteinBuffer::default(),
+        };
+        searcher.set_items(items);
+        Ok(searcher)
+    }
+
+    /// Searches the corpus for `query`.
+    ///
+    /// Only `limit`, `offset`, `include_ties`, `threshold`, `explain`,
+    /// `min_query_length` and `include_normalized` are read from `options`;
+    /// normalization is fixed by the options the searcher was constructed
+    /// with.
+    #[napi]
+    pub fn query(
+        &mut self,
+        query: String,
+        options: Option<SearchOptions>,
+    ) -> Result<Vec<SearchResult>> {
+        let opts = options.unwrap_or_default();
+        check_length(&query, self.max_input_length)?;
+        check_limit(&opts)?;
+        let normalized_query = normalize_with(&query, &self.normalization);
+        let min_query_length = opts.min_query_length.unwrap_or(0) as usize;
+        if is_shorter_than(&normalized_query, min_query_length) {
+            return Ok(Vec::new());
+        }
+
+        let scores = match self.cache.get(&normalized_query) {
+            Some(scores) => {
+                self.cache_hits += 1;
+                Rc::clone(scores)
+            }
+            None => {
+                let scores = Rc::new(self.score_all(&normalized_query));
+                self.cache
+                    .insert(normalized_query.clone(), Rc::clone(&scores));
+                scores
+            }
+        };
+
+        let explain = opts.explain.unwrap_or(false);
+        let include_normalized = opts.include_normalized.unwrap_or(false);
+        let threshold = opts.threshold.unwrap_or(0.0);
+        let passing = scores
+            .iter()
+            .take_while(|(_, score)| *score >= threshold)
+            .count();
+        let offset = (opts.offset.unwrap_or(0) as usize).min(passing);
+        let page = &scores[offset..passing];
+        let length = page_length(&opts, page, |&(_, score)| score)
+            .map_or(page.len(), |length| length.min(page.len()));
+
+        Ok(page[..length]
+            .iter()
+            .map(|&(index, score)| SearchResult {
+                item: self.items[index as usize].clone(),
+                score: round_score(&opts, score),
+                index,
+                explanation: self.normalized[index as usize]
+                    .as_ref()
+                    .filter(|_| explain)
+                    .map(|item| explain_similarity(&normalized_query, item)),
+                query_index: None,
+                raw_score: None,
+                normalized_item: self.normalized[index as usize]
+                    .as_ref()
+                    .filter(|_| include_normalized)
+                    .cloned(),
+                alias_index: None,
+                token_scores: None,
+            })
+            .collect())
+    }
+
+    /// Returns the items whose normalized form starts with the normalized
+    /// `query`, shortest first with ties in corpus order.
+    ///
+    /// Each result scores the fraction of the item the query covers, so an
+    /// exact match scores `1.0`. Only `limit` and `offset` are read from
+    /// `options`. Items longer than `maxInputLength` never match.
+    #[napi]
+    pub fn prefix_search(
+        &self,
+        query: String,
+        options: Option<SearchOptions>,
+    ) -> Result<Vec<SearchResult>> {
+        let opts = options.unwrap_or_default();
+        check_length(&query, self.max_input_length)?;
+        check_limit(&opts)?;
+        let prefix = normalize_with(&query, &self.normalization);
+
+        let ids = match &self.prefix {
+            Some(trie) => trie.starting_with(&prefix),
+            None => (0..self.normalized.len() as u32)
+                .filter(|&index| {
+                    self.normalized[index as usize]
+                        .as_ref()
+                        .is_some_and(|item| item.starts_with(&prefix))
+                })
+                .collect(),
+        };
+
+        let mut matches: Vec<(usize, u32)> = ids
+            .into_iter()
+            .map(|index| {
+                let item = self.normalized[index as usize]
+                    .as_deref()
+                    .unwrap_or_default();
+                (item.chars().count(), index)
+            })
+            .collect();
+        matches.sort_unstable();
+
+        let prefix_length = prefix.chars().count();
+        let offset = opts.offset.unwrap_or(0) as usize;
+        let limit = result_limit(&opts).unwrap_or(usize::MAX);
+        Ok(matches
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(length, index)| SearchResult {
+                item: self.items[index as usize].clone(),
+                score: if length == 0 {
+                    1.0
+                } else {
+                    prefix_length as f64 / length as f64
+                },
+                index,
+                explanation: None,
+                query_index: None,
+                raw_score: None,
+                normalized_item: None,
+                alias_index: None,
+                token_scores: None,
+            })
+            .collect())
+    }
+
+    /// Replaces the corpus, re-normalizing every item and clearing the cache.
+    #[napi]
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.clear();
+        self.add_items(items);
+    }
+
+    /// Appends `items` to the corpus and clears the cache.
+    ///
+    /// New items are numbered after the existing ones, so result indices stay
+    /// consistent across batches. This lets a large corpus be built up
+    /// incrementally instead of being passed in as one huge array.
+    #[napi]
+    pub fn add_items(&mut self, items: Vec<String>) {
+        self.items.reserve(items.len());
+        self.normalized.reserve(items.len());
+        self.removed.reserve(items.len());
+
+        for item in items {
+            let normalized = self.index_item(self.items.len() as u32, &item);
+            self.normalized.push(normalized);
+            self.removed.push(false);
+            self.items.push(item);
+        }
+
+        self.cache.clear();
+    }
+
+    /// Removes the item at `index` so it no longer appears in results, and
+    /// clears the cache.
+    ///
+    /// The slot is tombstoned, not reused: other items keep their indices.
+    /// Returns `false` if there is no item at `index` or it was already
+    /// removed.
+    #[napi]
+    pub fn remove_item(&mut self, index: u32) -> bool {
+        let i = index as usize;
+        if self
+            .removed
+            .get(i)
+            .copied()
+            .unwrap_or(true)
+        {
+            return false;
+        }
+
+        if let Some(normalized) = self.normalized[i].take() {
+            self.index.remove(index, &normalized);
+            if let Some(trie) = &mut self.prefix {
+                trie.remove(index, &normalized);
+            }
+        }
+        self.items[i] = String::new();
+        self.removed[i] = true;
+        self.cache.clear();
+        true
+    }
+
+    /// Replaces the item at `index` with `new_item` and clears the cache.
+    ///
+    /// Only the new item is normalized and indexed, and it keeps `index`, so
+    /// this is much cheaper than `setItems` for editing a live corpus.
+    /// Returns `false` if there is no item at `index` or it was removed.
+    #[napi]
+    pub fn update(&mut self, index: u32, new_item: String) -> bool {
+        let i = index as usize;
+        if self
+            .removed
+            .get(i)
+            .copied()
+            .unwrap_or(true)
+        {
+            return false;
+        }
+
+        if let Some(normalized) = self.normalized[i].take() {
+            self.index.remove(index, &normalized);
+            if let Some(trie) = &mut self.prefix {
+                trie.remove(index, &normalized);
+            }
+        }
+        self.normalized[i] = self.index_item(index, &new_item);
+        self.items[i] = new_item;
+        self.cache.clear();
+        true
+    }
+
+    /// Removes every item and clears the cache. Items added afterwards are
+    /// numbered from `0` again.
+    #[napi]
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.normalized.clear();
+        self.removed.clear();
+        self.index.clear();
+        if let Some(trie) = &mut self.prefix {
+            trie.clear();
+        }
+        self.cache.clear();
+    }
+
+    /// Number of queries answered from the cache so far.
+    #[napi(getter)]
+    pub fn cache_hits(&self) -> u32 {
+        self.cache_hits
+    }
+
+    /// Normalizes `item` and adds it to the trigram index and prefix trie
+    /// under `id`, or returns `None` if it is too long to ever be compared.
+    fn index_item(&mut self, id: u32, item: &str) -> Option<String> {
+        let normalized = (!exceeds_length(item, self.max_input_length))
+            .then(|| normalize_with(item, &self.normalization))?;
+
+        self.buffer.reserve(normalized.len());
+        if self.min_shared_grams.is_some() {
+            self.index.insert(id, &normalized);
+        }
+        if let Some(trie) = &mut self.prefix {
+            trie.insert(id, &normalized);
+        }
+        Some(normalized)
+    }
+
+    /// Scores every candidate item against `normalized_query`, sorted by
+    /// descending score with ties kept in corpus order.
+    ///
+    /// Without a trigram index every item that hasn't been removed is a
+    /// candidate.
+    fn score_all(&mut self, normalized_query: &str) -> Vec<(u32, f64)> {
+        let candidates = self
+            .min_shared_grams
+            .and_then(|min_shared| {
+                self.index
+                    .candidates(normalized_query, min_shared)
+            });
+        let removed = &self.removed;
+        let candidates = candidates.unwrap_or_else(|| {
+            (0..self.normalized.len() as u32)
+                .filter(|&index| !removed[index as usize])
+                .collect()
+        });
+
+        let query = PreparedQuery::new(normalized_query);
+        let buffer = &mut self.buffer;
+        let mut scores: Vec<(u32, f64)> = candidates
+            .into_iter()
+            .map(|index| {
+                let item = &self.normalized[index as usize];
+                let score = item.as_ref().map_or(0.0, |item| {
+                    similarity_prepared(&query, item, buffer)
+                });
+                (index, score)
+            })
+            .collect();
+
+        scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<String> {
+        vec![
+            "apple".to_string(),
+            "apply".to_string(),
+            "banana".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_repeated_query_hits_cache() {
+        let mut searcher = Searcher::new(items(), None).unwrap();
+
+        let first = searcher
+            .query("APPLE".to_string(), None)
+            .unwrap();
+        assert_eq!(searcher.cache_hits(), 0);
+
+        let second = searcher
+            .query("apple".to_string(), None)
+            .unwrap();
+        assert_eq!(searcher.cache_hits(), 1);
+        assert_eq!(first.len(), second.len());
+        assert_eq!(second[0].item, "apple");
+    }
+
+    #[test]
+    fn test_set_items_invalidates_cache() {
+        let mut searcher = Searcher::new(items(), None).unwrap();
+        searcher
+            .query("apple".to_string(), None)
+            .unwrap();
+
+        searcher.set_items(vec!["grape".to_string()]);
+        let results = searcher
+            .query("apple".to_string(), None)
+            .unwrap();
+        assert_eq!(searcher.cache_hits(), 0);
+        assert_eq!(results[0].item, "grape");
+    }
+
+    #[test]
+    fn test_add_items_across_batches() {
+        let options = SearcherOptions {
+            min_shared_grams: Some(1),
+            ..Default::default()
+        };
+        let mut searcher = Searcher::new(items(), Some(options)).unwrap();
+        searcher
+            .query("grape".to_string(), None)
+            .unwrap();
+
+        searcher.add_items(vec!["grape".to_string(), "apples".to_string()]);
+        let results = searcher
+            .query("grape".to_string(), None)
+            .unwrap();
+        assert_eq!(searcher.cache_hits(), 0);
+        assert_eq!(results[0].item, "grape");
+        assert_eq!(results[0].index, 3);
+
+        let results = searcher
+            .query("apples".to_string(), None)
+            .unwrap();
+        assert_eq!(results[0].index, 4);
+        assert_eq!(results[1].index, 0);
+    }
+
+    #[test]
+    fn test_removed_items_are_left_out() {
+        for min_shared_grams in [None, Some(1)] {
+            let options = SearcherOptions {
+                min_shared_grams,
+                ..Default::default()
+            };
+            let mut searcher = Searcher::new(items(), Some(options)).unwrap();
+            searcher
+                .query("apple".to_string(), None)
+                .unwrap();
+
+            assert!(searcher.remove_item(0));
+            assert!(!searcher.remove_item(0));
+            assert!(!searcher.remove_item(10));
+
+            let results = searcher
+                .query("apple".to_string(), None)
+                .unwrap();
+            assert_eq!(searcher.cache_hits(), 0);
+            assert!(results.iter().all(|r| r.index != 0));
+            assert_eq!(results[0].item, "apply");
+            assert_eq!(results[0].index, 1);
+        }
+    }
+
+    #[test]
+    fn test_update_replaces_item_in_place() {
+        for (min_shared_grams, prefix_index) in
+            [(None, None), (Some(1), Some(true))]
+        {
+            let options = SearcherOptions {
+                min_shared_grams,
+                prefix_index,
+                ..Default::default()
+            };
+            let mut searcher = Searcher::new(items(), Some(options)).unwrap();
+            searcher
+                .query("banana".to_string(), None)
+                .unwrap();
+
+            assert!(searcher.update(2, "Cherry".to_string()));
+            assert!(!searcher.update(10, "grape".to_string()));
+
+            let strict = SearchOptions {
+                threshold: Some(0.5),
+                ..Default::default()
+            };
+            let banana = searcher
+                .query("banana".to_string(), Some(strict))
+                .unwrap();
+            assert!(banana.is_empty());
+
+            let cherry = searcher
+                .query("cherry".to_string(), None)
+                .unwrap();
+            assert_eq!(searcher.cache_hits(), 0);
+            assert_eq!(cherry[0].item, "Cherry");
+            assert_eq!(cherry[0].index, 2);
+            assert_eq!(cherry[0].score, 1.0);
+
+            let prefixed = searcher
+                .prefix_search("ch".to_string(), None)
+                .unwrap();
+            assert_eq!(prefixed.len(), 1);
+            assert_eq!(prefixed[0].index, 2);
+
+            assert!(searcher.remove_item(2));
+            assert!(!searcher.update(2, "cherry".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_clear_restarts_indices() {
+        let mut searcher = Searcher::new(items(), None).unwrap();
+        searcher.clear();
+        assert!(searcher
+            .query("apple".to_string(), None)
+            .unwrap()
+            .is_empty());
+
+        searcher.add_items(vec!["grape".to_string()]);
+        let results = searcher
+            .query("grape".to_string(), None)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].index, 0);
+    }
+
+    #[test]
+    fn test_min_query_length_returns_nothing() {
+        let mut searcher = Searcher::new(items(), None).unwrap();
+        let options = SearchOptions {
+            min_query_length: Some(2),
+            ..Default::default()
+        };
+
+        let results = searcher
+            .query("a".to_string(), Some(options))
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_trigram_index_matches_brute_force() {
+        let corpus: Vec<String> = [
+            "apple",
+            "apples",
+            "application",
+            "banana",
+            "bandana",
+            "grape",
+            "grapefruit",
+            "pineapple",
+            "maple",
+            "kiwi",
+            "orange",
+            "mango",
+        ]
+        .map(String::from)
+        .to_vec();
+        let indexed_options = SearcherOptions {
+            min_shared_grams: Some(1),
+            ..Default::default()
+        };
+
+        let mut brute = Searcher::new(corpus.clone(), None).unwrap();
+        let mut indexed = Searcher::new(corpus, Some(indexed_options)).unwrap();
+
+        for query in ["apple", "aple", "banan", "grap", "mango", "orang"] {
+            let options = || SearchOptions {
+                threshold: Some(0.5),
+                limit: Some(None),
+                ..Default::default()
+            };
+            let expected: Vec<u32> = brute
+                .query(query.to_string(), Some(options()))
+                .unwrap()
+                .iter()
+                .map(|r| r.index)
+                .collect();
+            let actual: Vec<u32> = indexed
+                .query(query.to_string(), Some(options()))
+                .unwrap()
+                .iter()
+                .map(|r| r.index)
+                .collect();
+            assert_eq!(actual, expected, "query {query:?}");
+        }
+    }
+
+    #[test]
+    fn test_prefix_search_with_and_without_trie() {
+        let words: Vec<String> =
+            ["Cartoon", "car", "cat", "Carté", "dog", "care", "scar"]
+                .map(String::from)
+                .to_vec();
+
+        for prefix_index in [false, true] {
+            let options = SearcherOptions {
+                prefix_index: Some(prefix_index),
+                ..Default::default()
+            };
+            let mut searcher =
+                Searcher::new(words.clone(), Some(options)).unwrap();
+            let prefixed = |searcher: &Searcher, query: &str| -> Vec<String> {
+                searcher
+                    .prefix_search(query.to_string(), None)
+                    .unwrap()
+                    .into_iter()
+                    .map(|r| r.item)
+                    .collect()
+            };
+
+            assert_eq!(
+                prefixed(&searcher, "CAR"),
+                ["car", "care", "Carté", "Cartoon"]
+            );
+            assert!(prefixed(&searcher, "cow").is_empty());
+            assert_eq!(prefixed(&searcher, "").len(), words.len());
+
+            let results = searcher
+                .prefix_search("car".to_string(), None)
+                .unwrap();
+            assert_eq!(results[0].score, 1.0);
+            assert_eq!(results[1].score, 0.75);
+
+            let page = searcher
+                .prefix_search(
+                    "car".to_string(),
+                    Some(SearchOptions {
+                        offset: Some(1),
+                        limit: Some(Some(2.0)),
+                        ..Default::default()
+                    }),
+                )
+                .unwrap();
+            assert_eq!(page[0].item, "care");
+            assert_eq!(page.len(), 2);
+
+            assert!(searcher.remove_item(1));
+            searcher.add_items(vec!["cargo".to_string()]);
+            assert_eq!(
+                prefixed(&searcher, "car"),
+                ["care", "Carté", "cargo", "Cartoon"]
+            );
+
+            searcher.clear();
+            assert!(prefixed(&searcher, "").is_empty());
+        }
+    }
+
+    #[test]
+    fn test_long_inputs_are_guarded() {
+        let options = SearcherOptions {
+            max_input_length: Some(8),
+            ..Default::default()
+        };
+        let mut searcher = Searcher::new(
+            vec!["a".repeat(9), "apple".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        let results = searcher
+            .query("apple".to_string(), None)
+            .unwrap();
+        assert_eq!(results[0].item, "apple");
+        assert_eq!(results[1].score, 0.0);
+        assert!(searcher
+            .query("a".repeat(9), None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_normalizes_like_search() {
+        use crate::scorer::Scorer;
+
+        let searcher = Searcher::new(
+            Vec::new(),
+            Some(SearcherOptions {
+                compatibility: Some(true),
+                preserve_whitespace: Some(true),
+                trim: Some(true),
+                strip_control: Some(true),
+                latin_diacritics_only: Some(true),
+                keep_letters: Some("ñ".to_string()),
+                expand_ligatures: Some(true),
+                fold_superscripts: Some(true),
+                fold_digits: Some(true),
+                ignore_chars: Some("-".to_string()),
+                stopwords: Some(vec!["THE".to_string()]),
+                locale: Some("tr".to_string()),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        let scorer = Scorer::new(&SearchOptions {
+            compatibility: Some(true),
+            preserve_whitespace: Some(true),
+            trim: Some(true),
+            strip_control: Some(true),
+            latin_diacritics_only: Some(true),
+            keep_letters: Some("ñ".to_string()),
+            expand_ligatures: Some(true),
+            fold_superscripts: Some(true),
+            fold_digits: Some(true),
+            ignore_chars: Some("-".to_string()),
+            stopwords: Some(vec!["THE".to_string()]),
+            locale: Some("tr".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        for input in [
+            " The  NIÑO-ﬁle\u{200B} m² 42 ",
+            "İSTANBUL ἄλφα café",
+            "ＡＢＣ ½",
+        ] {
+            assert_eq!(
+                normalize_with(input, &searcher.normalization),
+                scorer.prepare(input)
+            );
+        }
+    }
+}
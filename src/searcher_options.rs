@@ -0,0 +1,191 @@
+use napi_derive::napi;
+
+/// Options used when constructing a [`crate::Searcher`].
+/// All fields are optional when provided from JavaScript/TypeScript; if omitted,
+/// defaults will be filled in from [`Default`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface SearcherOptions {
+///   normalize?: boolean;   // default: true
+///   ignoreCase?: boolean;  // default: true
+///   cacheSize?: number;    // default: 32
+///   minSharedGrams?: number; // default: unset (no trigram index)
+///   prefixIndex?: boolean;   // default: false
+///   maxInputLength?: number; // default: 10000
+///   turkishI?: boolean;      // default: false
+///   stem?: boolean;          // default: false
+///   stopwords?: string[];    // default: []
+///   alphanumericOnly?: boolean; // default: false
+///   compatibility?: boolean;  // default: false
+///   ignoreChars?: string;     // default: ""
+///   foldConfusables?: boolean; // default: false
+///   preserveWhitespace?: boolean; // default: false
+///   locale?: string;          // default: unset
+///   latinDiacriticsOnly?: boolean; // default: false
+///   keepLetters?: string;     // default: ""
+///   expandLigatures?: boolean; // default: false
+///   foldDigits?: boolean;     // default: false
+///   trim?: boolean;           // default: false
+///   stripControl?: boolean;   // default: false
+///   foldSuperscripts?: boolean; // default: false
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone)]
+pub struct SearcherOptions {
+    /// Whether to normalize/clean items and queries (e.g. remove diacritics, collapse whitespace).
+    /// Defaults to `true`.
+    pub normalize: Option<bool>,
+
+    /// Whether to compare case-insensitively.
+    /// Defaults to `true`.
+    pub ignore_case: Option<bool>,
+
+    /// Maximum number of distinct normalized queries whose scores are cached.
+    /// Each cached query holds one score per item, so memory grows with
+    /// `cacheSize * items.length`. Set to `0` to disable caching.
+    /// Defaults to `32`.
+    pub cache_size: Option<u32>,
+
+    /// When set, build a trigram index and only score items sharing at least
+    /// this many distinct character trigrams with the query. Higher values are
+    /// faster but may drop genuine matches; `0` disables the index.
+    /// Defaults to unset (every item is scored).
+    pub min_shared_grams: Option<u32>,
+
+    /// Whether to build a trie over the normalized items so `prefixSearch`
+    /// only visits matching items instead of scanning the whole corpus.
+    /// Defaults to `false`.
+    pub prefix_index: Option<bool>,
+
+    /// Maximum length, in characters, of queries and items.
+    /// Longer queries are rejected with an error; longer items score `0.0`.
+    /// Defaults to [`crate::DEFAULT_MAX_INPUT_LENGTH`].
+    pub max_input_length: Option<u32>,
+
+    /// Whether case folding uses the Turkish rules for the letter I
+    /// (`'I'` ↔ `'ı'`, `'İ'` ↔ `'i'`).
+    /// Defaults to `false`.
+    pub turkish_i: Option<bool>,
+
+    /// Whether to reduce words to their English stems before scoring, so
+    /// `"running"` matches `"run"`. Requires the `stemming` feature.
+    /// Defaults to `false`.
+    pub stem: Option<bool>,
+
+    /// Words to drop before scoring, e.g. `["the", "a"]`.
+    /// Defaults to none.
+    pub stopwords: Option<Vec<String>>,
+
+    /// Whether to keep only alphanumeric characters after the other
+    /// normalization steps, so `"ABC-123"` matches `"abc123"`. Whitespace is
+    /// dropped too, which turns multi-word strings into a single token.
+    /// Defaults to `false`.
+    pub alphanumeric_only: Option<bool>,
+
+    /// Whether to use Unicode compatibility normalization (NFKD/NFKC) so
+    /// full-width and half-width forms match, e.g. `"ＡＢＣ"` and `"ABC"`.
+    /// Defaults to `false`.
+    pub compatibility: Option<bool>,
+
+    /// Characters to remove from the query and items before scoring, e.g.
+    /// `"-"` so `"555-1234"` matches `"5551234"`, or `" "` to ignore spaces.
+    /// Defaults to none.
+    pub ignore_chars: Option<String>,
+
+    /// Whether to fold visually confusable characters to the ones they imitate,
+    /// so a Cyrillic-spoofed `"аpple"` matches `"apple"`.
+    /// Defaults to `false`.
+    pub fold_confusables: Option<bool>,
+
+    /// Whether to keep whitespace exactly as given instead of collapsing runs
+    /// into single spaces and trimming the ends, e.g. when matching code or
+    /// formatted text. Tabs and newlines then count as characters like any
+    /// other.
+    /// Defaults to `false`.
+    pub preserve_whitespace: Option<bool>,
+
+    /// BCP 47 language tag whose case mapping rules apply when folding case.
+    /// `"tr"` and `"az"`, and regional variants such as `"tr-TR"`, fold the
+    /// letter I as `turkish_i` does; other locales use the default Unicode
+    /// rules. Defaults to unset.
+    pub locale: Option<String>,
+
+    /// Whether `normalize` only strips the accents of Latin letters, keeping
+    /// the combining marks of other scripts, such as Hebrew points or Greek
+    /// tonos, where they can change the word.
+    /// Defaults to `false`.
+    pub latin_diacritics_only: Option<bool>,
+
+    /// Precomposed letters that `normalize` keeps whole instead of stripping
+    /// their accents, for languages where they are letters of their own, e.g.
+    /// `"ñå"` so `"niño"` no longer matches `"nino"`. Each letter also keeps
+    /// its other case. Only precomposed input is protected: an `'n'` followed
+    /// by a combining tilde still loses the tilde.
+    /// Defaults to none.
+    pub keep_letters: Option<String>,
+
+    /// Whether to spell out ligatures as their letters, so `"encyclopædia"`
+    /// matches `"encyclopaedia"` and `"ﬁle"` matches `"file"`. Covers `æ`,
+    /// `œ` and the compatibility ligatures such as `ﬁ` and `ĳ`.
+    /// Defaults to `false`.
+    pub expand_ligatures: Option<bool>,
+
+    /// Whether to replace every ASCII digit with the same placeholder before
+    /// scoring, so numbers only match by their shape: `"room 101"` matches
+    /// `"room 202"` exactly, and `"room 1010"` closely. Meant for template
+    /// matching.
+    /// Defaults to `false`.
+    pub fold_digits: Option<bool>,
+
+    /// Whether to trim whitespace from both ends of the query and each item
+    /// even when whitespace isn't collapsed, e.g. with `normalize: false` or
+    /// `preserveWhitespace`. Runs of whitespace in between are kept, and
+    /// diacritics and case are left to the other options.
+    /// Defaults to `false`; collapsing whitespace always trims.
+    pub trim: Option<bool>,
+
+    /// Whether to remove control characters and zero-width characters, such
+    /// as U+200B (zero-width space) and U+FEFF (byte order mark), from the
+    /// query and items before any other normalization, so text pasted from
+    /// the web matches its visible form. Tabs and newlines are kept as
+    /// whitespace.
+    /// Defaults to `false`.
+    pub strip_control: Option<bool>,
+
+    /// Fold superscripts, subscripts and vulgar fractions into plain
+    /// characters, so `"m²"` matches `"m2"` and `"½"` matches `"1/2"`.
+    /// Unlike a compatibility `form` this leaves every other character
+    /// alone.
+    pub fold_superscripts: Option<bool>,
+}
+
+impl Default for SearcherOptions {
+    fn default() -> Self {
+        Self {
+            normalize: true.into(),
+            ignore_case: true.into(),
+            cache_size: Some(32),
+            min_shared_grams: None,
+            prefix_index: false.into(),
+            max_input_length: Some(crate::DEFAULT_MAX_INPUT_LENGTH),
+            turkish_i: false.into(),
+            stem: false.into(),
+            stopwords: None,
+            alphanumeric_only: false.into(),
+            compatibility: false.into(),
+            ignore_chars: None,
+            fold_confusables: false.into(),
+            preserve_whitespace: false.into(),
+            locale: None,
+            latin_diacritics_only: false.into(),
+            keep_letters: None,
+            expand_ligatures: false.into(),
+            fold_digits: false.into(),
+            trim: false.into(),
+            strip_control: false.into(),
+            fold_superscripts: false.into(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use napi_derive::napi;
+
+/// One entry of the sparse similarity matrix returned by
+/// [`crate::similarity_pairs`].
+///
+/// # TypeScript definition (generated by `napi`):
+/// ```ts
+/// interface SimilarityPair {
+///   i: number;     // index of the first item, always below `j`
+///   j: number;     // index of the second item
+///   score: number; // what `similarityMatrix` has at `[i][j]`
+/// }
+/// ```
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimilarityPair {
+    /// Index of the earlier item, which was scored as the query.
+    pub i: u32,
+
+    /// Index of the later item.
+    pub j: u32,
+
+    /// The similarity score of the pair.
+    pub score: f64,
+}
@@ -0,0 +1,146 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::ranking::Ranking;
+use crate::Scored;
+
+/// Keeps the `k` best of a stream of scored items, so a search with a small
+/// `limit` holds `k` results instead of one per matching item.
+///
+/// "Best" is the order of `ranking`, the same one [`crate::rank`] sorts by.
+pub(crate) struct TopK<'a> {
+    k: usize,
+    ranking: Ranking<'a>,
+    heap: BinaryHeap<Worst<'a>>,
+    seen: usize,
+}
+
+impl<'a> TopK<'a> {
+    pub(crate) fn new(k: usize, ranking: Ranking<'a>) -> Self {
+        Self {
+            k,
+            ranking,
+            heap: BinaryHeap::with_capacity(k.saturating_add(1).min(1024)),
+            seen: 0,
+        }
+    }
+
+    /// Offers `scored`, evicting the current worst if it is full and
+    /// `scored` ranks higher.
+    pub(crate) fn push(&mut self, scored: Scored) {
+        self.seen += 1;
+        let scored = Worst {
+            scored,
+            ranking: self.ranking,
+        };
+        if self.heap.len() < self.k {
+            self.heap.push(scored);
+        } else if let Some(mut worst) = self.heap.peek_mut() {
+            if scored < *worst {
+                *worst = scored;
+            }
+        }
+    }
+
+    /// Combines two partial results, as produced by parallel workers.
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        let seen = self.seen + other.seen;
+        for Worst { scored, .. } in other.heap {
+            self.push(scored);
+        }
+        self.seen = seen;
+        self
+    }
+
+    /// Number of items offered so far, including evicted ones.
+    pub(crate) fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// The retained items, best first.
+    pub(crate) fn into_sorted_vec(self) -> Vec<Scored> {
+        // Ascending by `Worst` is best first.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|worst| worst.scored)
+            .collect()
+    }
+}
+
+/// Orders [`Scored`] items so that the worse one compares greater, putting
+/// the next one to evict at the top of the max-heap.
+struct Worst<'a> {
+    scored: Scored,
+    ranking: Ranking<'a>,
+}
+
+impl Ord for Worst<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.scored, &other.scored);
+        self.ranking
+            .compare((a.score, a.index), (b.score, b.index))
+    }
+}
+
+impl PartialOrd for Worst<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Worst<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Worst<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SearchOptions;
+
+    fn scored(index: u32, score: f64) -> Scored {
+        Scored {
+            index,
+            score,
+            raw_score: score,
+        }
+    }
+
+    #[test]
+    fn test_matches_sort_then_truncate() {
+        let scores = [0.5, 0.9, 0.5, 0.1, 0.9, 0.7, 0.5, 0.0, 0.7];
+        let mut expected: Vec<(u32, f64)> = scores
+            .iter()
+            .enumerate()
+            .map(|(i, &s)| (i as u32, s))
+            .collect();
+        expected.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        let opts = SearchOptions::default();
+        let ranking = Ranking::new(&opts, None);
+        for k in 0..=scores.len() + 1 {
+            let (left, right) = scores.split_at(4);
+            let mut a = TopK::new(k, ranking);
+            let mut b = TopK::new(k, ranking);
+            for (i, &s) in left.iter().enumerate() {
+                a.push(scored(i as u32, s));
+            }
+            for (i, &s) in right.iter().enumerate() {
+                b.push(scored((i + left.len()) as u32, s));
+            }
+            let top = b.merge(a);
+
+            assert_eq!(top.seen(), scores.len());
+            let kept: Vec<(u32, f64)> = top
+                .into_sorted_vec()
+                .iter()
+                .map(|s| (s.index, s.score))
+                .collect();
+            assert_eq!(kept, &expected[..k.min(expected.len())], "k = {k}");
+        }
+    }
+}
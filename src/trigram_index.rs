@@ -0,0 +1,192 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// A character trigram.
+type Trigram = [char; 3];
+
+/// Inverted index from character trigrams to the ids of items containing
+/// them, used by [`crate::Searcher`] to skip items that can't be close to the
+/// query.
+///
+/// Strings are padded with a space on both ends before being split, so short
+/// strings and the first/last characters of longer ones still produce
+/// trigrams.
+#[derive(Default)]
+pub(crate) struct TrigramIndex {
+    postings: HashMap<Trigram, Vec<u32>>,
+}
+
+impl TrigramIndex {
+    /// Indexes `text` under `id`.
+    pub(crate) fn insert(&mut self, id: u32, text: &str) {
+        for gram in trigrams(text) {
+            self.postings
+                .entry(gram)
+                .or_default()
+                .push(id);
+        }
+    }
+
+    /// Removes the entries `text` was indexed under for `id`.
+    pub(crate) fn remove(&mut self, id: u32, text: &str) {
+        for gram in trigrams(text) {
+            if let Some(ids) = self.postings.get_mut(&gram) {
+                ids.retain(|&other| other != id);
+                if ids.is_empty() {
+                    self.postings.remove(&gram);
+                }
+            }
+        }
+    }
+
+    /// Removes every entry from the index.
+    pub(crate) fn clear(&mut self) {
+        self.postings.clear();
+    }
+
+    /// Returns the ids of items sharing at least `min_shared` distinct
+    /// trigrams with `query`, in ascending order.
+    ///
+    /// Returns `None` when `query` has no trigrams at all (only for the empty
+    /// string), in which case every item has to be considered.
+    pub(crate) fn candidates(
+        &self,
+        query: &str,
+        min_shared: usize,
+    ) -> Option<Vec<u32>> {
+        let grams = trigrams(query);
+        if grams.is_empty() {
+            return None;
+        }
+
+        let mut shared: HashMap<u32, usize> = HashMap::new();
+        for gram in &grams {
+            for &id in self
+                .postings
+                .get(gram)
+                .into_iter()
+                .flatten()
+            {
+                *shared.entry(id).or_default() += 1;
+            }
+        }
+
+        let mut ids: Vec<u32> = shared
+            .into_iter()
+            .filter(|&(_, count)| count >= min_shared)
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        Some(ids)
+    }
+}
+
+thread_local! {
+    /// The last query [`shares_trigrams`] saw and its trigrams, one per
+    /// thread, so a search splits its query only once per thread.
+    static QUERY_GRAMS: RefCell<(String, HashSet<Trigram>)> =
+        RefCell::default();
+}
+
+/// Whether `item` shares at least `min_shared` distinct trigrams with
+/// `query`, a quick check that rules out items too different from the query
+/// to be worth an edit-distance DP. Trigrams are padded as in
+/// [`TrigramIndex`].
+pub(crate) fn shares_trigrams(
+    query: &str,
+    item: &str,
+    min_shared: usize,
+) -> bool {
+    if min_shared == 0 {
+        return true;
+    }
+
+    QUERY_GRAMS.with_borrow_mut(|(last, grams)| {
+        if last != query {
+            query.clone_into(last);
+            *grams = trigrams(query);
+        }
+        if grams.len() < min_shared {
+            return false;
+        }
+
+        if item.is_empty() {
+            return false;
+        }
+        let mut shared: Vec<Trigram> = Vec::with_capacity(min_shared);
+        let mut gram = [' ', ' ', ' '];
+        for (i, c) in item
+            .chars()
+            .chain(std::iter::once(' '))
+            .enumerate()
+        {
+            gram = [gram[1], gram[2], c];
+            if i >= 1 && grams.contains(&gram) && !shared.contains(&gram) {
+                shared.push(gram);
+                if shared.len() == min_shared {
+                    return true;
+                }
+            }
+        }
+        false
+    })
+}
+
+/// Distinct padded trigrams of `text`.
+fn trigrams(text: &str) -> HashSet<Trigram> {
+    if text.is_empty() {
+        return HashSet::new();
+    }
+
+    let padded: Vec<char> = std::iter::once(' ')
+        .chain(text.chars())
+        .chain(std::iter::once(' '))
+        .collect();
+
+    padded
+        .windows(3)
+        .map(|window| [window[0], window[1], window[2]])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates_share_trigrams() {
+        let mut index = TrigramIndex::default();
+        index.insert(0, "apple");
+        index.insert(1, "maple");
+        index.insert(2, "kiwi");
+
+        assert_eq!(index.candidates("apple", 1), Some(vec![0, 1]));
+        assert_eq!(index.candidates("apple", 3), Some(vec![0]));
+        assert_eq!(index.candidates("", 1), None);
+    }
+
+    #[test]
+    fn test_shares_trigrams_counts_distinct_ones() {
+        assert!(shares_trigrams("apple", "maple", 2));
+        assert!(!shares_trigrams("apple", "maple", 3));
+        assert!(shares_trigrams("apple", "apple", 5));
+        // Repeated trigrams of the item only count once.
+        assert!(!shares_trigrams("aaab", "aaaaaa", 3));
+        assert!(!shares_trigrams("a", "", 1));
+        assert!(shares_trigrams("kiwi", "banana", 0));
+        assert!(!shares_trigrams("kiwi", "banana", 1));
+    }
+
+    #[test]
+    fn test_remove_drops_postings() {
+        let mut index = TrigramIndex::default();
+        index.insert(0, "apple");
+        index.insert(1, "maple");
+
+        index.remove(0, "apple");
+        assert_eq!(index.candidates("apple", 1), Some(vec![1]));
+        assert!(!index
+            .postings
+            .contains_key(&[' ', 'a', 'p']));
+    }
+}
@@ -1,4 +1,30 @@
-const { search, fuzzy } = require("../index.js");
+const {
+  search,
+  searchWithCount,
+  bestMatch,
+  bestMatchWithEdits,
+  searchIndices,
+  searchArrays,
+  searchStream,
+  searchGrouped,
+  searchGroupedByInitial,
+  searchAny,
+  searchMulti,
+  searchAliases,
+  searchBy,
+  suggestThreshold,
+  similarityMatrix,
+  similarityPairs,
+  fuzzy,
+  score,
+  scoreDetailed,
+  isMatch,
+  similarity,
+  fuzzyBytes,
+  normalizeAscii,
+  setNormalizationCacheSize,
+  Searcher,
+} = require("../index.js");
 
 describe("Fast Fuzzy Search", () => {
   const testData = [
@@ -35,6 +61,62 @@ describe("Fast Fuzzy Search", () => {
       expect(results.length).toBeLessThanOrEqual(3);
     });
 
+    test("should tell a zero, null and missing limit apart", () => {
+      const items = Array.from({ length: 30 }, (_, i) => `item${i}`);
+      expect(search("item", items)).toHaveLength(10);
+      expect(search("item", items, {})).toHaveLength(10);
+      expect(search("item", items, { limit: undefined })).toHaveLength(10);
+      expect(search("item", items, { limit: null })).toHaveLength(30);
+      expect(search("item", items, { limit: 0 })).toHaveLength(0);
+    });
+
+    test("should round fractional limits down and reject negative ones", () => {
+      const items = Array.from({ length: 30 }, (_, i) => `item${i}`);
+      expect(search("item", items, { limit: 2.7 })).toHaveLength(2);
+      expect(search("item", items, { limit: Infinity })).toHaveLength(30);
+      expect(() => search("item", items, { limit: -1 })).toThrow();
+      expect(() => search("item", items, { limit: NaN })).toThrow();
+      const searcher = new Searcher(items);
+      expect(searcher.query("item", { limit: null })).toHaveLength(30);
+      expect(() => searcher.query("item", { limit: -1 })).toThrow();
+    });
+
+    test("should keep results tied at the limit with includeTies", () => {
+      const items = ["apply", "apple", "appla", "grape", "applo"];
+      const options = { limit: 2, threshold: 0.5 };
+      expect(search("apply", items, options)).toHaveLength(2);
+      const tied = search("apply", items, { ...options, includeTies: true });
+      expect(tied.map((r) => r.item)).toEqual([
+        "apply",
+        "apple",
+        "appla",
+        "applo",
+      ]);
+      const top = search("apply", items, { limit: 1, includeTies: true });
+      expect(top).toHaveLength(1);
+    });
+
+    test("should return reproducible approximate results with sampleLimit", () => {
+      const items = Array.from({ length: 5000 }, (_, i) => `item ${i}`);
+      const options = { sampleLimit: 200, sampleSeed: 7 };
+      const first = searchWithCount("item 4321", items, options);
+      expect(first.approximate).toBe(true);
+      expect(first.total).toBe(200);
+      expect(searchWithCount("item 4321", items, options)).toEqual(first);
+      expect(searchWithCount("item 4321", items).approximate).toBe(false);
+    });
+
+    test("should skip results with offset", () => {
+      const all = search("app", testData, { limit: 100 });
+      const page = search("app", testData, { offset: 2, limit: 2 });
+      expect(page).toEqual(all.slice(2, 4));
+      expect(search("app", testData, { offset: 2 })).toEqual(all.slice(2));
+    });
+
+    test("should return nothing for an offset past the end", () => {
+      expect(search("app", testData, { offset: 100 })).toEqual([]);
+    });
+
     test("should handle case insensitive search", () => {
       const results = search("APPLE", testData, { ignoreCase: true });
       expect(results[0].item).toBe("apple");
@@ -45,6 +127,881 @@ describe("Fast Fuzzy Search", () => {
       const results = search("cafe", data, { normalize: true });
       expect(results[0].item).toBe("café");
     });
+
+    test("should fold case beyond simple lowercasing", () => {
+      const results = search("STRASSE", ["straße", "strand"]);
+      expect(results[0].item).toBe("straße");
+      expect(results[0].score).toBe(1.0);
+    });
+
+    test("should apply Turkish I rules when turkishI is set", () => {
+      const options = { normalize: false, turkishI: true };
+      const results = search("istanbul", ["İstanbul"], options);
+      expect(results[0].score).toBe(1.0);
+    });
+
+    test("should apply Turkish I rules for a Turkish locale", () => {
+      const items = ["ılık", "ilik"];
+      expect(search("ILIK", items)[0].item).toBe("ilik");
+      expect(search("ILIK", items, { locale: "tr" })[0].item).toBe("ılık");
+      expect(search("İLİK", items, { locale: "tr-TR" })[0].item).toBe("ilik");
+    });
+
+    test("should keep non-Latin marks with latinDiacriticsOnly", () => {
+      const options = { latinDiacriticsOnly: true };
+      expect(search("cafe", ["café"], options)[0].score).toBe(1.0);
+      expect(search("αθηναι", ["ἀθῆναι"])[0].score).toBe(1.0);
+      expect(search("αθηναι", ["ἀθῆναι"], options)[0].score).toBeLessThan(1.0);
+    });
+
+    test("should tell niño from nino with keepLetters", () => {
+      const options = { keepLetters: "ñ" };
+      expect(search("niño", ["nino"])[0].score).toBe(1.0);
+      expect(search("niño", ["nino"], options)[0].score).toBeLessThan(1.0);
+      const [result] = search("NIÑO", ["nino", "niño"], options);
+      expect(result.item).toBe("niño");
+      expect(result.score).toBe(1.0);
+    });
+
+    test("should stem words when stem is set", () => {
+      const results = search("running shoes", ["run shoe", "rung shoes"], {
+        stem: true,
+      });
+      expect(results[0].item).toBe("run shoe");
+      expect(results[0].score).toBe(1.0);
+    });
+
+    test("should drop stopwords before scoring", () => {
+      const results = search("the apple", ["apple"], { stopwords: ["the"] });
+      expect(results[0].score).toBe(1.0);
+    });
+
+    test("should require every query token when requireAllTokens is set", () => {
+      const data = ["red bicycle", "reed cart"];
+      const results = search("red car", data, {
+        requireAllTokens: true,
+        threshold: 0.1,
+      });
+      expect(results).toHaveLength(1);
+      expect(results[0].item).toBe("reed cart");
+    });
+
+    test("should score over-length items as 0 instead of comparing them", () => {
+      const long = "a".repeat(20);
+      const results = search("apple", [long, "apple"], { maxInputLength: 10 });
+      expect(results[0].item).toBe("apple");
+      expect(results[1].score).toBe(0.0);
+    });
+
+    test("should reject an over-length query", () => {
+      const long = "a".repeat(20);
+      expect(() => search(long, testData, { maxInputLength: 10 })).toThrow();
+    });
+
+    test("should explain scores when requested", () => {
+      const results = search("kitten", ["sitting"], { explain: true });
+      expect(results[0].explanation).toEqual({
+        distance: 3,
+        maxLength: 7,
+        algorithm: "levenshtein",
+        boosts: [],
+      });
+    });
+
+    test("should not explain scores by default", () => {
+      const results = search("kitten", ["sitting"]);
+      expect(results[0].explanation).toBeUndefined();
+    });
+
+    test("should score substring matches 1 and others 0", () => {
+      const results = search("app", ["pineapple", "appel", "grape"], {
+        exactSubstring: true,
+      });
+      expect(results.map((r) => [r.item, r.score])).toEqual([
+        ["pineapple", 1],
+        ["appel", 1],
+        ["grape", 0],
+      ]);
+
+      const strict = search("apple", ["appel", "apple pie"], {
+        exactSubstring: true,
+        threshold: 1,
+      });
+      expect(strict.map((r) => r.item)).toEqual(["apple pie"]);
+    });
+
+    test("should let weights outrank a closer match", () => {
+      const results = search("apple", ["apple", "maple"], {
+        weights: [1, 2],
+        includeRawScore: true,
+      });
+      expect(results[0].item).toBe("maple");
+      expect(results[0].score).toBeCloseTo(1.2);
+      expect(results[0].rawScore).toBeCloseTo(0.6);
+      expect(() =>
+        search("apple", ["apple", "maple"], { weights: [1] })
+      ).toThrow();
+    });
+
+    test("should return nothing for queries below minQueryLength", () => {
+      expect(search("a", testData, { minQueryLength: 2 })).toEqual([]);
+      expect(search("", testData, { minQueryLength: 1 })).toEqual([]);
+      expect(search("ap", testData, { minQueryLength: 2 }).length).toBe(
+        Math.min(testData.length, 10)
+      );
+    });
+
+    test("should return every item for an empty query when asked", () => {
+      expect(search("", testData, { threshold: 0.1 })).toEqual([]);
+      const all = search("", testData, {
+        emptyQueryReturnsAll: true,
+        limit: 100,
+      });
+      expect(all.map((r) => r.item)).toEqual(testData);
+      expect(all.every((r) => r.score === 1)).toBe(true);
+    });
+
+    test("should match serial numbers with alphanumericOnly", () => {
+      const serials = ["SN 0042-B", "abc123", "ABC-124"];
+      const results = search("ABC-123", serials, { alphanumericOnly: true });
+      expect(results[0].item).toBe("abc123");
+      expect(results[0].score).toBe(1);
+      expect(search("abc-123", serials)[0].score).toBeLessThan(1);
+    });
+
+    test("should score short strings more gently with the ratio formula", () => {
+      const [distance] = search("cat", ["cut"]);
+      const [ratio] = search("cat", ["cut"], { scoreFormula: "ratio" });
+      expect(distance.score).toBeCloseTo(2 / 3);
+      expect(ratio.score).toBeCloseTo(5 / 6);
+      expect(() => search("cat", ["cut"], { scoreFormula: "nope" })).toThrow();
+    });
+
+    test("should match full-width forms with compatibility", () => {
+      const items = ["ｶﾀｶﾅ", "ＡＢＣ１２３"];
+      const options = { compatibility: true };
+      expect(search("abc123", items, options)[0].item).toBe("ＡＢＣ１２３");
+      expect(search("カタカナ", items, options)[0].score).toBe(1);
+      expect(search("abc123", items)[0].score).toBeLessThan(1);
+    });
+
+    test("should ignore the characters listed in ignoreChars", () => {
+      const phones = ["555 1299", "5551234"];
+      const [best] = search("555-1234", phones, { ignoreChars: "-" });
+      expect(best.item).toBe("5551234");
+      expect(best.score).toBe(1);
+    });
+
+    test("should rank suffix matches higher with boostSuffix", () => {
+      const files = ["pdfs", "report.pdf"];
+      expect(search("pdf", files)[0].item).toBe("pdfs");
+      const [best] = search("pdf", files, { boostSuffix: 0.5, explain: true });
+      expect(best.item).toBe("report.pdf");
+      expect(best.explanation.boosts).toEqual([{ name: "suffix", value: 0.5 }]);
+    });
+
+    test("should favour shared prefixes with prefixBoostWeight", () => {
+      const items = ["amrtha", "marhta"];
+      const plain = search("martha", items);
+      expect(plain[0].score).toBe(plain[1].score);
+      const options = { prefixBoostWeight: 0.1 };
+      const [best, other] = search("martha", items, options);
+      expect(best.item).toBe("marhta");
+      expect(best.score).toBeCloseTo(plain[0].score + 0.3 * (1 - 4 / 6));
+      expect(other.score).toBe(plain[0].score);
+      expect(fuzzy("abcd", "abce", { prefixBoostWeight: 1 })).toBe(1);
+    });
+
+    test("should normalize with a custom normalizer", () => {
+      const items = ["apple", "Äpple", "banana"];
+      const normalizer = (s) => s.toUpperCase();
+      const results = search("APPLE", items, { normalizer, includeNormalized: true });
+      expect(results[0].item).toBe("apple");
+      expect(results[0].normalizedItem).toBe("APPLE");
+      expect(results[0].score).toBe(1);
+      // The built-in pipeline is bypassed, so the accent is kept.
+      expect(results[1].item).toBe("Äpple");
+      expect(results[1].score).toBeLessThan(1);
+
+      expect(bestMatch("apple", items, { normalizer }).item).toBe("apple");
+      expect(searchIndices("apple", items, { normalizer })[0].index).toBe(0);
+      expect(() => search("a", items, { normalizer: () => { throw new Error("boom"); } })).toThrow();
+      expect(() => search("a", items, { normalizer: () => 1 })).toThrow();
+      expect(() => fuzzy("a", "b", { normalizer })).toThrow();
+    });
+
+    test("should match wildcard patterns with wildcard", () => {
+      const items = ["apple", "ace", "banana"];
+      const starred = search("a*e", items, { wildcard: true, threshold: 0.5 });
+      expect(starred.map((r) => r.item)).toEqual(["apple", "ace"]);
+      expect(starred[0].score).toBe(1);
+
+      const single = search("a?e", items, { wildcard: true, threshold: 0.5 });
+      expect(single[0].item).toBe("ace");
+      expect(single[0].score).toBe(1);
+      expect(fuzzy("a?e", "apple", { wildcard: true })).toBe(0);
+      expect(fuzzy("a*e", "apple")).toBeLessThan(1);
+    });
+
+    test("should prefilter by shared trigrams with minTrigramOverlap", () => {
+      const items = ["apple", "apply", "maple", "banana", "orange", "kiwi"];
+      const options = { threshold: 0.5, limit: null };
+      const bruteForce = search("aple", items, options);
+      expect(search("aple", items, { ...options, minTrigramOverlap: 1 })).toEqual(bruteForce);
+
+      // Without a threshold, items sharing too few trigrams are left out.
+      expect(search("apple", items, { minTrigramOverlap: 2 }).map((r) => r.item)).toEqual([
+        "apple",
+        "apply",
+        "maple",
+      ]);
+    });
+
+    test("should favour one long gap with the alignment algorithm", () => {
+      const items = ["axbcxdexfgxh", "abcdxxxxefgh"];
+      const results = search("abcdefgh", items, { algorithm: "alignment" });
+      expect(results[0].item).toBe("abcdxxxxefgh");
+      expect(results[0].score).toBeGreaterThan(results[1].score);
+
+      const uniform = { algorithm: "alignment", gapOpen: 1, gapExtend: 1 };
+      expect(fuzzy("abcdefgh", "abcdxxxxefgh", uniform)).toBe(fuzzy("abcdefgh", "abcdxxxxefgh"));
+      expect(() => fuzzy("a", "b", { algorithm: "alignment", gapOpen: -1 })).toThrow();
+
+      const alignment = { algorithm: "alignment" };
+      expect(fuzzy("abc", "xyz", alignment)).toBe(0);
+      expect(fuzzy("a", "b", alignment)).toBe(0);
+      const unrelated = search("abc", ["xyz", "qrs"], alignment);
+      expect(unrelated.map((r) => r.score)).toEqual([0, 0]);
+    });
+
+    test("should trim the ends without normalizing with trim", () => {
+      const items = ["Apple  Pie"];
+      const raw = { normalize: false, ignoreCase: false };
+      expect(search("  Apple  Pie ", items, raw)[0].score).toBeLessThan(1);
+
+      const results = search("  Apple  Pie ", items, {
+        ...raw,
+        trim: true,
+        includeNormalized: true,
+      });
+      expect(results[0].score).toBe(1);
+      expect(results[0].normalizedItem).toBe("Apple  Pie");
+    });
+
+    test("should favour equal word counts with wordCountBonus", () => {
+      const items = ["John Smith Jr", "John Smith", "Jon Smth"];
+      const options = { requireAllTokens: true };
+      const plain = search("john smith", items, options);
+      expect(plain[0].score).toBe(plain[1].score);
+
+      const bonus = search("john smith", items, {
+        ...options,
+        wordCountBonus: 0.05,
+      });
+      expect(bonus.map((r) => r.item)).toEqual([
+        "John Smith",
+        "John Smith Jr",
+        "Jon Smth",
+      ]);
+      expect(() => search("a", items, { wordCountBonus: -1 })).toThrow();
+    });
+
+    test("should split tokens at word boundaries with unicodeWords", () => {
+      const options = { requireAllTokens: true };
+      const items = ["new-york city", "boston"];
+      expect(search("new york", items, options)[0].score).toBe(0);
+
+      const words = search("new york", items, {
+        ...options,
+        unicodeWords: true,
+      });
+      expect(words[0].item).toBe("new-york city");
+      expect(words[0].score).toBe(1);
+
+      const cjk = search("東京", ["東京タワー"], {
+        ...options,
+        unicodeWords: true,
+        includeTokenScores: true,
+      });
+      expect(cjk[0].item).toBe("東京タワー");
+      expect(cjk[0].tokenScores).toEqual([1, 1]);
+    });
+
+    test("should clamp scores with scoreFloor and scoreCeil", () => {
+      const items = ["apple", "appla", "axxxx", "zzzzz"];
+      const options = { scoreFloor: 0.3, scoreCeil: 0.9 };
+      const scores = search("apple", items, options).map((r) => r.score);
+      expect(scores).toEqual([0.9, 0.8, 0.3, 0]);
+
+      // The floor is applied before the threshold.
+      const kept = search("apple", items, { ...options, threshold: 0.3 });
+      expect(kept.map((r) => r.item)).toEqual(["apple", "appla", "axxxx"]);
+      expect(search("apple", items, { ...options, threshold: 0.95 })).toEqual(
+        []
+      );
+      const inverted = { scoreFloor: 0.6, scoreCeil: 0.5 };
+      expect(() => search("a", items, inverted)).toThrow();
+    });
+
+    test("should ignore zero-width characters with stripControl", () => {
+      const items = ["\uFEFFcafe\u200Bbar", "cafe bar"];
+      expect(search("cafebar", items)[0].score).toBeLessThan(1);
+      const [best] = search("cafebar", items, { stripControl: true });
+      expect(best.item).toBe("\uFEFFcafe\u200Bbar");
+      expect(best.score).toBe(1);
+    });
+
+    test("should score each query token with includeTokenScores", () => {
+      const results = search("red car", ["Reed Cart", "red bus"], {
+        includeTokenScores: true,
+        threshold: 0,
+      });
+      const byItem = Object.fromEntries(
+        results.map((r) => [r.item, r.tokenScores]),
+      );
+      expect(byItem["Reed Cart"]).toEqual([0.75, 0.75]);
+      expect(byItem["red bus"]).toEqual([1, 0]);
+      expect(search("red car", ["red car"])[0].tokenScores).toBeUndefined();
+    });
+
+    test("should fold every Greek sigma to the same letter", () => {
+      const items = ["ΟΔΟΣ", "όδος", "οδοσ"];
+      const results = search("οδος", items);
+      expect(results.map((r) => r.score)).toEqual([1, 1, 1]);
+      expect(search("οδος", items, { ignoreCase: false })[0].item).toBe("όδος");
+    });
+
+    test("should match superscripts with foldSuperscripts", () => {
+      const items = ["area in m²", "½ cup sugar"];
+      expect(search("area in m2", items)[0].score).toBeLessThan(1);
+      const options = { foldSuperscripts: true };
+      expect(search("area in m2", items, options)[0].item).toBe("area in m²");
+      expect(search("area in m2", items, options)[0].score).toBe(1);
+      expect(search("1/2 cup sugar", items, options)[0].score).toBe(1);
+    });
+
+    test("should compare digits by shape with foldDigits", () => {
+      const plain = fuzzy("room 101", "room 202");
+      const folded = fuzzy("room 101", "room 202", { foldDigits: true });
+      expect(folded).toBe(1);
+      expect(plain).toBeLessThan(folded);
+      expect(fuzzy("room 101", "room 1010", { foldDigits: true })).toBeLessThan(1);
+    });
+
+    test("should match ligatures with expandLigatures", () => {
+      const items = ["encyclopædia", "\uFB01le", "œuvre"];
+      const results = search("encyclopaedia", items, { expandLigatures: true });
+      expect(results[0].item).toBe("encyclopædia");
+      expect(results[0].score).toBe(1);
+      expect(search("file", items, { expandLigatures: true })[0].score).toBe(1);
+      expect(search("oeuvre", items, { expandLigatures: true })[0].score).toBe(1);
+      expect(search("encyclopaedia", items)[0].score).toBeLessThan(1);
+    });
+
+    test("should match Cyrillic-spoofed items with foldConfusables", () => {
+      const spoofed = "\u0430pple";
+      expect(search("apple", [spoofed])[0].score).toBeLessThan(1);
+      const [best] = search("apple", [spoofed], { foldConfusables: true });
+      expect(best.score).toBe(1);
+    });
+
+    test("should penalize length mismatches with lengthPenalty", () => {
+      const items = ["cataclysmic", "car"];
+      const plain = search("cat", items);
+      const penalized = search("cat", items, { lengthPenalty: 1 });
+      expect(penalized[1].item).toBe("cataclysmic");
+      expect(penalized[1].score).toBeCloseTo((plain[1].score * 3) / 11);
+      expect(penalized[0].score).toBe(plain[0].score);
+      expect(() => search("cat", items, { lengthPenalty: -1 })).toThrow();
+    });
+
+    test("should drop items beyond maxDistance", () => {
+      const items = ["apple", "apply", "appeal", "maple", "pear"];
+      const results = search("appel", items, { maxDistance: 2 });
+      expect(results.map((r) => r.item)).toEqual(["appeal", "apple", "apply"]);
+      const strict = search("appel", items, { maxDistance: 2, threshold: 0.7 });
+      expect(strict.map((r) => r.item)).toEqual(["appeal"]);
+    });
+
+    test("should count whole words with algorithm word-level", () => {
+      const items = ["the fox", "the quick brown dog"];
+      const options = { algorithm: "word-level" };
+      const [best] = search("the quick fox", items, options);
+      expect(best.item).toBe("the fox");
+      expect(best.score).toBeCloseTo(2 / 3);
+      expect(fuzzy("a b c d", "a x c d", options)).toBe(0.75);
+      expect(fuzzy("the fox", "the fox jumps", options)).toBeCloseTo(2 / 3);
+    });
+
+    test("should count adjacent swaps as one edit with algorithm osa", () => {
+      const [osa] = search("from", ["form"], { algorithm: "osa" });
+      const [levenshtein] = search("from", ["form"]);
+      expect(osa.score).toBe(0.75);
+      expect(levenshtein.score).toBe(0.5);
+      expect(() =>
+        search("from", ["form"], { algorithm: "damerau" })
+      ).toThrow();
+    });
+
+    test("should match names that sound alike with phonetic", () => {
+      const names = ["Smyth", "Smithers", "Rupert"];
+      const [best] = search("Smith", names, { phonetic: true });
+      expect([best.item, best.score]).toEqual(["Smyth", 1]);
+      const [robert] = search("Robert", names, { phonetic: true });
+      expect([robert.item, robert.score]).toEqual(["Rupert", 1]);
+    });
+
+    test("should attach the normalized item with includeNormalized", () => {
+      const [result] = search("creme", ["Crème Brûlée"], {
+        includeNormalized: true,
+      });
+      expect(result.normalizedItem).toBe("creme brulee");
+      const [plain] = search("creme", ["Crème Brûlée"]);
+      expect(plain.normalizedItem).toBeUndefined();
+    });
+
+    test("should count emoji as one character with graphemes", () => {
+      const family = "\u{1F468}\u200D\u{1F469}\u200D\u{1F467}";
+      const [byGrapheme] = search(`${family}x`, [`${family}y`], {
+        graphemes: true,
+      });
+      const [byChar] = search(`${family}x`, [`${family}y`]);
+      expect(byGrapheme.score).toBe(0.5);
+      expect(byChar.score).toBeGreaterThan(byGrapheme.score);
+    });
+
+    test("should break score ties by descending sortKeys", () => {
+      const items = ["cart", "card", "care"];
+      const indices = (options) =>
+        search("car", items, options).map((r) => r.index);
+      expect(indices({})).toEqual([0, 1, 2]);
+      expect(indices({ sortKeys: [1, 3, 2] })).toEqual([1, 2, 0]);
+      expect(() => search("car", items, { sortKeys: [1] })).toThrow();
+    });
+
+    test("should give accented letters partial credit with accentCost", () => {
+      const score = (options) => search("resume", ["résumé"], options)[0].score;
+      const partial = score({ accentCost: 0.25 });
+      expect(score({})).toBe(1);
+      expect(partial).toBeGreaterThan(score({ accentCost: 1 }));
+      expect(1).toBeGreaterThan(partial);
+    });
+
+    test("should order ties naturally with naturalOrder", () => {
+      const items = ["v10", "v9", "v1.2", "v1.10"];
+      const options = { exactSubstring: true, naturalOrder: true };
+      const results = search("v", items, options).map((r) => r.item);
+      expect(results).toEqual(["v1.2", "v1.10", "v9", "v10"]);
+    });
+
+    test("should divide by the chosen length with scoreDenominator", () => {
+      const score = (scoreDenominator) =>
+        search("app", ["apple"], { scoreDenominator })[0].score;
+      expect(score("max")).toBeCloseTo(0.6);
+      expect(score("min")).toBeCloseTo(1 / 3);
+      expect(score("item")).toBeCloseTo(0.6);
+      expect(() => score("sum")).toThrow();
+    });
+
+    test("should keep tabs and double spaces with preserveWhitespace", () => {
+      const items = ["a\tb", "a  b", "a b"];
+      const options = { preserveWhitespace: true, includeNormalized: true };
+      const results = search("a  b", items, options);
+      expect(results[0].item).toBe("a  b");
+      expect(results[0].score).toBe(1);
+      expect(results.map((r) => r.normalizedItem).sort()).toEqual(
+        ["a\tb", "a  b", "a b"].sort(),
+      );
+    });
+
+    test("should blend algorithms into a weighted mean", () => {
+      const score = (algorithm) =>
+        search("martha", ["marhta"], { algorithm })[0].score;
+      const levenshtein = score("levenshtein");
+      const osa = score("osa");
+      const blended = score([
+        { name: "levenshtein", weight: 1 },
+        { name: "osa", weight: 3 },
+      ]);
+      expect(blended).toBeCloseTo(0.25 * levenshtein + 0.75 * osa);
+      expect(score([{ name: "jaro-winkler" }])).toBeCloseTo(0.961, 3);
+      expect(() => score([])).toThrow();
+    });
+
+    test("should round scores with precision", () => {
+      const items = ["abcdef", "abcdex", "abcdxy"];
+      const scores = search("abcdex", items, { precision: 2 }).map(
+        (r) => r.score,
+      );
+      expect(scores).toEqual([1, 0.83, 0.67]);
+      expect(fuzzy("abcdef", "abcdex", { precision: 3 })).toBe(0.833);
+    });
+
+    test("should weight early query tokens with positionDecay", () => {
+      const options = { requireAllTokens: true, positionDecay: 0.5 };
+      const [inOrder] = search("red car", ["red cart"], options);
+      const [reordered] = search("car red", ["red cart"], options);
+      expect(inOrder.score).toBeGreaterThan(reordered.score);
+
+      const flat = { requireAllTokens: true };
+      expect(search("red car", ["red cart"], flat)[0].score).toBe(
+        search("car red", ["red cart"], flat)[0].score,
+      );
+      expect(() => search("a", ["a"], { positionDecay: 0 })).toThrow();
+    });
+  });
+
+  describe("searchWithCount", () => {
+    test("should count matches before applying limit", () => {
+      const data = Array.from({ length: 50 }, (_, i) => `item${i}`);
+      const { results, total } = searchWithCount("item", data, {
+        limit: 10,
+        threshold: 0.5,
+      });
+      expect(results).toHaveLength(10);
+      expect(total).toBe(50);
+    });
+
+    test("should count only items passing the threshold", () => {
+      const { results, total } = searchWithCount("apple", testData, {
+        threshold: 1.0,
+      });
+      expect(total).toBe(1);
+      expect(results[0].item).toBe("apple");
+    });
+
+    test("should tell an empty corpus apart from no matches", () => {
+      expect(searchWithCount("x", [])).toEqual({
+        results: [],
+        total: 0,
+        itemCount: 0,
+        approximate: false,
+      });
+      const noMatch = searchWithCount("zzz", testData, { threshold: 1.0 });
+      expect(noMatch.total).toBe(0);
+      expect(noMatch.itemCount).toBe(testData.length);
+    });
+
+    test("should not throw for an empty corpus with any options", () => {
+      const variants = [
+        { limit: 0, offset: 3, threshold: 1.0 },
+        { explain: true, includeRawScore: true, naturalOrder: true },
+        { requireAllTokens: true, weights: [], sortKeys: [] },
+        { emptyQueryReturnsAll: true, precision: 2 },
+      ];
+      for (const options of variants) {
+        expect(searchWithCount("", [], options).itemCount).toBe(0);
+        expect(search("apple pie", [], options)).toEqual([]);
+      }
+    });
+  });
+
+  describe("bestMatch", () => {
+    test("should return the single best match", () => {
+      const best = bestMatch("aple", testData, { threshold: 0.7 });
+      expect(best.item).toBe("apple");
+      expect(best.index).toBe(0);
+    });
+
+    test("should return null when nothing passes the threshold", () => {
+      expect(bestMatch("zzzz", testData, { threshold: 0.7 })).toBeNull();
+    });
+  });
+
+  describe("searchIndices", () => {
+    test("should return the same ranking as search without items", () => {
+      const full = search("appl", testData);
+      const indices = searchIndices("appl", testData);
+      expect(indices).toEqual(
+        full.map(({ index, score }) => ({ index, score }))
+      );
+      expect(indices[0].item).toBeUndefined();
+    });
+  });
+
+  describe("searchArrays", () => {
+    test("should return the ranking as parallel typed arrays", () => {
+      const full = search("appl", testData, { limit: null });
+      const { indices, scores } = searchArrays("appl", testData, { limit: null });
+      expect(indices instanceof Uint32Array).toBe(true);
+      expect(scores instanceof Float64Array).toBe(true);
+      expect(Array.from(indices)).toEqual(full.map((r) => r.index));
+      expect(Array.from(scores)).toEqual(full.map((r) => r.score));
+      expect(testData[indices[0]]).toBe(full[0].item);
+    });
+  });
+
+  describe("searchStream", () => {
+    test("should call back once per item above the threshold", async () => {
+      const options = { threshold: 0.3, limit: null };
+      const expected = search("appl", testData, options);
+      const streamed = [];
+      const count = await searchStream(
+        "appl",
+        testData,
+        (result) => streamed.push(result),
+        options
+      );
+      expect(count).toBe(expected.length);
+      expect(streamed).toHaveLength(expected.length);
+      // Corpus order, unlike search.
+      const indices = streamed.map((r) => r.index);
+      expect(indices).toEqual([...indices].sort((a, b) => a - b));
+    });
+
+    test("should stream the search page with streamOrder score", async () => {
+      const streamed = [];
+      await searchStream("appl", testData, (r) => streamed.push(r), {
+        limit: 3,
+        streamOrder: "score",
+      });
+      expect(streamed).toEqual(search("appl", testData, { limit: 3 }));
+    });
+
+    test("should throw on invalid options before streaming", () => {
+      const stream = () =>
+        searchStream("appl", testData, () => {}, { limit: -1 });
+      expect(stream).toThrow();
+    });
+  });
+
+  describe("bestMatchWithEdits", () => {
+    test("should list the edits from kitten to sitting", () => {
+      const found = bestMatchWithEdits("kitten", ["sitting", "banana"]);
+      expect(found.result.item).toBe("sitting");
+      expect(found.edits).toEqual([
+        { op: "substitute", position: 0, from: "k", to: "s" },
+        { op: "substitute", position: 4, from: "e", to: "i" },
+        { op: "insert", position: 6, to: "g" },
+      ]);
+    });
+
+    test("should return null without a match", () => {
+      expect(bestMatchWithEdits("kitten", [], {})).toBe(null);
+    });
+  });
+
+  describe("searchGrouped", () => {
+    test("should bucket results by score edges", () => {
+      const groups = searchGrouped(
+        "apple",
+        ["apple", "apply", "maple", "kiwi"],
+        [0.7, 0.9],
+        { limit: 100 }
+      );
+      expect(groups.map((g) => g.label)).toEqual([">=0.9", "0.7-0.9", "<0.7"]);
+      expect(groups.map((g) => g.results.map((r) => r.item))).toEqual([
+        ["apple"],
+        ["apply"],
+        ["maple", "kiwi"],
+      ]);
+      expect(groups[1].min).toBe(0.7);
+      expect(groups[0].max).toBeUndefined();
+    });
+  });
+
+  describe("searchGroupedByInitial", () => {
+    test("should group results by normalized first letter", () => {
+      const groups = searchGroupedByInitial(
+        "apple",
+        ["Apple", "maple", "7up", "Éclair", "apply", "-apple"],
+        { limit: 100 }
+      );
+      expect(groups.map((g) => g.initial)).toEqual(["#", "a", "e", "m"]);
+      expect(groups.map((g) => g.results.map((r) => r.item))).toEqual([
+        ["-apple", "7up"],
+        ["Apple", "apply"],
+        ["Éclair"],
+        ["maple"],
+      ]);
+      groups.forEach(({ results }) => {
+        const scores = results.map((r) => r.score);
+        expect(scores).toEqual([...scores].sort((a, b) => b - a));
+      });
+    });
+  });
+
+  describe("searchAny", () => {
+    test("should rank items by their best-matching query", () => {
+      const results = searchAny(["grape", "melon"], testData);
+      expect(results[0].item).toBe("grape");
+      expect(results[0].queryIndex).toBe(0);
+      expect(results[1].item).toBe("watermelon");
+      expect(results[1].queryIndex).toBe(1);
+    });
+
+    test("should apply threshold and limit to the best scores", () => {
+      const results = searchAny(["apple", "banana"], testData, {
+        threshold: 0.9,
+        limit: 1,
+      });
+      expect(results).toHaveLength(1);
+      expect(results[0].score).toBe(1.0);
+    });
+  });
+
+  describe("searchMulti", () => {
+    const items = ["car", "cart", "cat", "dog"];
+
+    test("should rank by the best query with max", () => {
+      const results = searchMulti(["cat", "car"], items, "max");
+      expect(results[0].item).toBe("car");
+      expect(results[0].score).toBe(1);
+      expect(results[0].queryIndex).toBe(1);
+    });
+
+    test("should rank by the worst query with min", () => {
+      const results = searchMulti(["cat", "car"], items, "min");
+      expect(results[0].item).toBe("cart");
+      expect(results[0].score).toBe(0.75);
+    });
+
+    test("should rank by the average score with mean", () => {
+      const results = searchMulti(["cat", "car"], items, "mean");
+      expect(results[0].item).toBe("car");
+      expect(results[0].score).toBeCloseTo(5 / 6, 10);
+      expect(results[0].queryIndex).toBeUndefined();
+    });
+
+    test("should reject an unknown aggregator", () => {
+      expect(() => searchMulti(["cat"], items, "sum")).toThrow();
+    });
+  });
+
+  describe("searchAliases", () => {
+    test("should match an alias and return the canonical name", () => {
+      const groups = [
+        ["United States", "USA", "US"],
+        ["United Kingdom", "UK", "Great Britain"],
+      ];
+      const [result] = searchAliases("great britan", groups, { limit: 1 });
+      expect(result.item).toBe("United Kingdom");
+      expect(result.index).toBe(1);
+      expect(result.aliasIndex).toBe(2);
+    });
+  });
+
+  describe("searchBy", () => {
+    const products = [
+      { name: "Desk lamp", description: "An apple-shaped light", price: 20 },
+      { name: "Apple", description: "A crisp fruit" },
+      { name: "Chair", description: 42 },
+    ];
+    const keys = [
+      { key: "name", weight: 1 },
+      { key: "description", weight: 0.5 },
+    ];
+
+    test("should weight name above description", () => {
+      const results = searchBy("apple", products, keys, {
+        exactSubstring: true,
+        threshold: 0.1,
+      });
+      expect(results).toEqual([
+        { index: 1, score: 1, field: "name" },
+        { index: 0, score: 0.5, field: "description" },
+      ]);
+    });
+
+    test("should only score the sampled records with sampleLimit", () => {
+      const records = Array.from({ length: 500 }, (_, i) => ({
+        name: `item ${i}`,
+      }));
+      const options = { sampleLimit: 50, sampleSeed: 3, limit: null };
+      const byKey = searchBy("item 1", records, [{ key: "name" }], options);
+      const items = records.map((r) => r.name);
+      const plain = search("item 1", items, options);
+      const sorted = (results) => results.map((r) => r.index).sort((a, b) => a - b);
+      expect(byKey.length).toBe(50);
+      expect(sorted(byKey)).toEqual(sorted(plain));
+    });
+
+    test("should add field scores with combineFields sum", () => {
+      const results = searchBy("a", products, keys, {
+        exactSubstring: true,
+        combineFields: "sum",
+      });
+      expect(results.map((r) => r.score)).toEqual([1.5, 1.5, 1]);
+      expect(results[2]).toEqual({ index: 2, score: 1, field: "name" });
+    });
+  });
+
+  describe("suggestThreshold", () => {
+    test("should cut between good and poor matches", () => {
+      const items = ["apple", "apply", "appl", "zebra", "xylophone", "quartz"];
+      const threshold = suggestThreshold("apple", items);
+      const kept = search("apple", items, { threshold }).map((r) => r.item);
+      expect(kept).toEqual(["apple", "apply", "appl"]);
+    });
+
+    test("should return 0 without a gap", () => {
+      expect(suggestThreshold("apple", ["apple", "apple"])).toBe(0);
+    });
+  });
+
+  describe("similarityMatrix", () => {
+    test("should be symmetric with ones on the diagonal", () => {
+      const items = ["apple", "apply", "zebra"];
+      const matrix = similarityMatrix(items);
+      expect(matrix.length).toBe(3);
+      items.forEach((_, i) => {
+        expect(matrix[i][i]).toBe(1);
+        items.forEach((_, j) => expect(matrix[i][j]).toBe(matrix[j][i]));
+      });
+      expect(matrix[0][1]).toBeCloseTo(0.8);
+    });
+
+    test("should zero out scores below threshold", () => {
+      const matrix = similarityMatrix(["apple", "zebra"], { threshold: 0.5 });
+      expect(matrix).toEqual([
+        [1, 0],
+        [0, 1],
+      ]);
+    });
+  });
+
+  describe("similarityPairs", () => {
+    test("should match the dense matrix filtered by threshold", () => {
+      const items = ["apple", "apply", "applet", "zebra", "zebras"];
+      const options = { threshold: 0.5, parallelThreshold: 0 };
+      const matrix = similarityMatrix(items, options);
+      const expected = [];
+      items.forEach((_, i) => {
+        items.forEach((_, j) => {
+          if (i < j && matrix[i][j] > 0) {
+            expected.push({ i, j, score: matrix[i][j] });
+          }
+        });
+      });
+      expect(expected.length).toBeGreaterThan(0);
+      expect(similarityPairs(items, options)).toEqual(expected);
+    });
+
+    test("should list every pair once without a threshold", () => {
+      const pairs = similarityPairs(["a", "b", "c"]);
+      expect(pairs.map(({ i, j }) => [i, j])).toEqual([
+        [0, 1],
+        [0, 2],
+        [1, 2],
+      ]);
+    });
+  });
+
+  describe("isMatch", () => {
+    test("should agree with fuzzy at the threshold", () => {
+      expect(fuzzy("apple", "appla")).toBe(0.8);
+      expect(isMatch("apple", "appla", 0.8)).toBe(true);
+      expect(isMatch("apple", "appla", 0.80001)).toBe(false);
+      expect(isMatch("kitten", "sitting", 0)).toBe(true);
+      expect(isMatch("kitten", "sitting", 1)).toBe(false);
+    });
+
+    test("should apply search options", () => {
+      expect(isMatch("Café", "cafe", 1)).toBe(true);
+      expect(isMatch("Café", "cafe", 1, { ignoreCase: false })).toBe(false);
+      expect(isMatch("ten", "kitten", 0.9)).toBe(false);
+      expect(isMatch("ten", "kitten", 0.9, { boostSuffix: 0.5 })).toBe(true);
+    });
   });
 
   describe("fuzzy", () => {
@@ -67,5 +1024,197 @@ describe("Fast Fuzzy Search", () => {
       const score = fuzzy("café", "cafe", true);
       expect(score).toBe(1.0);
     });
+
+    test("should accept the same options as search", () => {
+      expect(fuzzy("Café", "Cafe", { ignoreCase: false })).toBe(1.0);
+      expect(fuzzy("Café", "cafe", { ignoreCase: false })).toBe(0.75);
+      expect(fuzzy("CAFÉ", "café", { normalize: false })).toBe(1.0);
+      expect(fuzzy("form", "from", { algorithm: "osa" })).toBe(0.75);
+      expect(fuzzy("form", "from", {})).toBe(search("form", ["from"])[0].score);
+    });
+  });
+
+  describe("score", () => {
+    test("should reproduce the scores search returns", () => {
+      const items = ["Café au lait", "cafeteria", "report.pdf"];
+      for (const options of [
+        {},
+        { algorithm: "osa", prefixBoostWeight: 0.1, precision: 3 },
+        { boostSuffix: 0.3, lengthPenalty: 0.5 },
+      ]) {
+        const all = { ...options, threshold: 0, limit: null };
+        for (const result of search("cafe", items, all)) {
+          expect(score("cafe", result.item, all)).toBe(result.score);
+        }
+      }
+    });
+
+    test("should score items search skips as zero", () => {
+      expect(score("apple", "banana", { maxDistance: 1 })).toBe(0);
+      expect(score("apple", "apple", { threshold: 1 })).toBe(1);
+    });
+  });
+
+  describe("scoreDetailed", () => {
+    test("should report distance 0 for identical strings", () => {
+      expect(scoreDetailed("Apple", "apple")).toEqual({
+        distance: 0,
+        similarity: 1,
+      });
+    });
+
+    test("should report the distance of empty and typical pairs", () => {
+      expect(scoreDetailed("", "abc")).toEqual({ distance: 3, similarity: 0 });
+      const { distance, similarity } = scoreDetailed("kitten", "sitting");
+      expect(distance).toBe(3);
+      expect(similarity).toBe(fuzzy("kitten", "sitting", {}));
+    });
+  });
+
+  describe("similarity", () => {
+    test("should match fuzzy without normalization", () => {
+      expect(similarity("kitten", "sitting")).toBeCloseTo(1 - 3 / 7);
+      expect(similarity("Café", "cafe")).toBe(fuzzy("Café", "cafe", false));
+    });
+
+    test("should handle equal and empty strings", () => {
+      expect(similarity("", "")).toBe(1.0);
+      expect(similarity("same", "same")).toBe(1.0);
+      expect(similarity("abc", "")).toBe(0.0);
+    });
+  });
+
+  describe("fuzzyBytes", () => {
+    test("should score bytes like similarity scores ASCII", () => {
+      const a = Buffer.from("kitten");
+      const b = Buffer.from("sitting");
+      expect(fuzzyBytes(a, b)).toBe(similarity("kitten", "sitting"));
+      expect(fuzzyBytes(Buffer.alloc(0), Buffer.alloc(0))).toBe(1.0);
+      expect(fuzzyBytes(a, Buffer.alloc(0))).toBe(0.0);
+    });
+
+    test("should compare invalid UTF-8 byte by byte", () => {
+      const a = Buffer.from([0xff, 0xfe, 0x80, 0x00]);
+      const b = Buffer.from([0xff, 0xfe, 0x81, 0x00]);
+      expect(fuzzyBytes(a, b)).toBe(0.75);
+      // Lossy decoding would turn both into the same replacement chars.
+      const c = Buffer.from([0xfd, 0xfc, 0x81, 0x00]);
+      expect(a.toString()).toBe(c.toString());
+      expect(fuzzyBytes(a, c)).toBe(0.25);
+    });
+
+    test("should not normalize", () => {
+      const upper = Buffer.from("CAFE");
+      expect(fuzzyBytes(upper, Buffer.from("cafe"))).toBe(0.0);
+      // "é" is two bytes in UTF-8.
+      expect(fuzzyBytes(Buffer.from("café"), Buffer.from("cafe"))).toBe(0.6);
+    });
+  });
+
+  describe("normalizeAscii", () => {
+    test("should lowercase and collapse whitespace", () => {
+      expect(normalizeAscii("  Hello\tWORLD\n")).toBe("hello world");
+      expect(normalizeAscii("Hello  World", false)).toBe("Hello World");
+      expect(normalizeAscii("")).toBe("");
+    });
+
+    test("should leave non-ASCII characters alone", () => {
+      expect(normalizeAscii("CAFÉ")).toBe("cafÉ");
+    });
+  });
+
+  describe("setNormalizationCacheSize", () => {
+    test("should never change results with cacheNormalization", () => {
+      const items = ["Crème Brûlée", "CRÈME", "creme"];
+      const expected = search("creme", items);
+      for (const size of [0, 1, 100]) {
+        setNormalizationCacheSize(size);
+        const options = { cacheNormalization: true };
+        expect(search("creme", items, options)).toEqual(expected);
+        expect(search("creme", items, options)).toEqual(expected);
+      }
+    });
+  });
+
+  describe("Searcher", () => {
+    test("should return the same results as search", () => {
+      const searcher = new Searcher(testData);
+      expect(searcher.query("app")).toEqual(search("app", testData));
+    });
+
+    test("should answer repeated queries from the cache", () => {
+      const searcher = new Searcher(testData);
+      searcher.query("apple");
+      searcher.query("apple");
+      expect(searcher.cacheHits).toBe(1);
+    });
+
+    test("should match brute force when using a trigram index", () => {
+      const indexed = new Searcher(testData, { minSharedGrams: 1 });
+      const brute = new Searcher(testData);
+      for (const query of ["apple", "aple", "grap", "melon"]) {
+        const options = { threshold: 0.5 };
+        expect(indexed.query(query, options)).toEqual(
+          brute.query(query, options)
+        );
+      }
+    });
+
+    test("should search across batches added with addItems", () => {
+      const searcher = new Searcher(["apple", "banana"]);
+      searcher.addItems(["cherry", "grape"]);
+      const cherry = searcher.query("cherry")[0];
+      expect(cherry.item).toBe("cherry");
+      expect(cherry.index).toBe(2);
+      expect(searcher.query("apple")[0].index).toBe(0);
+    });
+
+    test("should leave removed items out of results", () => {
+      const searcher = new Searcher(["apple", "apply", "banana"], {
+        minSharedGrams: 1,
+      });
+      expect(searcher.removeItem(0)).toBe(true);
+      expect(searcher.removeItem(0)).toBe(false);
+      const results = searcher.query("apple");
+      expect(results.map((r) => r.index)).not.toContain(0);
+      expect(results[0].index).toBe(1);
+
+      searcher.clear();
+      expect(searcher.query("apple")).toEqual([]);
+    });
+
+    test("should replace an item in place with update", () => {
+      const searcher = new Searcher(["apple", "banana", "cherry"], {
+        minSharedGrams: 1,
+      });
+      searcher.query("banana");
+      expect(searcher.update(1, "grape")).toBe(true);
+      expect(searcher.update(5, "kiwi")).toBe(false);
+      expect(searcher.query("banana").map((r) => r.index)).not.toContain(1);
+      const grape = searcher.query("grape")[0];
+      expect(grape.item).toBe("grape");
+      expect(grape.index).toBe(1);
+    });
+
+    test("should search the new corpus after setItems", () => {
+      const searcher = new Searcher(testData);
+      searcher.query("kiwi");
+      searcher.setItems(["kiwi", "lemon"]);
+      const results = searcher.query("kiwi");
+      expect(results[0].item).toBe("kiwi");
+      expect(searcher.cacheHits).toBe(0);
+    });
+
+    test("should list items starting with the query in prefixSearch", () => {
+      const words = ["cartoon", "car", "cat", "Care", "scar", "dog"];
+      for (const prefixIndex of [false, true]) {
+        const searcher = new Searcher(words, { prefixIndex });
+        const results = searcher.prefixSearch("CAR");
+        expect(results.map((r) => r.item)).toEqual(["car", "Care", "cartoon"]);
+        expect(results[0].score).toBe(1.0);
+        expect(searcher.prefixSearch("ca", { limit: 1 })[0].item).toBe("car");
+        expect(searcher.prefixSearch("x")).toEqual([]);
+      }
+    });
   });
 });